@@ -4,7 +4,7 @@ use super::*;
 #[jsonrpc_derive::rpc(server)]
 pub trait Api {
   #[rpc(name = "getblockchaininfo")]
-  fn get_blockchain_info(&self) -> Result<GetBlockchainInfoResult, jsonrpc_core::Error>;
+  fn get_blockchain_info(&self) -> Result<Value, jsonrpc_core::Error>;
 
   #[rpc(name = "getnetworkinfo")]
   fn get_network_info(&self) -> Result<GetNetworkInfoResult, jsonrpc_core::Error>;
@@ -117,6 +117,12 @@ pub trait Api {
     include_watchonly: Option<bool>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getmempoolentry")]
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error>;
+
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
   #[rpc(name = "getrawtransaction")]
   fn get_raw_transaction(
     &self,
@@ -194,12 +200,18 @@ pub trait Api {
   #[rpc(name = "listwalletdir")]
   fn list_wallet_dir(&self) -> Result<ListWalletDirResult, jsonrpc_core::Error>;
 
+  #[rpc(name = "getaddressinfo")]
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
   #[rpc(name = "walletprocesspsbt")]
   fn wallet_process_psbt(
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error>;
 
@@ -208,5 +220,5 @@ pub trait Api {
     &self,
     psbt: String,
     extract: Option<bool>,
-  ) -> Result<FinalizePsbtResult, jsonrpc_core::Error>;
+  ) -> Result<Value, jsonrpc_core::Error>;
 }
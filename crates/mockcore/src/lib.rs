@@ -21,14 +21,15 @@ use {
   },
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, EstimateMode, FeeRatePercentiles,
-    FinalizePsbtResult, GetBalancesResult, GetBalancesResultEntry, GetBlockHeaderResult,
-    GetBlockStatsResult, GetBlockchainInfoResult, GetDescriptorInfoResult, GetNetworkInfoResult,
-    GetRawTransactionResult, GetRawTransactionResultVout, GetRawTransactionResultVoutScriptPubKey,
-    GetTransactionResult, GetTransactionResultDetail, GetTransactionResultDetailCategory,
-    GetTxOutResult, GetWalletInfoResult, ImportDescriptors, ImportMultiResult,
-    ListTransactionResult, ListUnspentResultEntry, ListWalletDirItem, ListWalletDirResult,
-    LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult, Timestamp,
-    WalletProcessPsbtResult, WalletTxInfo,
+    GetBalancesResult, GetBalancesResultEntry, GetBlockHeaderResult, GetBlockStatsResult,
+    GetBlockchainInfoResult, GetDescriptorInfoResult, GetMempoolEntryResult,
+    GetMempoolEntryResultFees, GetNetworkInfoResult, GetRawTransactionResult,
+    GetRawTransactionResultVout, GetRawTransactionResultVoutScriptPubKey, GetTransactionResult,
+    GetTransactionResultDetail, GetTransactionResultDetailCategory, GetTxOutResult,
+    GetWalletInfoResult, ImportDescriptors, ImportMultiResult, ListTransactionResult,
+    ListUnspentResultEntry, ListWalletDirItem, ListWalletDirResult, LoadWalletResult,
+    SignRawTransactionInput, SignRawTransactionResult, Timestamp, WalletProcessPsbtResult,
+    WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -74,6 +75,7 @@ pub fn builder() -> Builder {
   Builder {
     fail_lock_unspent: false,
     network: Network::Bitcoin,
+    testnet4: false,
     version: 250000,
   }
 }
@@ -81,6 +83,7 @@ pub fn builder() -> Builder {
 pub struct Builder {
   fail_lock_unspent: bool,
   network: Network,
+  testnet4: bool,
   version: usize,
 }
 
@@ -96,6 +99,14 @@ impl Builder {
     Self { network, ..self }
   }
 
+  pub fn testnet4(self) -> Self {
+    Self {
+      network: Network::Testnet,
+      testnet4: true,
+      ..self
+    }
+  }
+
   pub fn version(self, version: usize) -> Self {
     Self { version, ..self }
   }
@@ -103,6 +114,7 @@ impl Builder {
   pub fn build(self) -> Handle {
     let state = Arc::new(Mutex::new(State::new(
       self.network,
+      self.testnet4,
       self.version,
       self.fail_lock_unspent,
     )));
@@ -344,7 +356,13 @@ impl Handle {
   }
 
   pub fn network(&self) -> String {
-    match self.state().network {
+    let state = self.state();
+
+    if state.testnet4 {
+      return "testnet4".into();
+    }
+
+    match state.network {
       Network::Bitcoin => "mainnet".to_string(),
       Network::Testnet => Network::Testnet.to_string(),
       Network::Signet => Network::Signet.to_string(),
@@ -48,8 +48,8 @@ impl Api for Server {
     }
   }
 
-  fn get_blockchain_info(&self) -> Result<GetBlockchainInfoResult, jsonrpc_core::Error> {
-    Ok(GetBlockchainInfoResult {
+  fn get_blockchain_info(&self) -> Result<Value, jsonrpc_core::Error> {
+    let mut info = serde_json::to_value(GetBlockchainInfoResult {
       chain: self.network,
       blocks: 0,
       headers: 0,
@@ -67,6 +67,14 @@ impl Api for Server {
       softforks: HashMap::new(),
       warnings: StringOrStringArray::String(String::new()),
     })
+    .unwrap();
+
+    // rust-bitcoin has no testnet4 network, so report its chain name directly
+    if self.state().testnet4 {
+      info["chain"] = "testnet4".into();
+    }
+
+    Ok(info)
   }
 
   fn get_network_info(&self) -> Result<GetNetworkInfoResult, jsonrpc_core::Error> {
@@ -280,7 +288,9 @@ impl Api for Server {
   }
 
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
-    if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
+    let state = self.state();
+
+    if let Some(wallet_name) = state.loaded_wallets.first().cloned() {
       Ok(GetWalletInfoResult {
         avoid_reuse: None,
         balance: Amount::from_sat(0),
@@ -290,7 +300,7 @@ impl Api for Server {
         keypool_size: 0,
         keypool_size_hd_internal: 0,
         pay_tx_fee: Amount::from_sat(0),
-        private_keys_enabled: true,
+        private_keys_enabled: !state.watch_only.contains(&wallet_name),
         scanning: None,
         tx_count: 0,
         unconfirmed_balance: Amount::from_sat(0),
@@ -340,12 +350,19 @@ impl Api for Server {
   fn create_wallet(
     &self,
     name: String,
-    _disable_private_keys: Option<bool>,
+    disable_private_keys: Option<bool>,
     _blank: Option<bool>,
     _passphrase: Option<String>,
     _avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error> {
-    self.state().wallets.insert(name.clone());
+    let mut state = self.state();
+
+    state.wallets.insert(name.clone());
+
+    if disable_private_keys.unwrap_or_default() {
+      state.watch_only.insert(name.clone());
+    }
+
     Ok(LoadWalletResult {
       name,
       warning: None,
@@ -559,6 +576,8 @@ impl Api for Server {
       }
     }
 
+    state.evict_conflicts(&tx);
+
     state.mempool.push(tx.clone());
 
     Ok(tx.compute_txid().to_string())
@@ -682,6 +701,84 @@ impl Api for Server {
     )
   }
 
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let Some(tx) = state.mempool().iter().find(|tx| tx.compute_txid() == txid) else {
+      return Err(jsonrpc_core::Error::new(
+        jsonrpc_core::types::error::ErrorCode::ServerError(-5),
+      ));
+    };
+
+    let ancestors = state.mempool_relatives(txid, true);
+    let descendants = state.mempool_relatives(txid, false);
+
+    let fee = state.fee(tx);
+    let vsize = u64::try_from(tx.vsize()).unwrap();
+
+    let package = |relatives: &[&Transaction]| {
+      relatives.iter().fold((fee, vsize), |(fee, vsize), tx| {
+        (
+          fee + state.fee(tx),
+          vsize + u64::try_from(tx.vsize()).unwrap(),
+        )
+      })
+    };
+
+    let (ancestor_fee, ancestor_size) = package(&ancestors);
+    let (descendant_fee, descendant_size) = package(&descendants);
+
+    Ok(GetMempoolEntryResult {
+      vsize,
+      weight: Some(tx.weight().to_wu()),
+      time: 0,
+      height: u64::try_from(state.hashes.len() - 1).unwrap(),
+      descendant_count: u64::try_from(descendants.len()).unwrap() + 1,
+      descendant_size,
+      ancestor_count: u64::try_from(ancestors.len()).unwrap() + 1,
+      ancestor_size,
+      wtxid: Txid::from_raw_hash(tx.compute_wtxid().to_raw_hash()),
+      fees: GetMempoolEntryResultFees {
+        base: fee,
+        modified: fee,
+        ancestor: ancestor_fee,
+        descendant: descendant_fee,
+      },
+      depends: ancestors
+        .iter()
+        .map(|ancestor| ancestor.compute_txid())
+        .filter(|ancestor| {
+          tx.input
+            .iter()
+            .any(|txin| txin.previous_output.txid == *ancestor)
+        })
+        .collect(),
+      spent_by: descendants
+        .iter()
+        .filter(|descendant| {
+          descendant
+            .input
+            .iter()
+            .any(|txin| txin.previous_output.txid == txid)
+        })
+        .map(|descendant| descendant.compute_txid())
+        .collect(),
+      bip125_replaceable: tx.is_explicitly_rbf(),
+      unbroadcast: Some(false),
+    })
+  }
+
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(
+      self
+        .state()
+        .mempool()
+        .iter()
+        .map(|tx| tx.compute_txid())
+        .collect(),
+    )
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -701,7 +798,7 @@ impl Api for Server {
     let blockhash = tx_height.map(|tx_height| state.hashes[usize::try_from(*tx_height).unwrap()]);
 
     if verbose.unwrap_or(false) {
-      match state.transactions.get(&txid) {
+      match state.transaction(txid) {
         Some(transaction) => Ok(
           serde_json::to_value(GetRawTransactionResult {
             in_active_chain: Some(true),
@@ -740,7 +837,7 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state.transaction(txid) {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -982,15 +1079,37 @@ impl Api for Server {
     })
   }
 
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let address = address.require_network(self.network).unwrap();
+
+    Ok(serde_json::json!({
+      "address": address,
+      "scriptPubKey": address.script_pubkey(),
+      "ismine": self.state().is_wallet_address(&address),
+      "iswatchonly": false,
+      "labels": [],
+    }))
+  }
+
   fn wallet_process_psbt(
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
-    bip32derivs: Option<bool>,
+    sighash_type: Option<String>,
+    _bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
-    assert!(sighash_type.is_none());
-    assert!(bip32derivs.is_none());
+    let signature = match sighash_type.as_deref() {
+      None | Some("ALL") => vec![0; 64],
+      Some("SINGLE|ANYONECANPAY") => {
+        let mut signature = vec![0; 64];
+        signature.push(TapSighashType::SinglePlusAnyoneCanPay as u8);
+        signature
+      }
+      Some(sighash_type) => panic!("sighash_type {sighash_type} not supported"),
+    };
 
     let mut psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
@@ -1000,21 +1119,28 @@ impl Api for Server {
     .unwrap();
 
     for (i, txin) in psbt.unsigned_tx.input.iter().enumerate() {
-      psbt.inputs[i].witness_utxo = Some(
-        self
+      if psbt.inputs[i].witness_utxo.is_none() {
+        psbt.inputs[i].witness_utxo = self
           .state()
-          .transactions
-          .get(&txin.previous_output.txid)
-          .unwrap()
-          .output[txin.previous_output.vout as usize]
-          .clone(),
-      );
+          .transaction(txin.previous_output.txid)
+          .map(|tx| tx.output[txin.previous_output.vout as usize].clone());
+      }
     }
 
+    let watch_only = {
+      let state = self.state();
+      state
+        .loaded_wallets
+        .first()
+        .is_some_and(|wallet| state.watch_only.contains(wallet))
+    };
+
     if let Some(sign) = sign {
-      if sign {
+      if sign && !watch_only {
         for input in psbt.inputs.iter_mut() {
-          input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+          if input.final_script_witness.is_none() {
+            input.final_script_witness = Some(Witness::from_slice(&[&signature]));
+          }
         }
       }
     }
@@ -1029,25 +1155,34 @@ impl Api for Server {
     &self,
     psbt: String,
     _extract: Option<bool>,
-  ) -> Result<FinalizePsbtResult, jsonrpc_core::Error> {
-    let mut transaction = Psbt::deserialize(
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
         .unwrap(),
     )
-    .unwrap()
-    .unsigned_tx;
+    .unwrap();
 
-    for input in &mut transaction.input {
-      if input.witness.is_empty() {
-        input.witness = Witness::from_slice(&[&[0; 64]]);
-      }
+    if psbt
+      .inputs
+      .iter()
+      .any(|input| input.final_script_witness.is_none())
+    {
+      return Ok(serde_json::json!({
+        "psbt": base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+        "complete": false,
+      }));
     }
 
-    Ok(FinalizePsbtResult {
-      psbt: None,
-      hex: Some(serialize(&transaction)),
-      complete: true,
-    })
+    let mut transaction = psbt.unsigned_tx;
+
+    for (input, psbt_input) in transaction.input.iter_mut().zip(psbt.inputs) {
+      input.witness = psbt_input.final_script_witness.unwrap();
+    }
+
+    Ok(serde_json::json!({
+      "hex": hex::encode(serialize(&transaction)),
+      "complete": true,
+    }))
   }
 }
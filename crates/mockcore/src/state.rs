@@ -10,11 +10,13 @@ pub struct State {
   pub locked: BTreeSet<OutPoint>,
   pub mempool: Vec<Transaction>,
   pub network: Network,
+  pub testnet4: bool,
   pub nonce: u32,
   pub transactions: BTreeMap<Txid, Transaction>,
   pub txid_to_block_height: BTreeMap<Txid, u32>,
   pub utxos: BTreeMap<OutPoint, Amount>,
   pub version: usize,
+  pub watch_only: BTreeSet<String>,
   pub receive_addresses: Vec<Address>,
   pub change_addresses: Vec<Address>,
   pub wallets: BTreeSet<String>,
@@ -22,11 +24,20 @@ pub struct State {
 }
 
 impl State {
-  pub(crate) fn new(network: Network, version: usize, fail_lock_unspent: bool) -> Self {
+  pub(crate) fn new(
+    network: Network,
+    testnet4: bool,
+    version: usize,
+    fail_lock_unspent: bool,
+  ) -> Self {
     let mut hashes = Vec::new();
     let mut blocks = BTreeMap::new();
 
-    let genesis_block = bitcoin::blockdata::constants::genesis_block(network);
+    let genesis_block = if testnet4 {
+      Self::testnet4_genesis_block()
+    } else {
+      bitcoin::blockdata::constants::genesis_block(network)
+    };
     let genesis_block_hash = genesis_block.block_hash();
     hashes.push(genesis_block_hash);
     blocks.insert(genesis_block_hash, genesis_block);
@@ -42,16 +53,60 @@ impl State {
       mempool: Vec::new(),
       network,
       nonce: 0,
+      testnet4,
       receive_addresses: Vec::new(),
       transactions: BTreeMap::new(),
       txid_to_block_height: BTreeMap::new(),
       utxos: BTreeMap::new(),
       version,
+      watch_only: BTreeSet::new(),
       wallets: BTreeSet::new(),
       wallet: Wallet::new(network),
     }
   }
 
+  fn testnet4_genesis_block() -> Block {
+    let coinbase = Transaction {
+      version: Version::ONE,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: script::Builder::new()
+          .push_int(486604799)
+          .push_slice([4])
+          .push_slice(
+            <&script::PushBytes>::try_from(
+              b"03/May/2024 000000000000000000001ebd58c244970b3aa9d783bb001011fbe8ea8e98e00e"
+                .as_slice(),
+            )
+            .unwrap(),
+          )
+          .into_script(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: Amount::from_int_btc(50),
+        script_pubkey: script::Builder::new()
+          .push_slice([0; 33])
+          .push_opcode(bitcoin::opcodes::all::OP_CHECKSIG)
+          .into_script(),
+      }],
+    };
+
+    Block {
+      header: Header {
+        version: bitcoin::block::Version::ONE,
+        prev_blockhash: BlockHash::all_zeros(),
+        merkle_root: coinbase.compute_txid().to_raw_hash().into(),
+        time: 1714777860,
+        bits: CompactTarget::from_consensus(0x1d00ffff),
+        nonce: 393743547,
+      },
+      txdata: vec![coinbase],
+    }
+  }
+
   pub(crate) fn new_address(&mut self, change: bool) -> Address {
     let address = self.wallet.new_address();
 
@@ -70,7 +125,12 @@ impl State {
   }
 
   pub(crate) fn clear(&mut self) {
-    *self = Self::new(self.network, self.version, self.fail_lock_unspent);
+    *self = Self::new(
+      self.network,
+      self.testnet4,
+      self.version,
+      self.fail_lock_unspent,
+    );
   }
 
   #[track_caller]
@@ -285,6 +345,94 @@ impl State {
     &self.mempool
   }
 
+  pub(crate) fn transaction(&self, txid: Txid) -> Option<&Transaction> {
+    self
+      .transactions
+      .get(&txid)
+      .or_else(|| self.mempool.iter().find(|tx| tx.compute_txid() == txid))
+  }
+
+  pub(crate) fn fee(&self, tx: &Transaction) -> Amount {
+    tx.input
+      .iter()
+      .map(|txin| {
+        self.transaction(txin.previous_output.txid).unwrap().output
+          [usize::try_from(txin.previous_output.vout).unwrap()]
+        .value
+      })
+      .sum::<Amount>()
+      - tx.output.iter().map(|txout| txout.value).sum::<Amount>()
+  }
+
+  /// Returns the mempool transactions related to `txid`, following inputs to
+  /// parents if `ancestors` is true, or outputs to children otherwise.
+  /// The result does not include the transaction itself.
+  pub(crate) fn mempool_relatives(&self, txid: Txid, ancestors: bool) -> Vec<&Transaction> {
+    let mut relatives = Vec::<&Transaction>::new();
+    let mut queue = vec![txid];
+
+    while let Some(txid) = queue.pop() {
+      let parents = self
+        .mempool
+        .iter()
+        .find(|tx| tx.compute_txid() == txid)
+        .map(|tx| {
+          tx.input
+            .iter()
+            .map(|txin| txin.previous_output.txid)
+            .collect::<Vec<Txid>>()
+        })
+        .unwrap_or_default();
+
+      for tx in &self.mempool {
+        let related = if ancestors {
+          parents.contains(&tx.compute_txid())
+        } else {
+          tx.input
+            .iter()
+            .any(|txin| txin.previous_output.txid == txid)
+        };
+
+        if related && !relatives.contains(&tx) {
+          relatives.push(tx);
+          queue.push(tx.compute_txid());
+        }
+      }
+    }
+
+    relatives
+  }
+
+  /// Removes mempool transactions that spend any of the same outputs as `tx`,
+  /// along with their descendants, as a replacement would.
+  pub(crate) fn evict_conflicts(&mut self, tx: &Transaction) {
+    let conflicts = self
+      .mempool
+      .iter()
+      .filter(|mempool_tx| {
+        mempool_tx.input.iter().any(|a| {
+          tx.input
+            .iter()
+            .any(|b| a.previous_output == b.previous_output)
+        })
+      })
+      .map(|conflict| conflict.compute_txid())
+      .collect::<Vec<Txid>>();
+
+    let mut evicted = BTreeSet::new();
+
+    for txid in conflicts {
+      evicted.insert(txid);
+      for descendant in self.mempool_relatives(txid, false) {
+        evicted.insert(descendant.compute_txid());
+      }
+    }
+
+    self
+      .mempool
+      .retain(|mempool_tx| !evicted.contains(&mempool_tx.compute_txid()));
+  }
+
   pub(crate) fn get_confirmations(&self, tx: &Transaction) -> i32 {
     for (confirmations, hash) in self.hashes.iter().rev().enumerate() {
       if self.blocks.get(hash).unwrap().txdata.contains(tx) {
@@ -1,5 +1,7 @@
 use {
   super::*,
+  crate::index::event::Event,
+  bitcoin::hashes::sha256,
   serde_hex::{SerHex, Strict},
 };
 
@@ -95,15 +97,19 @@ pub struct Inscription {
   pub charms: Vec<Charm>,
   pub child_count: u64,
   pub children: Vec<InscriptionId>,
+  pub content_duplicates: Option<u64>,
+  pub content_hash: Option<sha256::Hash>,
   pub content_length: Option<usize>,
   pub content_type: Option<String>,
   pub effective_content_type: Option<String>,
   pub fee: u64,
+  pub first_with_content: Option<InscriptionId>,
   pub height: u32,
   pub id: InscriptionId,
   pub next: Option<InscriptionId>,
   pub number: i32,
   pub parents: Vec<InscriptionId>,
+  pub pending_transfer: Option<Txid>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<ordinals::Sat>,
@@ -113,6 +119,13 @@ pub struct Inscription {
   pub metaprotocol: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistory {
+  pub history: Vec<InscriptionTransfer>,
+  pub more: bool,
+  pub page_index: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
@@ -144,6 +157,15 @@ pub struct ChildInscriptionRecursive {
   pub timestamp: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionTransfer {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: SatPoint,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
@@ -151,12 +173,33 @@ pub struct Inscriptions {
   pub page_index: u32,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mempool {
+  pub transactions: Vec<MempoolTransaction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MempoolInscription {
+  pub id: InscriptionId,
+  pub new_satpoint: Option<SatPoint>,
+  pub old_satpoint: Option<SatPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MempoolTransaction {
+  pub inscriptions: Vec<MempoolInscription>,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub runestone: Option<Artifact>,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Vec<InscriptionId>,
   pub outpoint: OutPoint,
+  pub pending_spend: Option<Txid>,
   pub runes: BTreeMap<SpacedRune, Pile>,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub script_pubkey: ScriptBuf,
@@ -184,6 +227,7 @@ impl Output {
       indexed,
       inscriptions,
       outpoint,
+      pending_spend: None,
       runes,
       sat_ranges,
       script_pubkey: tx_out.script_pubkey,
@@ -194,6 +238,72 @@ impl Output {
   }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuneActivity {
+  pub amount: u128,
+  pub from: Vec<OutPoint>,
+  pub height: u32,
+  pub kind: RuneActivityKind,
+  pub to: Option<OutPoint>,
+  pub txid: Txid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneActivityKind {
+  Burn,
+  Mint,
+  Transfer,
+}
+
+impl Display for RuneActivityKind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Burn => "burn",
+        Self::Mint => "mint",
+        Self::Transfer => "transfer",
+      }
+    )
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneActivities {
+  pub activity: Vec<RuneActivity>,
+  pub more: bool,
+  pub page_index: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub balance: Pile,
+  pub script_pubkey: ScriptBuf,
+}
+
+impl RuneHolder {
+  pub fn new(chain: Chain, script_pubkey: ScriptBuf, balance: Pile) -> Self {
+    Self {
+      address: chain
+        .address_from_script(&script_pubkey)
+        .ok()
+        .map(|address| uncheck(&address)),
+      balance,
+      script_pubkey,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolders {
+  pub holders: Vec<RuneHolder>,
+  pub more: bool,
+  pub page_index: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub address: Option<String>,
@@ -226,6 +336,13 @@ pub struct SatInscriptions {
   pub page: u64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressHistory {
+  pub history: Vec<AddressTransaction>,
+  pub more: bool,
+  pub page_index: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddressInfo {
   pub outputs: Vec<OutPoint>,
@@ -233,3 +350,45 @@ pub struct AddressInfo {
   pub sat_balance: u64,
   pub runes_balances: Vec<(SpacedRune, Decimal, Option<char>)>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Events {
+  pub events: Vec<JournaledEvent>,
+  pub more: bool,
+  pub page_index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournaledEvent {
+  pub event: Event,
+  pub height: u32,
+  pub sequence_number: u32,
+  pub tx_index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressTransaction {
+  pub height: u32,
+  pub inscriptions_received: Vec<InscriptionId>,
+  pub inscriptions_sent: Vec<InscriptionId>,
+  pub received: u64,
+  pub runes_received: BTreeMap<RuneId, u128>,
+  pub runes_sent: BTreeMap<RuneId, u128>,
+  pub sent: u64,
+  pub txid: Txid,
+}
+
+impl AddressTransaction {
+  pub fn new(height: u32, txid: Txid) -> Self {
+    Self {
+      height,
+      inscriptions_received: Vec::new(),
+      inscriptions_sent: Vec::new(),
+      received: 0,
+      runes_received: BTreeMap::new(),
+      runes_sent: BTreeMap::new(),
+      sent: 0,
+      txid,
+    }
+  }
+}
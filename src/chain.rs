@@ -1,4 +1,14 @@
-use {super::*, clap::ValueEnum};
+use {
+  super::*,
+  bitcoin::{
+    block::{self, Header},
+    opcodes,
+    p2p::Magic,
+    pow::CompactTarget,
+    script::PushBytes,
+  },
+  clap::ValueEnum,
+};
 
 #[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -8,6 +18,7 @@ pub enum Chain {
   Mainnet,
   #[value(alias("test"))]
   Testnet,
+  Testnet4,
   Signet,
   Regtest,
 }
@@ -23,13 +34,14 @@ impl Chain {
       Self::Regtest => 18443,
       Self::Signet => 38332,
       Self::Testnet => 18332,
+      Self::Testnet4 => 48332,
     }
   }
 
   pub(crate) fn inscription_content_size_limit(self) -> Option<usize> {
     match self {
       Self::Mainnet | Self::Regtest => None,
-      Self::Testnet | Self::Signet => Some(1024),
+      Self::Testnet | Self::Testnet4 | Self::Signet => Some(1024),
     }
   }
 
@@ -39,11 +51,29 @@ impl Chain {
       Self::Regtest => 0,
       Self::Signet => 112402,
       Self::Testnet => 2413343,
+      Self::Testnet4 => 0,
     }
   }
 
   pub(crate) fn first_rune_height(self) -> u32 {
-    Rune::first_rune_height(self.into())
+    Rune::first_rune_height(self.rune_network())
+  }
+
+  pub(crate) fn minimum_rune_at_height(self, height: Height) -> Rune {
+    Rune::minimum_at_height(self.rune_network(), height)
+  }
+
+  pub(crate) fn rune_unlock_height(self, rune: Rune) -> Option<Height> {
+    rune.unlock_height(self.rune_network())
+  }
+
+  // rust-bitcoin has no testnet4 network, and testnet4, like signet, unlocks
+  // runes starting at genesis
+  fn rune_network(self) -> Network {
+    match self {
+      Self::Testnet4 => Network::Signet,
+      _ => self.network(),
+    }
   }
 
   pub(crate) fn jubilee_height(self) -> u32 {
@@ -52,11 +82,64 @@ impl Chain {
       Self::Regtest => 110,
       Self::Signet => 175392,
       Self::Testnet => 2544192,
+      Self::Testnet4 => 0,
     }
   }
 
   pub(crate) fn genesis_block(self) -> Block {
-    bitcoin::blockdata::constants::genesis_block(self.network())
+    match self {
+      Self::Testnet4 => Self::testnet4_genesis_block(),
+      _ => bitcoin::blockdata::constants::genesis_block(self.network()),
+    }
+  }
+
+  fn testnet4_genesis_block() -> Block {
+    let coinbase = Transaction {
+      version: Version::ONE,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: script::Builder::new()
+          .push_int(486604799)
+          .push_slice([4])
+          .push_slice(
+            <&PushBytes>::try_from(
+              b"03/May/2024 000000000000000000001ebd58c244970b3aa9d783bb001011fbe8ea8e98e00e"
+                .as_slice(),
+            )
+            .unwrap(),
+          )
+          .into_script(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: Amount::from_int_btc(50),
+        script_pubkey: script::Builder::new()
+          .push_slice([0; 33])
+          .push_opcode(opcodes::all::OP_CHECKSIG)
+          .into_script(),
+      }],
+    };
+
+    Block {
+      header: Header {
+        version: block::Version::ONE,
+        prev_blockhash: BlockHash::all_zeros(),
+        merkle_root: coinbase.compute_txid().to_raw_hash().into(),
+        time: 1714777860,
+        bits: CompactTarget::from_consensus(0x1d00ffff),
+        nonce: 393743547,
+      },
+      txdata: vec![coinbase],
+    }
+  }
+
+  pub(crate) fn magic(self) -> Magic {
+    match self {
+      Self::Testnet4 => Magic::from_bytes([0x1c, 0x16, 0x3f, 0x28]),
+      _ => self.network().magic(),
+    }
   }
 
   pub(crate) fn genesis_coinbase_outpoint(self) -> OutPoint {
@@ -74,6 +157,7 @@ impl Chain {
     match self {
       Self::Mainnet => data_dir.as_ref().to_owned(),
       Self::Testnet => data_dir.as_ref().join("testnet3"),
+      Self::Testnet4 => data_dir.as_ref().join("testnet4"),
       Self::Signet => data_dir.as_ref().join("signet"),
       Self::Regtest => data_dir.as_ref().join("regtest"),
     }
@@ -84,7 +168,7 @@ impl From<Chain> for Network {
   fn from(chain: Chain) -> Network {
     match chain {
       Chain::Mainnet => Network::Bitcoin,
-      Chain::Testnet => Network::Testnet,
+      Chain::Testnet | Chain::Testnet4 => Network::Testnet,
       Chain::Signet => Network::Signet,
       Chain::Regtest => Network::Regtest,
     }
@@ -101,6 +185,7 @@ impl Display for Chain {
         Self::Regtest => "regtest",
         Self::Signet => "signet",
         Self::Testnet => "testnet",
+        Self::Testnet4 => "testnet4",
       }
     )
   }
@@ -115,6 +200,7 @@ impl FromStr for Chain {
       "regtest" => Ok(Self::Regtest),
       "signet" => Ok(Self::Signet),
       "testnet" => Ok(Self::Testnet),
+      "testnet4" => Ok(Self::Testnet4),
       _ => Err(SnafuError::InvalidChain {
         chain: s.to_string(),
       }),
@@ -132,9 +218,27 @@ mod tests {
     assert_eq!("regtest".parse::<Chain>().unwrap(), Chain::Regtest);
     assert_eq!("signet".parse::<Chain>().unwrap(), Chain::Signet);
     assert_eq!("testnet".parse::<Chain>().unwrap(), Chain::Testnet);
+    assert_eq!("testnet4".parse::<Chain>().unwrap(), Chain::Testnet4);
     assert_eq!(
       "foo".parse::<Chain>().unwrap_err().to_string(),
       "Invalid chain `foo`"
     );
   }
+
+  #[test]
+  fn testnet4_genesis_block() {
+    assert_eq!(
+      Chain::Testnet4.genesis_block().block_hash().to_string(),
+      "00000000da84f2bafbbc53dee25a72ae507ff4914b867c565be350b0da8bf043",
+    );
+  }
+
+  #[test]
+  fn testnet4_rune_schedule() {
+    assert_eq!(Chain::Testnet4.first_rune_height(), 0);
+    assert_eq!(
+      Chain::Testnet4.minimum_rune_at_height(Height(0)),
+      Chain::Signet.minimum_rune_at_height(Height(0)),
+    );
+  }
 }
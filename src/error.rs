@@ -43,6 +43,8 @@ pub enum SnafuError {
     source: ordinals::sat_point::Error,
     input: String,
   },
+  #[snafu(display("Invalid sat range `{}`: start must be less than end", input))]
+  SatRangeEmpty { input: String },
   #[snafu(display("Unrecognized representation: `{}`", input))]
   UnrecognizedRepresentation { input: String },
   #[snafu(display("Unrecognized outgoing amount: `{}`", input))]
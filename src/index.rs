@@ -1,10 +1,14 @@
 use {
   self::{
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      AddressHistoryKeyValue, Entry, EventKeyValue, HeaderValue, InscriptionEntry,
+      InscriptionEntryValue, InscriptionIdValue, InscriptionTransferKeyValue, OutPointValue,
+      RuneActivityKeyValue, RuneBalanceValue, RuneEntryValue, RuneHolderValue, RuneIdValue,
+      SatPointValue, SatRange, TxidValue,
     },
     event::Event,
+    hook::{HookBlock, IndexHook, Metaprotocols},
+    intersection::Intersection,
     lot::Lot,
     reorg::Reorg,
     updater::Updater,
@@ -13,10 +17,13 @@ use {
   super::*,
   crate::{
     runes::MintError,
-    subcommand::{find::FindRangeOutput, server::query},
+    subcommand::{
+      find::FindRangeOutput,
+      server::{query, InscriptionsQuery},
+    },
     templates::StatusHtml,
   },
-  bitcoin::block::Header,
+  bitcoin::{block::Header, hashes::sha256},
   bitcoincore_rpc::{
     json::{GetBlockHeaderResult, GetBlockStatsResult},
     Client,
@@ -29,9 +36,11 @@ use {
     ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, RepairSession,
     StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
   },
+  serde::de::DeserializeOwned,
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    ops::{Bound, RangeInclusive},
     sync::Once,
   },
 };
@@ -41,6 +50,8 @@ pub use self::entry::RuneEntry;
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
+pub mod hook;
+mod intersection;
 mod lot;
 mod reorg;
 mod rtx;
@@ -50,24 +61,38 @@ mod utxo_entry;
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 29;
+pub(crate) const SCHEMA_VERSION: u64 = 42;
 
+define_multimap_table! { CONTENT_HASH_TO_SEQUENCE_NUMBER, &[u8; 32], u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
+define_table! { ADDRESS_HISTORY_KEY_TO_TRANSACTION, AddressHistoryKeyValue, &[u8] }
+define_table! { CONTENT_TYPE_AND_SEQUENCE_NUMBER, (&str, u32), () }
+define_table! { EVENT_KEY_TO_EVENT, EventKeyValue, &[u8] }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { INSCRIPTION_TRANSFER_KEY_TO_TRANSFER, InscriptionTransferKeyValue, &[u8] }
+define_table! { MEDIA_AND_SEQUENCE_NUMBER, (&str, u32), () }
+define_table! { METAPROTOCOL_AND_SEQUENCE_NUMBER, (&str, u32), () }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_RUNE_HOLDER, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
+define_table! { RUNE_ACTIVITY_KEY_TO_RUNE_ACTIVITY, RuneActivityKeyValue, &[u8] }
+define_table! { RUNE_BALANCE_TO_HOLDER, RuneBalanceValue, () }
+define_table! { RUNE_HOLDER_TO_BALANCE, RuneHolderValue, u128 }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SAVEPOINT_TO_HEIGHT, u64, u32 }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT_HASH, u32, &[u8; 32] }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SIZE_BUCKET_AND_SEQUENCE_NUMBER, (u8, u32), () }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
@@ -91,9 +116,73 @@ pub(crate) enum Statistic {
   Runes = 13,
   SatRanges = 14,
   UnboundInscriptions = 16,
+  IndexEvents = 17,
+  IndexRuneActivity = 18,
+  IndexAddressHistory = 19,
+  IndexInscriptionHistory = 20,
+  IndexMetaprotocols = 21,
+  IndexInscriptionAttributes = 22,
+  IndexContentHashes = 23,
 }
 
 impl Statistic {
+  /// Statistics which record whether an optional index is enabled, with the
+  /// name of the index's flag and the setting which enables it.
+  pub(crate) const INDEX_FLAGS: [(Self, &'static str, fn(&Settings) -> bool); 12] = [
+    (
+      Self::IndexAddressHistory,
+      "index_address_history",
+      Settings::index_address_history_raw,
+    ),
+    (
+      Self::IndexAddresses,
+      "index_addresses",
+      Settings::index_addresses_raw,
+    ),
+    (
+      Self::IndexContentHashes,
+      "index_content_hashes",
+      Settings::index_content_hashes_raw,
+    ),
+    (
+      Self::IndexEvents,
+      "index_events",
+      Settings::index_events_raw,
+    ),
+    (
+      Self::IndexInscriptionAttributes,
+      "index_inscription_attributes",
+      Settings::index_inscription_attributes_raw,
+    ),
+    (
+      Self::IndexInscriptionHistory,
+      "index_inscription_history",
+      Settings::index_inscription_history_raw,
+    ),
+    (
+      Self::IndexInscriptions,
+      "index_inscriptions",
+      Settings::index_inscriptions_raw,
+    ),
+    (
+      Self::IndexMetaprotocols,
+      "index_metaprotocols",
+      Settings::index_metaprotocols_raw,
+    ),
+    (
+      Self::IndexRuneActivity,
+      "index_rune_activity",
+      Settings::index_rune_activity_raw,
+    ),
+    (Self::IndexRunes, "index_runes", Settings::index_runes_raw),
+    (Self::IndexSats, "index_sats", Settings::index_sats_raw),
+    (
+      Self::IndexTransactions,
+      "index_transactions",
+      Settings::index_transactions_raw,
+    ),
+  ];
+
   fn key(self) -> u64 {
     self.into()
   }
@@ -117,6 +206,7 @@ pub struct Info {
   outputs_traversed: u64,
   page_size: usize,
   sat_ranges: u64,
+  savepoints: Vec<u32>,
   stored_bytes: u64,
   tables: BTreeMap<String, TableInfo>,
   total_bytes: u64,
@@ -198,8 +288,16 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  hooks: Vec<Box<dyn IndexHook>>,
+  index_address_history: bool,
   index_addresses: bool,
+  index_content_hashes: bool,
+  index_events: bool,
+  index_inscription_attributes: bool,
+  index_inscription_history: bool,
   index_inscriptions: bool,
+  index_metaprotocols: bool,
+  index_rune_activity: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
@@ -218,6 +316,16 @@ impl Index {
   pub fn open_with_event_sender(
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  ) -> Result<Self> {
+    Index::open_with_hooks(settings, event_sender, Vec::new())
+  }
+
+  /// Opens the index, calling each of `hooks` with every block indexed, in
+  /// addition to any built-in hooks enabled by `settings`.
+  pub fn open_with_hooks(
+    settings: &Settings,
+    event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+    mut hooks: Vec<Box<dyn IndexHook>>,
   ) -> Result<Self> {
     let client = settings.bitcoin_rpc_client(None)?;
 
@@ -296,6 +404,16 @@ impl Index {
       Err(DatabaseError::Storage(StorageError::Io(error)))
         if error.kind() == io::ErrorKind::NotFound =>
       {
+        ensure!(
+          !settings.index_address_history_raw() || settings.index_addresses_raw(),
+          "`--index-address-history` requires `--index-addresses`",
+        );
+
+        ensure!(
+          !settings.index_rune_activity_raw() || settings.index_runes_raw(),
+          "`--index-rune-activity` requires `--index-runes`",
+        );
+
         let database = Database::builder()
           .set_cache_size(index_cache_size)
           .create(&path)?;
@@ -304,57 +422,44 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_table(ADDRESS_HISTORY_KEY_TO_TRANSACTION)?;
+        tx.open_table(CONTENT_TYPE_AND_SEQUENCE_NUMBER)?;
+        tx.open_table(EVENT_KEY_TO_EVENT)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(INSCRIPTION_TRANSFER_KEY_TO_TRANSFER)?;
+        tx.open_table(MEDIA_AND_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_RUNE_HOLDER)?;
         tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+        tx.open_table(RUNE_ACTIVITY_KEY_TO_RUNE_ACTIVITY)?;
+        tx.open_table(RUNE_BALANCE_TO_HOLDER)?;
+        tx.open_table(RUNE_HOLDER_TO_BALANCE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SAVEPOINT_TO_HEIGHT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_CONTENT_HASH)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SIZE_BUCKET_AND_SEQUENCE_NUMBER)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
         {
           let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
 
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexAddresses,
-            u64::from(settings.index_addresses_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexInscriptions,
-            u64::from(settings.index_inscriptions_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexRunes,
-            u64::from(settings.index_runes_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexSats,
-            u64::from(settings.index_sats_raw()),
-          )?;
-
-          Self::set_statistic(
-            &mut statistics,
-            Statistic::IndexTransactions,
-            u64::from(settings.index_transactions_raw()),
-          )?;
+          for (statistic, _flag, enabled) in Statistic::INDEX_FLAGS {
+            Self::set_statistic(&mut statistics, statistic, u64::from(enabled(settings)))?;
+          }
 
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
@@ -388,6 +493,7 @@ impl Index {
                 ),
                 offset: (None, None),
               }),
+              holders: 0,
               mints: 0,
               number: 0,
               premine: 0,
@@ -410,22 +516,56 @@ impl Index {
       Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_address_history;
     let index_addresses;
+    let index_content_hashes;
+    let index_events;
+    let index_rune_activity;
     let index_runes;
     let index_sats;
     let index_transactions;
+    let index_inscription_attributes;
+    let index_inscription_history;
     let index_inscriptions;
+    let index_metaprotocols;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_address_history = Self::is_statistic_set(&statistics, Statistic::IndexAddressHistory)?;
       index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_content_hashes = Self::is_statistic_set(&statistics, Statistic::IndexContentHashes)?;
+      index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
+      index_inscription_attributes =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionAttributes)?;
+      index_inscription_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionHistory)?;
       index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
+      index_metaprotocols = Self::is_statistic_set(&statistics, Statistic::IndexMetaprotocols)?;
+      index_rune_activity = Self::is_statistic_set(&statistics, Statistic::IndexRuneActivity)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
     }
 
+    if index_metaprotocols {
+      hooks.insert(0, Box::new(Metaprotocols));
+    }
+
+    if !hooks.is_empty() {
+      let mut tx = database.begin_write()?;
+
+      tx.set_durability(durability);
+
+      for hook in &hooks {
+        hook
+          .create_tables(&tx)
+          .with_context(|| format!("failed to create tables for `{}` hook", hook.name()))?;
+      }
+
+      tx.commit()?;
+    }
+
     let genesis_block_coinbase_transaction =
       settings.chain().genesis_block().coinbase().unwrap().clone();
 
@@ -448,11 +588,19 @@ impl Index {
       first_index_height,
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      hooks,
+      index_address_history,
       index_addresses,
+      index_content_hashes,
+      index_events,
+      index_inscription_attributes,
+      index_inscription_history,
+      index_rune_activity,
       index_runes,
       index_sats,
       index_transactions,
       index_inscriptions,
+      index_metaprotocols,
       settings: settings.clone(),
       path,
       started: Utc::now(),
@@ -460,6 +608,13 @@ impl Index {
     })
   }
 
+  /// Returns the size bucket of an inscription body of `size` bytes. Bucket
+  /// zero holds empty bodies, and bucket `n` holds bodies of at least
+  /// 2^(n-1) and less than 2^n bytes.
+  pub(crate) fn size_bucket(size: usize) -> u8 {
+    (usize::BITS - size.leading_zeros()).try_into().unwrap()
+  }
+
   pub fn have_full_utxo_index(&self) -> bool {
     self.first_index_height == 0
   }
@@ -489,14 +644,73 @@ impl Index {
     )
   }
 
+  pub fn has_address_history_index(&self) -> bool {
+    self.index_address_history
+  }
+
   pub fn has_address_index(&self) -> bool {
     self.index_addresses
   }
 
+  pub(crate) fn index_flags(&self) -> Result<BTreeMap<String, bool>> {
+    let rtx = self.database.begin_read()?;
+
+    let statistics = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+    Statistic::INDEX_FLAGS
+      .into_iter()
+      .map(|(statistic, flag, _enabled)| {
+        Ok((flag.into(), Self::is_statistic_set(&statistics, statistic)?))
+      })
+      .collect()
+  }
+
+  pub(crate) fn table_lengths(&self) -> Result<BTreeMap<String, u64>> {
+    let rtx = self.database.begin_read()?;
+
+    let mut lengths = BTreeMap::new();
+
+    for handle in rtx.list_tables()? {
+      let name = handle.name().into();
+      lengths.insert(name, rtx.open_untyped_table(handle)?.len()?);
+    }
+
+    for handle in rtx.list_multimap_tables()? {
+      let name = handle.name().into();
+      lengths.insert(name, rtx.open_untyped_multimap_table(handle)?.len()?);
+    }
+
+    Ok(lengths)
+  }
+
+  pub fn has_content_hash_index(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
+  pub fn has_inscription_attribute_index(&self) -> bool {
+    self.index_inscription_attributes
+  }
+
+  pub fn has_inscription_history_index(&self) -> bool {
+    self.index_inscription_history
+  }
+
   pub fn has_inscription_index(&self) -> bool {
     self.index_inscriptions
   }
 
+  pub fn has_metaprotocol_index(&self) -> bool {
+    self.index_metaprotocols
+  }
+
+  pub fn has_rune_activity_index(&self) -> bool {
+    self.index_rune_activity
+  }
+
   pub fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -543,10 +757,10 @@ impl Index {
       inscriptions: blessed_inscriptions + cursed_inscriptions,
       json_api,
       lost_sats: statistic(Statistic::LostSats)?,
-      minimum_rune_for_next_block: Rune::minimum_at_height(
-        self.settings.chain().network(),
-        Height(next_height),
-      ),
+      minimum_rune_for_next_block: self
+        .settings
+        .chain()
+        .minimum_rune_at_height(Height(next_height)),
       rune_index: self.has_rune_index(),
       runes: statistic(Statistic::Runes)?,
       sat_index: self.has_sat_index(),
@@ -618,6 +832,15 @@ impl Index {
         leaf_pages: stats.leaf_pages(),
         metadata_bytes: stats.metadata_bytes(),
         sat_ranges,
+        savepoints: {
+          let mut savepoints = rtx
+            .open_table(SAVEPOINT_TO_HEIGHT)?
+            .iter()?
+            .map(|result| result.map(|(_id, height)| height.value()))
+            .collect::<Result<Vec<u32>, StorageError>>()?;
+          savepoints.sort();
+          savepoints
+        },
         outputs_traversed,
         page_size: stats.page_size(),
         stored_bytes: stats.stored_bytes(),
@@ -683,6 +906,10 @@ impl Index {
     }
   }
 
+  pub(crate) fn rollback(&self, height: u32) -> Result<u32> {
+    Reorg::rollback(self, height)
+  }
+
   pub fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(File::create(filename)?);
     let rtx = self.database.begin_read()?;
@@ -902,6 +1129,17 @@ impl Index {
     )
   }
 
+  pub(crate) fn get_rune_entry(&self, id: RuneId) -> Result<Option<RuneEntry>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(&id.store())?
+        .map(|entry| RuneEntry::load(entry.value())),
+    )
+  }
+
   pub fn get_rune_by_number(&self, number: usize) -> Result<Option<Rune>> {
     match self
       .database
@@ -1258,6 +1496,20 @@ impl Index {
       .collect()
   }
 
+  pub(crate) fn get_inscription_history_paginated(
+    &self,
+    sequence_number: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::InscriptionTransfer>, bool)> {
+    self.get_json_paginated(
+      INSCRIPTION_TRANSFER_KEY_TO_TRANSFER,
+      (sequence_number, 0, 0)..=(sequence_number, u32::MAX, u32::MAX),
+      page_size,
+      page_index,
+    )
+  }
+
   pub fn get_children_by_sequence_number_paginated(
     &self,
     sequence_number: u32,
@@ -1293,6 +1545,35 @@ impl Index {
     Ok((children, more))
   }
 
+  pub fn get_inscriptions_by_metaprotocol(&self, metaprotocol: &str) -> Result<Vec<InscriptionId>> {
+    ensure!(
+      self.index_metaprotocols,
+      "metaprotocol index requires index created with `--index-metaprotocols` flag",
+    );
+
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    rtx
+      .open_table(METAPROTOCOL_AND_SEQUENCE_NUMBER)?
+      .range((metaprotocol, 0)..=(metaprotocol, u32::MAX))?
+      .map(|result| {
+        let sequence_number = result?.0.value().1;
+
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_entry
+              .get(sequence_number)?
+              .ok_or_else(|| anyhow!("missing inscription entry {sequence_number}"))?
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect()
+  }
+
   pub fn get_parents_by_sequence_number_paginated(
     &self,
     parent_sequence_numbers: Vec<u32>,
@@ -1815,6 +2096,253 @@ impl Index {
     Ok((inscriptions, more))
   }
 
+  pub fn get_inscriptions_by_content_hash(
+    &self,
+    content_hash: sha256::Hash,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut inscriptions = rtx
+      .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?
+      .get(&content_hash.to_byte_array())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let sequence_number = result?.value();
+
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .ok_or_else(|| anyhow!("missing inscription entry {sequence_number}"))?
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  /// Returns inscriptions matching all filters in `query`, newest first, or
+  /// in inscription order if restricted to the block at `block_height`.
+  pub(crate) fn get_inscriptions_filtered(
+    &self,
+    query: &InscriptionsQuery,
+    block_height: Option<u32>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let (oldest, newest) = match block_height {
+      Some(block_height) => {
+        let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+        let Some(newest) = height_to_last_sequence_number
+          .get(&block_height)?
+          .map(|newest| newest.value())
+        else {
+          return Ok((Vec::new(), false));
+        };
+
+        let oldest = height_to_last_sequence_number
+          .get(block_height.saturating_sub(1))?
+          .map(|oldest| oldest.value())
+          .unwrap_or(0);
+
+        (oldest, newest)
+      }
+      None => (0, u32::MAX),
+    };
+
+    let descending = block_height.is_none();
+
+    let mut iterators = Vec::new();
+
+    if let Some(content_type) = &query.content_type {
+      let content_type = content_type.as_str();
+
+      iterators.push(Self::sequence_numbers(
+        rtx
+          .open_table(CONTENT_TYPE_AND_SEQUENCE_NUMBER)?
+          .range((content_type, oldest)..(content_type, newest))?,
+        descending,
+      ));
+    }
+
+    if let Some(media) = &query.media {
+      let media = media.as_str();
+
+      iterators.push(Self::sequence_numbers(
+        rtx
+          .open_table(MEDIA_AND_SEQUENCE_NUMBER)?
+          .range((media, oldest)..(media, newest))?,
+        descending,
+      ));
+    }
+
+    if let Some(metaprotocol) = &query.metaprotocol {
+      let metaprotocol = metaprotocol.as_str();
+
+      iterators.push(Self::sequence_numbers(
+        rtx
+          .open_table(METAPROTOCOL_AND_SEQUENCE_NUMBER)?
+          .range((metaprotocol, oldest)..(metaprotocol, newest))?,
+        descending,
+      ));
+    }
+
+    if let Some(size_bucket) = query.size_bucket {
+      iterators.push(Self::sequence_numbers(
+        rtx
+          .open_table(SIZE_BUCKET_AND_SEQUENCE_NUMBER)?
+          .range((size_bucket, oldest)..(size_bucket, newest))?,
+        descending,
+      ));
+    }
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut inscriptions = Intersection::new(iterators, descending)
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let sequence_number = result?;
+
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .ok_or_else(|| anyhow!("missing inscription entry {sequence_number}"))?
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  /// Sequence numbers from a range of a table keyed by attribute and
+  /// sequence number, which are ascending for a single attribute.
+  fn sequence_numbers<K: redb::Key + 'static>(
+    range: redb::Range<'static, (K, u32), ()>,
+    descending: bool,
+  ) -> Box<dyn Iterator<Item = Result<u32, StorageError>>> {
+    let sequence_numbers = range.map(|result| result.map(|(key, _value)| key.value().1));
+
+    if descending {
+      Box::new(sequence_numbers.rev())
+    } else {
+      Box::new(sequence_numbers)
+    }
+  }
+
+  pub fn get_events_paginated(
+    &self,
+    from_height: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::JournaledEvent>, bool)> {
+    let mut events = self
+      .database
+      .begin_read()?
+      .open_table(EVENT_KEY_TO_EVENT)?
+      .range((from_height, 0, 0)..)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let (key, value) = result?;
+        Self::load_journaled_event(key.value(), value.value())
+      })
+      .collect::<Result<Vec<api::JournaledEvent>>>()?;
+
+    let more = events.len() > page_size;
+
+    if more {
+      events.pop();
+    }
+
+    Ok((events, more))
+  }
+
+  /// Returns up to `limit` journaled events that follow `cursor`, or the
+  /// first `limit` events if `cursor` is `None`.
+  pub(crate) fn get_events_after(
+    &self,
+    cursor: Option<EventKeyValue>,
+    limit: usize,
+  ) -> Result<Vec<api::JournaledEvent>> {
+    let rtx = self.database.begin_read()?;
+
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+
+    let range = match cursor {
+      Some(cursor) => event_key_to_event.range((Bound::Excluded(cursor), Bound::Unbounded))?,
+      None => event_key_to_event.range::<EventKeyValue>(..)?,
+    };
+
+    range
+      .take(limit)
+      .map(|result| {
+        let (key, value) = result?;
+        Self::load_journaled_event(key.value(), value.value())
+      })
+      .collect()
+  }
+
+  /// Returns a cursor which precedes every journaled event in blocks at or
+  /// above `height`.
+  pub(crate) fn event_cursor_before(height: u32) -> Option<EventKeyValue> {
+    height
+      .checked_sub(1)
+      .map(|height| (height, u32::MAX, u32::MAX))
+  }
+
+  pub(crate) fn last_event_key(&self) -> Result<Option<EventKeyValue>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(EVENT_KEY_TO_EVENT)?
+        .last()?
+        .map(|(key, _value)| key.value()),
+    )
+  }
+
+  fn load_journaled_event(
+    (height, tx_index, sequence_number): EventKeyValue,
+    value: &[u8],
+  ) -> Result<api::JournaledEvent> {
+    Ok(api::JournaledEvent {
+      event: serde_json::from_slice(value)?,
+      height,
+      sequence_number,
+      tx_index,
+    })
+  }
+
   pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
@@ -2087,6 +2615,45 @@ impl Index {
       Charm::Lost.set(&mut charms);
     }
 
+    let content_hash = if self.index_content_hashes {
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_CONTENT_HASH)?
+        .get(sequence_number)?
+        .map(|content_hash| sha256::Hash::from_byte_array(*content_hash.value()))
+    } else {
+      None
+    };
+
+    let (content_duplicates, first_with_content) = match content_hash {
+      Some(content_hash) => {
+        let mut sequence_numbers = rtx
+          .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?
+          .get(&content_hash.to_byte_array())?;
+
+        let count = sequence_numbers.len();
+
+        let first = sequence_numbers
+          .next()
+          .transpose()?
+          .map(|first| first.value())
+          .unwrap_or(sequence_number);
+
+        (
+          Some(count.saturating_sub(1)),
+          Some(
+            InscriptionEntry::load(
+              sequence_number_to_inscription_entry
+                .get(first)?
+                .unwrap()
+                .value(),
+            )
+            .id,
+          ),
+        )
+      }
+      _ => (None, None),
+    };
+
     let effective_mime_type = if let Some(delegate_id) = inscription.delegate() {
       let delegate_result = self.get_inscription_by_id(delegate_id);
       if let Ok(Some(delegate)) = delegate_result {
@@ -2113,15 +2680,19 @@ impl Index {
         charms: Charm::charms(charms),
         child_count,
         children,
+        content_duplicates,
+        content_hash,
         content_length: inscription.content_length(),
         content_type: inscription.content_type().map(|s| s.to_string()),
         effective_content_type: effective_mime_type,
         fee: entry.fee,
+        first_with_content,
         height: entry.height,
         id: entry.id,
         next,
         number: entry.inscription_number,
         parents,
+        pending_transfer: None,
         previous,
         rune,
         sat: entry.sat,
@@ -2283,13 +2854,155 @@ impl Index {
       .collect()
   }
 
-  pub(crate) fn get_aggregated_rune_balances_for_outputs(
+  pub(crate) fn get_address_history_paginated(
     &self,
-    outputs: &Vec<OutPoint>,
-  ) -> Result<Vec<(SpacedRune, Decimal, Option<char>)>> {
-    let mut runes = BTreeMap::new();
-
-    for output in outputs {
+    script_pubkey: &Script,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::AddressTransaction>, bool)> {
+    let script_pubkey = script_pubkey.as_bytes();
+
+    self.get_json_paginated(
+      ADDRESS_HISTORY_KEY_TO_TRANSACTION,
+      (script_pubkey, 0, 0)..=(script_pubkey, u32::MAX, u32::MAX),
+      page_size,
+      page_index,
+    )
+  }
+
+  pub(crate) fn get_rune_balances_for_script_pubkey(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<Vec<(SpacedRune, Decimal, Option<char>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_holder_to_balance = rtx.open_table(RUNE_HOLDER_TO_BALANCE)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let script_pubkey = script_pubkey.as_bytes();
+
+    let mut balances = Vec::new();
+
+    for result in rune_holder_to_balance
+      .range((script_pubkey, (0, 0))..=(script_pubkey, (u64::MAX, u32::MAX)))?
+    {
+      let (key, balance) = result?;
+
+      let (_script_pubkey, id) = key.value();
+
+      let entry = RuneEntry::load(rune_id_to_rune_entry.get(id)?.unwrap().value());
+
+      balances.push((
+        entry.spaced_rune,
+        Decimal {
+          value: balance.value(),
+          scale: entry.divisibility,
+        },
+        entry.symbol,
+      ));
+    }
+
+    balances.sort_by_key(|(spaced_rune, _decimal, _symbol)| *spaced_rune);
+
+    Ok(balances)
+  }
+
+  pub(crate) fn get_rune_holders_paginated(
+    &self,
+    id: RuneId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(ScriptBuf, u128)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_balance_to_holder = rtx.open_table(RUNE_BALANCE_TO_HOLDER)?;
+
+    let start = (id.store(), 0, [].as_slice());
+
+    let next = match id.tx.checked_add(1) {
+      Some(tx) => Some((id.block, tx)),
+      None => id.block.checked_add(1).map(|block| (block, 0)),
+    };
+
+    let range = match next {
+      Some(next) => rune_balance_to_holder.range(start..(next, 0, [].as_slice()))?,
+      None => rune_balance_to_holder.range(start..)?,
+    };
+
+    let mut holders = range
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result.map(|(key, _)| {
+          let (_id, balance, script_pubkey) = key.value();
+          (ScriptBuf::from_bytes(script_pubkey.to_vec()), balance)
+        })
+      })
+      .collect::<Result<Vec<(ScriptBuf, u128)>, StorageError>>()?;
+
+    let more = holders.len() > page_size;
+
+    if more {
+      holders.pop();
+    }
+
+    Ok((holders, more))
+  }
+
+  pub(crate) fn get_rune_activity_paginated(
+    &self,
+    id: RuneId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::RuneActivity>, bool)> {
+    self.get_json_paginated(
+      RUNE_ACTIVITY_KEY_TO_RUNE_ACTIVITY,
+      (id.store(), 0, 0, 0)..=(id.store(), u32::MAX, u32::MAX, u32::MAX),
+      page_size,
+      page_index,
+    )
+  }
+
+  /// Returns a page of the JSON-encoded values in `range` of `table`, newest
+  /// first, and whether there are more.
+  fn get_json_paginated<'a, K: redb::Key + 'static, T: DeserializeOwned>(
+    &self,
+    table: TableDefinition<K, &'static [u8]>,
+    range: RangeInclusive<K::SelfType<'a>>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<T>, bool)> {
+    let mut values = self
+      .database
+      .begin_read()?
+      .open_table(table)?
+      .range(range)?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let (_key, value) = result?;
+        Ok(serde_json::from_slice(value.value())?)
+      })
+      .collect::<Result<Vec<T>>>()?;
+
+    let more = values.len() > page_size;
+
+    if more {
+      values.pop();
+    }
+
+    Ok((values, more))
+  }
+
+  pub(crate) fn get_aggregated_rune_balances_for_outputs(
+    &self,
+    outputs: &Vec<OutPoint>,
+  ) -> Result<Vec<(SpacedRune, Decimal, Option<char>)>> {
+    let mut runes = BTreeMap::new();
+
+    for output in outputs {
       let rune_balances = self.get_rune_balances_for_output(*output)?;
 
       for (spaced_rune, pile) in rune_balances {
@@ -4594,6 +5307,57 @@ mod tests {
     }
   }
 
+  #[test]
+  fn rollback_restores_nearest_savepoint() {
+    let mut context = Context::builder().arg("--savepoint-count=3").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    for _ in 0..4 {
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(context.index.info().unwrap().savepoints, [2, 3, 4]);
+
+    assert_eq!(context.index.rollback(3).unwrap(), 3);
+
+    assert_eq!(context.index.block_count().unwrap(), 4);
+
+    assert_eq!(context.index.info().unwrap().savepoints, [2, 3]);
+
+    assert_eq!(
+      context.index.rollback(1).unwrap_err().to_string(),
+      "no savepoint at or below height 1, available savepoints: 2, 3",
+    );
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.block_count().unwrap(), 6);
+  }
+
+  #[test]
+  fn savepoints_are_created_at_configured_interval() {
+    let mut context = Context::builder()
+      .arg("--savepoint-count=2")
+      .arg("--savepoint-interval=3")
+      .build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.info().unwrap().savepoints, [1, 2]);
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.info().unwrap().savepoints, [1, 2]);
+
+    context.mine_blocks(2);
+
+    assert_eq!(context.index.info().unwrap().savepoints, [2, 5]);
+  }
+
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
     for mut context in Context::configurations() {
@@ -6335,6 +7099,238 @@ mod tests {
     );
   }
 
+  #[test]
+  fn address_history_is_recorded() {
+    let context = Context::builder()
+      .arg("--index-addresses")
+      .arg("--index-address-history")
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid0 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: txid0,
+      index: 0,
+    };
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let output = context.core.tx_by_id(txid0).output[0].clone();
+
+    let received = api::AddressTransaction {
+      inscriptions_received: vec![inscription_id],
+      received: output.value.to_sat(),
+      ..api::AddressTransaction::new(2, txid0)
+    };
+
+    let sent = api::AddressTransaction {
+      inscriptions_sent: vec![inscription_id],
+      sent: output.value.to_sat(),
+      ..api::AddressTransaction::new(3, txid1)
+    };
+
+    assert_eq!(
+      context
+        .index
+        .get_address_history_paginated(&output.script_pubkey, 100, 0)
+        .unwrap(),
+      (vec![sent.clone(), received.clone()], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_address_history_paginated(&output.script_pubkey, 1, 0)
+        .unwrap(),
+      (vec![sent], true),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_address_history_paginated(&output.script_pubkey, 1, 1)
+        .unwrap(),
+      (vec![received], false),
+    );
+  }
+
+  #[test]
+  fn address_history_requires_address_index() {
+    assert_eq!(
+      Context::builder()
+        .arg("--index-address-history")
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      "`--index-address-history` requires `--index-addresses`",
+    );
+  }
+
+  #[test]
+  fn rune_activity_requires_rune_index() {
+    assert_eq!(
+      Context::builder()
+        .arg("--index-rune-activity")
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      "`--index-rune-activity` requires `--index-runes`",
+    );
+  }
+
+  #[test]
+  fn inscription_history_is_recorded() {
+    let context = Context::builder()
+      .arg("--index-inscription-history")
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid0 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid2 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Witness::new())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let address = |txid| {
+      context
+        .index
+        .settings
+        .chain()
+        .address_from_script(&context.core.tx_by_id(txid).output[0].script_pubkey)
+        .ok()
+        .map(|address| uncheck(&address))
+    };
+
+    let first = api::InscriptionTransfer {
+      address: address(txid1),
+      height: 3,
+      new_satpoint: SatPoint {
+        outpoint: OutPoint::new(txid1, 0),
+        offset: 0,
+      },
+      old_satpoint: SatPoint {
+        outpoint: OutPoint::new(txid0, 0),
+        offset: 0,
+      },
+      txid: txid1,
+    };
+
+    let second = api::InscriptionTransfer {
+      address: address(txid2),
+      height: 4,
+      new_satpoint: SatPoint {
+        outpoint: OutPoint::new(txid2, 0),
+        offset: 0,
+      },
+      old_satpoint: first.new_satpoint,
+      txid: txid2,
+    };
+
+    assert!(first.address.is_some());
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history_paginated(0, 100, 0)
+        .unwrap(),
+      (vec![second.clone(), first.clone()], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history_paginated(0, 1, 0)
+        .unwrap(),
+      (vec![second], true),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history_paginated(0, 1, 1)
+        .unwrap(),
+      (vec![first], false),
+    );
+  }
+
+  #[test]
+  fn inscription_history_is_not_recorded_without_flag() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_inscription_history_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history_paginated(0, 100, 0)
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn missing_blk_files_fall_back_to_rpc() {
+    let bitcoin_data_dir = TempDir::new().unwrap();
+
+    let context = Context::builder()
+      .arg("--index-blk-files")
+      .arg("--bitcoin-data-dir")
+      .arg(bitcoin_data_dir.path())
+      .build();
+
+    context.mine_blocks(110);
+
+    assert_eq!(context.index.block_count().unwrap(), 111);
+  }
+
   #[test]
   fn fee_spent_inscriptions_are_numbered_last_in_block() {
     for context in Context::configurations() {
@@ -6675,6 +7671,290 @@ mod tests {
     );
   }
 
+  #[test]
+  fn event_journal() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    let created = api::JournaledEvent {
+      event: Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        inscription_id,
+        location: Some(SatPoint {
+          outpoint: OutPoint {
+            txid: create_txid,
+            vout: 0,
+          },
+          offset: 0,
+        }),
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 0,
+      },
+      height: 2,
+      sequence_number: 0,
+      tx_index: 1,
+    };
+
+    let transferred = api::JournaledEvent {
+      event: Event::InscriptionTransferred {
+        block_height: 3,
+        inscription_id,
+        new_location: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        old_location: SatPoint {
+          outpoint: OutPoint {
+            txid: create_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        sequence_number: 0,
+      },
+      height: 3,
+      sequence_number: 0,
+      tx_index: 1,
+    };
+
+    pretty_assert_eq!(
+      context.index.get_events_after(None, 100).unwrap(),
+      [created.clone(), transferred.clone()],
+    );
+
+    pretty_assert_eq!(
+      context
+        .index
+        .get_events_after(Index::event_cursor_before(3), 100)
+        .unwrap(),
+      [transferred.clone()],
+    );
+
+    pretty_assert_eq!(
+      context.index.get_events_after(None, 1).unwrap(),
+      [created.clone()],
+    );
+
+    pretty_assert_eq!(
+      context.index.get_events_after(Some((2, 1, 0)), 1).unwrap(),
+      [transferred.clone()],
+    );
+
+    pretty_assert_eq!(
+      context.index.get_events_paginated(0, 1, 0).unwrap(),
+      (vec![created], true),
+    );
+
+    pretty_assert_eq!(
+      context.index.get_events_paginated(0, 1, 1).unwrap(),
+      (vec![transferred], false),
+    );
+  }
+
+  #[test]
+  fn event_journal_is_empty_without_flag() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_event_index());
+    assert_eq!(context.index.get_events_after(None, 100).unwrap(), []);
+  }
+
+  #[test]
+  fn event_journal_is_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-events").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(6);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.get_events_after(None, 100).unwrap().len(), 2);
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let events = context.index.get_events_after(None, 100).unwrap();
+
+    assert_eq!(context.index.block_height().unwrap(), Some(Height(9)));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].height, 2);
+  }
+
+  #[test]
+  fn metaprotocol_index_records_inscriptions_by_metaprotocol() {
+    let context = Context::builder().arg("--index-metaprotocols").build();
+
+    assert!(context.index.has_metaprotocol_index());
+
+    context.mine_blocks(2);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          metaprotocol: Some("foo".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metaprotocol("foo")
+        .unwrap(),
+      [InscriptionId { txid, index: 0 }],
+    );
+
+    assert!(context
+      .index
+      .get_inscriptions_by_metaprotocol("bar")
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn metaprotocol_index_requires_flag() {
+    let context = Context::builder().build();
+
+    assert!(!context.index.has_metaprotocol_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metaprotocol("foo")
+        .unwrap_err()
+        .to_string(),
+      "metaprotocol index requires index created with `--index-metaprotocols` flag",
+    );
+  }
+
+  #[test]
+  fn hook_tables_are_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-metaprotocols").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let inscription = |body: &str| {
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some(body.into()),
+        metaprotocol: Some("foo".into()),
+        ..default()
+      }
+      .to_witness()
+    };
+
+    let first = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("hello"))],
+      ..default()
+    });
+
+    context.mine_blocks(6);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("world"))],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metaprotocol("foo")
+        .unwrap()
+        .len(),
+      2,
+    );
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metaprotocol("foo")
+        .unwrap(),
+      [InscriptionId {
+        txid: first,
+        index: 0
+      }],
+    );
+  }
+
+  #[test]
+  fn size_bucket() {
+    assert_eq!(Index::size_bucket(0), 0);
+    assert_eq!(Index::size_bucket(1), 1);
+    assert_eq!(Index::size_bucket(2), 2);
+    assert_eq!(Index::size_bucket(3), 2);
+    assert_eq!(Index::size_bucket(4), 3);
+    assert_eq!(Index::size_bucket(1023), 10);
+    assert_eq!(Index::size_bucket(1024), 11);
+  }
+
   #[test]
   fn assert_schema_statistic_key_is_zero() {
     // other schema statistic keys may chenge when the schema changes, but for
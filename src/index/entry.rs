@@ -8,6 +8,10 @@ pub(crate) trait Entry: Sized {
   fn store(self) -> Self::Value;
 }
 
+/// Block height, index of the transaction in the block, and the order in
+/// which the event was emitted while indexing the block.
+pub(crate) type EventKeyValue = (u32, u32, u32);
+
 pub(super) type HeaderValue = [u8; 80];
 
 impl Entry for Header {
@@ -45,6 +49,7 @@ pub struct RuneEntry {
   pub burned: u128,
   pub divisibility: u8,
   pub etching: Txid,
+  pub holders: u64,
   pub mints: u128,
   pub number: u64,
   pub premine: u128,
@@ -155,7 +160,7 @@ pub(super) type RuneEntryValue = (
   u128,                    // burned
   u8,                      // divisibility
   (u128, u128),            // etching
-  u128,                    // mints
+  (u64, u128),             // holders and mints
   u64,                     // number
   u128,                    // premine
   (u128, u32),             // spaced rune
@@ -172,6 +177,7 @@ impl Default for RuneEntry {
       burned: 0,
       divisibility: 0,
       etching: Txid::all_zeros(),
+      holders: 0,
       mints: 0,
       number: 0,
       premine: 0,
@@ -193,7 +199,7 @@ impl Entry for RuneEntry {
       burned,
       divisibility,
       etching,
-      mints,
+      (holders, mints),
       number,
       premine,
       (rune, spacers),
@@ -217,6 +223,7 @@ impl Entry for RuneEntry {
           high[14], high[15],
         ])
       },
+      holders,
       mints,
       number,
       premine,
@@ -254,7 +261,7 @@ impl Entry for RuneEntry {
           ]),
         )
       },
-      self.mints,
+      (self.holders, self.mints),
       self.number,
       self.premine,
       (self.spaced_rune.rune.0, self.spaced_rune.spacers),
@@ -275,6 +282,24 @@ impl Entry for RuneEntry {
 
 pub(super) type RuneIdValue = (u64, u32);
 
+/// Rune ID, balance, and holder script pubkey, ordering holders of each rune
+/// by balance.
+pub(super) type RuneBalanceValue = (RuneIdValue, u128, &'static [u8]);
+
+/// Holder script pubkey and rune ID.
+pub(super) type RuneHolderValue = (&'static [u8], RuneIdValue);
+
+/// Script pubkey, block height, and index of the transaction in the block.
+pub(super) type AddressHistoryKeyValue = (&'static [u8], u32, u32);
+
+/// Inscription sequence number, block height, and the order in which the
+/// transfer was recorded while indexing the block.
+pub(super) type InscriptionTransferKeyValue = (u32, u32, u32);
+
+/// Rune ID, block height, index of the transaction in the block, and the
+/// order in which the activity was recorded while indexing the block.
+pub(super) type RuneActivityKeyValue = (RuneIdValue, u32, u32, u32);
+
 impl Entry for RuneId {
   type Value = RuneIdValue;
 
@@ -571,6 +596,7 @@ mod tests {
         0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
         0x1E, 0x1F,
       ]),
+      holders: 9,
       terms: Some(Terms {
         cap: Some(1),
         height: (Some(2), Some(3)),
@@ -597,7 +623,7 @@ mod tests {
         0x0F0E0D0C0B0A09080706050403020100,
         0x1F1E1D1C1B1A19181716151413121110,
       ),
-      11,
+      (9, 11),
       6,
       12,
       (7, 8),
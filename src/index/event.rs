@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
 pub enum Event {
   InscriptionCreated {
     block_height: u32,
@@ -42,3 +43,140 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+impl Event {
+  pub fn block_height(&self) -> u32 {
+    match self {
+      Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+    }
+  }
+
+  pub fn kind(&self) -> EventKind {
+    match self {
+      Self::InscriptionCreated { .. } => EventKind::InscriptionCreated,
+      Self::InscriptionTransferred { .. } => EventKind::InscriptionTransferred,
+      Self::RuneBurned { .. } => EventKind::RuneBurned,
+      Self::RuneEtched { .. } => EventKind::RuneEtched,
+      Self::RuneMinted { .. } => EventKind::RuneMinted,
+      Self::RuneTransferred { .. } => EventKind::RuneTransferred,
+    }
+  }
+
+  pub fn inscription_id(&self) -> Option<InscriptionId> {
+    match self {
+      Self::InscriptionCreated { inscription_id, .. }
+      | Self::InscriptionTransferred { inscription_id, .. } => Some(*inscription_id),
+      _ => None,
+    }
+  }
+
+  pub fn rune_id(&self) -> Option<RuneId> {
+    match self {
+      Self::RuneBurned { rune_id, .. }
+      | Self::RuneEtched { rune_id, .. }
+      | Self::RuneMinted { rune_id, .. }
+      | Self::RuneTransferred { rune_id, .. } => Some(*rune_id),
+      _ => None,
+    }
+  }
+
+  /// The output an event moved something into, if any.
+  pub fn outpoint(&self) -> Option<OutPoint> {
+    match self {
+      Self::InscriptionCreated { location, .. } => location.map(|location| location.outpoint),
+      Self::InscriptionTransferred { new_location, .. } => Some(new_location.outpoint),
+      Self::RuneTransferred { outpoint, .. } => Some(*outpoint),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+  InscriptionCreated,
+  InscriptionTransferred,
+  RuneBurned,
+  RuneEtched,
+  RuneMinted,
+  RuneTransferred,
+}
+
+impl EventKind {
+  pub const ALL: [Self; 6] = [
+    Self::InscriptionCreated,
+    Self::InscriptionTransferred,
+    Self::RuneBurned,
+    Self::RuneEtched,
+    Self::RuneMinted,
+    Self::RuneTransferred,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::InscriptionCreated => "inscription_created",
+      Self::InscriptionTransferred => "inscription_transferred",
+      Self::RuneBurned => "rune_burned",
+      Self::RuneEtched => "rune_etched",
+      Self::RuneMinted => "rune_minted",
+      Self::RuneTransferred => "rune_transferred",
+    }
+  }
+}
+
+impl Display for EventKind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for EventKind {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    Self::ALL
+      .into_iter()
+      .find(|kind| kind.name() == s)
+      .ok_or_else(|| anyhow!("invalid event kind `{s}`"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn event_kind_round_trip() {
+    for kind in EventKind::ALL {
+      assert_eq!(kind.to_string().parse::<EventKind>().unwrap(), kind);
+    }
+
+    assert!("foo".parse::<EventKind>().is_err());
+  }
+
+  #[test]
+  fn event_serialization() {
+    let event = Event::RuneEtched {
+      block_height: 1,
+      rune_id: RuneId { block: 1, tx: 2 },
+      txid: txid(3),
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+
+    assert_eq!(
+      json,
+      format!(
+        r#"{{"type":"rune_etched","block_height":1,"rune_id":"1:2","txid":"{}"}}"#,
+        txid(3)
+      )
+    );
+
+    assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+  }
+}
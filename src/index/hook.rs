@@ -0,0 +1,41 @@
+use super::*;
+
+pub(crate) use self::metaprotocols::Metaprotocols;
+
+mod metaprotocols;
+
+/// A block as seen by an `IndexHook`, after the inscription and rune updaters
+/// have processed it.
+pub struct HookBlock<'a> {
+  /// Events emitted while indexing the block, along with the index of the
+  /// transaction which produced them, in order.
+  pub events: &'a [(u32, Event)],
+  pub height: u32,
+  /// Inscriptions created in the block, as parsed by the inscription updater.
+  pub inscriptions: &'a HashMap<InscriptionId, Inscription>,
+  pub transactions: &'a [(Transaction, Txid)],
+}
+
+impl HookBlock<'_> {
+  /// Returns the inscription with `id`, if it was created in this block.
+  pub fn inscription(&self, id: InscriptionId) -> Option<&Inscription> {
+    self.inscriptions.get(&id)
+  }
+}
+
+/// Indexes data for a metaprotocol built on top of inscriptions or runes.
+///
+/// Hooks are called once per block, inside the same write transaction as the
+/// rest of the index. Tables they open in that transaction are committed with
+/// the block and rolled back with it on reorg.
+pub trait IndexHook: Send + Sync {
+  fn name(&self) -> &'static str;
+
+  /// Creates any tables the hook reads from, so that they exist before the
+  /// first block is indexed.
+  fn create_tables(&self, _wtx: &WriteTransaction) -> Result {
+    Ok(())
+  }
+
+  fn index_block(&self, wtx: &WriteTransaction, block: &HookBlock) -> Result;
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+/// Indexes inscriptions by the metaprotocol they declare.
+pub(crate) struct Metaprotocols;
+
+impl IndexHook for Metaprotocols {
+  fn name(&self) -> &'static str {
+    "metaprotocols"
+  }
+
+  fn create_tables(&self, wtx: &WriteTransaction) -> Result {
+    wtx.open_table(METAPROTOCOL_AND_SEQUENCE_NUMBER)?;
+    Ok(())
+  }
+
+  fn index_block(&self, wtx: &WriteTransaction, block: &HookBlock) -> Result {
+    let mut metaprotocol_and_sequence_number = wtx.open_table(METAPROTOCOL_AND_SEQUENCE_NUMBER)?;
+
+    for (_tx_index, event) in block.events {
+      let Event::InscriptionCreated {
+        inscription_id,
+        sequence_number,
+        ..
+      } = event
+      else {
+        continue;
+      };
+
+      let Some(inscription) = block.inscription(*inscription_id) else {
+        continue;
+      };
+
+      if let Some(metaprotocol) = inscription.metaprotocol() {
+        metaprotocol_and_sequence_number.insert((metaprotocol, *sequence_number), ())?;
+      }
+    }
+
+    Ok(())
+  }
+}
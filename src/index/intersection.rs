@@ -0,0 +1,117 @@
+use {super::*, std::iter::Peekable};
+
+/// Iterates over the sequence numbers present in all of a set of iterators,
+/// each of which must yield sequence numbers in the same order.
+pub(crate) struct Intersection<I: Iterator<Item = Result<u32, StorageError>>> {
+  descending: bool,
+  iterators: Vec<Peekable<I>>,
+}
+
+impl<I: Iterator<Item = Result<u32, StorageError>>> Intersection<I> {
+  pub(crate) fn new(iterators: Vec<I>, descending: bool) -> Self {
+    Self {
+      descending,
+      iterators: iterators.into_iter().map(Iterator::peekable).collect(),
+    }
+  }
+
+  fn head(iterator: &mut Peekable<I>) -> Result<Option<u32>, StorageError> {
+    match iterator.peek() {
+      None => Ok(None),
+      Some(Ok(sequence_number)) => Ok(Some(*sequence_number)),
+      Some(Err(_)) => Err(iterator.next().unwrap().unwrap_err()),
+    }
+  }
+
+  fn advance(&mut self) -> Result<Option<u32>, StorageError> {
+    let Some(first) = self.iterators.first_mut() else {
+      return Ok(None);
+    };
+
+    let Some(mut target) = Self::head(first)? else {
+      return Ok(None);
+    };
+
+    loop {
+      let mut agreed = true;
+
+      for iterator in &mut self.iterators {
+        loop {
+          let Some(head) = Self::head(iterator)? else {
+            return Ok(None);
+          };
+
+          let behind = if self.descending {
+            head > target
+          } else {
+            head < target
+          };
+
+          if behind {
+            iterator.next();
+            continue;
+          }
+
+          if head != target {
+            target = head;
+            agreed = false;
+          }
+
+          break;
+        }
+      }
+
+      if agreed {
+        for iterator in &mut self.iterators {
+          iterator.next();
+        }
+
+        return Ok(Some(target));
+      }
+    }
+  }
+}
+
+impl<I: Iterator<Item = Result<u32, StorageError>>> Iterator for Intersection<I> {
+  type Item = Result<u32, StorageError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.advance().transpose()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(iterators: &[&[u32]], descending: bool, expected: &[u32]) {
+    assert_eq!(
+      Intersection::new(
+        iterators
+          .iter()
+          .map(|iterator| iterator.iter().copied().map(Ok))
+          .collect(),
+        descending,
+      )
+      .collect::<Result<Vec<u32>, StorageError>>()
+      .unwrap(),
+      expected,
+    );
+  }
+
+  #[test]
+  fn intersection() {
+    case(&[], false, &[]);
+    case(&[&[1, 2, 3]], false, &[1, 2, 3]);
+    case(&[&[1, 2, 3], &[2, 3, 4]], false, &[2, 3]);
+    case(
+      &[&[1, 3, 5, 7], &[2, 3, 4, 7], &[0, 3, 7, 8]],
+      false,
+      &[3, 7],
+    );
+    case(&[&[1, 2], &[3, 4]], false, &[]);
+    case(&[&[1, 2], &[]], false, &[]);
+    case(&[&[7, 5, 3, 1], &[8, 7, 3]], true, &[7, 3]);
+  }
+}
@@ -19,8 +19,6 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
 const CHAIN_TIP_DISTANCE: u32 = 21;
 
 pub(crate) struct Reorg {}
@@ -32,8 +30,10 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
+        let savepoint_interval = index.settings.savepoint_interval();
+
         let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+          (index.settings.savepoint_count() - 1) * savepoint_interval + height % savepoint_interval;
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -60,15 +60,13 @@ impl Reorg {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
-    let mut wtx = index.begin_write()?;
-
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let oldest_savepoint = index
+      .begin_write()?
+      .list_persistent_savepoints()?
+      .min()
+      .unwrap();
 
-    wtx.restore_savepoint(&oldest_savepoint)?;
-
-    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-    wtx.commit()?;
+    Self::restore_savepoint(index, oldest_savepoint)?;
 
     log::info!(
       "successfully rolled back database to height {}",
@@ -78,18 +76,104 @@ impl Reorg {
     Ok(())
   }
 
+  /// Rolls back the index to the most recent savepoint at or below `height`,
+  /// and returns the height of the last block in the rolled back index.
+  pub(crate) fn rollback(index: &Index, height: u32) -> Result<u32> {
+    if let redb::Durability::None = index.durability {
+      panic!("set index durability to `Durability::Immediate` to test rollback");
+    }
+
+    let savepoints = Self::savepoints(index)?;
+
+    let Some((&id, &savepoint_height)) = savepoints
+      .iter()
+      .filter(|(_id, savepoint_height)| **savepoint_height <= height)
+      .max_by_key(|(_id, savepoint_height)| **savepoint_height)
+    else {
+      bail!(
+        "no savepoint at or below height {height}, available savepoints: {}",
+        if savepoints.is_empty() {
+          "none".into()
+        } else {
+          savepoints
+            .values()
+            .map(|height| height.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+        }
+      );
+    };
+
+    log::info!("rolling back database to savepoint at height {savepoint_height}");
+
+    Self::restore_savepoint(index, id)?;
+
+    Ok(savepoint_height)
+  }
+
+  fn savepoints(index: &Index) -> Result<BTreeMap<u64, u32>> {
+    let wtx = index.begin_write()?;
+
+    let heights = wtx
+      .open_table(SAVEPOINT_TO_HEIGHT)?
+      .iter()?
+      .map(|result| result.map(|(id, height)| (id.value(), height.value())))
+      .collect::<Result<BTreeMap<u64, u32>, StorageError>>()?;
+
+    Ok(
+      wtx
+        .list_persistent_savepoints()?
+        .filter_map(|id| heights.get(&id).map(|height| (id, *height)))
+        .collect(),
+    )
+  }
+
+  fn restore_savepoint(index: &Index, id: u64) -> Result {
+    let savepoints = Self::savepoints(index)?;
+
+    let mut wtx = index.begin_write()?;
+
+    let savepoint = wtx.get_persistent_savepoint(id)?;
+
+    wtx.restore_savepoint(&savepoint)?;
+
+    // The savepoint height table is rolled back along with everything else,
+    // so re-record the heights of the savepoints that survived the restore.
+    let remaining = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+
+    {
+      let mut savepoint_to_height = wtx.open_table(SAVEPOINT_TO_HEIGHT)?;
+
+      savepoint_to_height.retain(|_id, _height| false)?;
+
+      for id in remaining {
+        if let Some(height) = savepoints.get(&id) {
+          savepoint_to_height.insert(id, height)?;
+        }
+      }
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
   pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
     if let redb::Durability::None = index.durability {
       return Ok(());
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
+    let savepoint_interval = index.settings.savepoint_interval();
+
+    if (height < savepoint_interval || height % savepoint_interval == 0)
       && u32::try_from(
         index
           .settings
           .bitcoin_rpc_client(None)?
-          .get_blockchain_info()?
-          .headers,
+          .call::<serde_json::Value>("getblockchaininfo", &[])?["headers"]
+          .as_u64()
+          .unwrap_or_default(),
       )
       .unwrap()
       .saturating_sub(height)
@@ -97,10 +181,20 @@ impl Reorg {
     {
       let wtx = index.begin_write()?;
 
-      let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+      let mut savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
-        wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
+      savepoints.sort();
+
+      let excess = (savepoints.len() + 1)
+        .saturating_sub(usize::try_from(index.settings.savepoint_count()).unwrap());
+
+      {
+        let mut savepoint_to_height = wtx.open_table(SAVEPOINT_TO_HEIGHT)?;
+
+        for id in savepoints.into_iter().take(excess) {
+          wtx.delete_persistent_savepoint(id)?;
+          savepoint_to_height.remove(id)?;
+        }
       }
 
       Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
@@ -109,7 +203,13 @@ impl Reorg {
       let wtx = index.begin_write()?;
 
       log::debug!("creating savepoint at height {}", height);
-      wtx.persistent_savepoint()?;
+      let id = wtx.persistent_savepoint()?;
+
+      // `height` is the number of blocks in the index, so the savepoint
+      // contains blocks up to and including `height - 1`.
+      wtx
+        .open_table(SAVEPOINT_TO_HEIGHT)?
+        .insert(id, height.saturating_sub(1))?;
 
       Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
       wtx.commit()?;
@@ -132,10 +132,50 @@ impl Context {
       balances.sort_by_key(|(id, _)| *id);
     }
 
+    let mut holders: BTreeMap<(RuneId, ScriptBuf), u128> = BTreeMap::new();
+
+    for (outpoint, balances) in balances.iter() {
+      let script_pubkey = self.core.tx_by_id(outpoint.txid).output[outpoint.vout.into_usize()]
+        .script_pubkey
+        .clone();
+
+      for (id, balance) in balances {
+        *holders.entry((*id, script_pubkey.clone())).or_default() += *balance;
+      }
+    }
+
+    // holder counts are derived from the expected balances
+    for (id, entry) in runes.iter_mut() {
+      entry.holders = holders
+        .keys()
+        .filter(|(holder_id, _script_pubkey)| holder_id == id)
+        .count()
+        .try_into()
+        .unwrap();
+    }
+
     pretty_assert_eq!(runes, self.index.runes().unwrap());
 
     pretty_assert_eq!(balances, self.index.get_rune_balances().unwrap());
 
+    for (id, _entry) in runes.iter() {
+      let mut expected = holders
+        .iter()
+        .filter(|((holder_id, _script_pubkey), _balance)| holder_id == id)
+        .map(|((_id, script_pubkey), balance)| (script_pubkey.clone(), *balance))
+        .collect::<Vec<(ScriptBuf, u128)>>();
+
+      expected.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+
+      pretty_assert_eq!(
+        (expected, false),
+        self
+          .index
+          .get_rune_holders_paginated(*id, usize::MAX - 1, 0)
+          .unwrap()
+      );
+    }
+
     let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
 
     for (_, balances) in balances {
@@ -1,5 +1,10 @@
 use {
-  self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
+  self::{
+    block_source::{BlkFileSource, BlkFiles, BlockSource, RpcBlockSource},
+    event_emitter::EventEmitter,
+    inscription_updater::InscriptionUpdater,
+    rune_updater::RuneUpdater,
+  },
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
   tokio::sync::{
@@ -8,6 +13,8 @@ use {
   },
 };
 
+mod block_source;
+mod event_emitter;
 mod inscription_updater;
 mod rune_updater;
 
@@ -162,25 +169,31 @@ impl Updater<'_> {
 
     let client = index.settings.bitcoin_rpc_client(None)?;
 
-    thread::spawn(move || loop {
-      if let Some(height_limit) = height_limit {
-        if height >= height_limit {
-          break;
-        }
-      }
+    let settings = index.settings.clone();
+
+    thread::spawn(move || {
+      let mut source = Self::block_source(&settings, client, height);
 
-      match Self::get_block_with_retries(&client, height, first_index_height) {
-        Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
-            log::info!("Block receiver disconnected: {err}");
+      loop {
+        if let Some(height_limit) = height_limit {
+          if height >= height_limit {
             break;
           }
-          height += 1;
         }
-        Ok(None) => break,
-        Err(err) => {
-          log::error!("failed to fetch block {height}: {err}");
-          break;
+
+        match source.get_block(height, height < first_index_height) {
+          Ok(Some(block)) => {
+            if let Err(err) = tx.send(block.into()) {
+              log::info!("Block receiver disconnected: {err}");
+              break;
+            }
+            height += 1;
+          }
+          Ok(None) => break,
+          Err(err) => {
+            log::error!("failed to fetch block {height}: {err}");
+            break;
+          }
         }
       }
     });
@@ -188,49 +201,23 @@ impl Updater<'_> {
     Ok(rx)
   }
 
-  fn get_block_with_retries(
-    client: &Client,
-    height: u32,
-    first_index_height: u32,
-  ) -> Result<Option<Block>> {
-    let mut errors = 0;
-    loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if height >= first_index_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
-        Err(err) => {
-          if cfg!(test) {
-            return Err(err);
-          }
-
-          errors += 1;
-          let seconds = 1 << errors;
-          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
-
-          if seconds > 120 {
-            log::error!("would sleep for more than 120s, giving up");
-            return Err(err);
-          }
-
-          thread::sleep(Duration::from_secs(seconds));
+  fn block_source(settings: &Settings, client: Client, height: u32) -> Box<dyn BlockSource> {
+    if settings.index_blk_files() {
+      match BlkFiles::open(settings, &client, height) {
+        Ok(Some((blk_files, locations))) => {
+          return Box::new(BlkFileSource::new(
+            blk_files,
+            height,
+            locations,
+            RpcBlockSource::new(client),
+          ))
         }
-        Ok(result) => return Ok(result),
+        Ok(None) => {}
+        Err(err) => log::warn!("failed to read blk files, fetching blocks over RPC: {err}"),
       }
     }
+
+    Box::new(RpcBlockSource::new(client))
   }
 
   fn spawn_fetcher(index: &Index) -> Result<(mpsc::Sender<OutPoint>, broadcast::Receiver<TxOut>)> {
@@ -332,6 +319,26 @@ impl Updater<'_> {
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
 
+    let mut event_emitter = EventEmitter {
+      block_events: if self.index.hooks.is_empty() {
+        None
+      } else {
+        Some(Vec::new())
+      },
+      block_inscriptions: if self.index.hooks.is_empty() {
+        None
+      } else {
+        Some(HashMap::new())
+      },
+      event_key_to_event: if self.index.index_events {
+        Some(wtx.open_table(EVENT_KEY_TO_EVENT)?)
+      } else {
+        None
+      },
+      event_sender: self.index.event_sender.clone(),
+      sequence_number: 0,
+    };
+
     if self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats {
       self.index_utxo_entries(
         &block,
@@ -339,6 +346,7 @@ impl Updater<'_> {
         output_sender,
         utxo_cache,
         wtx,
+        &mut event_emitter,
         &mut inscription_id_to_sequence_number,
         &mut statistic_to_count,
         &mut sat_ranges_written,
@@ -348,6 +356,9 @@ impl Updater<'_> {
 
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut outpoint_to_rune_holder = wtx.open_table(OUTPOINT_TO_RUNE_HOLDER)?;
+      let mut rune_balance_to_holder = wtx.open_table(RUNE_BALANCE_TO_HOLDER)?;
+      let mut rune_holder_to_balance = wtx.open_table(RUNE_HOLDER_TO_BALANCE)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
@@ -358,19 +369,39 @@ impl Updater<'_> {
         .map(|x| x.value())
         .unwrap_or(0);
 
+      let mut rune_activity_key_to_rune_activity = if self.index.index_rune_activity {
+        Some(wtx.open_table(RUNE_ACTIVITY_KEY_TO_RUNE_ACTIVITY)?)
+      } else {
+        None
+      };
+
+      let mut address_history_key_to_transaction = if self.index.index_address_history {
+        Some(wtx.open_table(ADDRESS_HISTORY_KEY_TO_TRANSACTION)?)
+      } else {
+        None
+      };
+
       let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
+        address_history: address_history_key_to_transaction.as_mut(),
+        activity_key_to_activity: rune_activity_key_to_rune_activity.as_mut(),
+        activity_sequence_number: 0,
+        balance_to_holder: &mut rune_balance_to_holder,
         block_time: block.header.time,
         burned: HashMap::new(),
         client: &self.index.client,
+        event_emitter: &mut event_emitter,
         height: self.height,
+        holder_to_balance: &mut rune_holder_to_balance,
+        holders: HashMap::new(),
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
-        minimum: Rune::minimum_at_height(
-          self.index.settings.chain().network(),
-          Height(self.height),
-        ),
+        minimum: self
+          .index
+          .settings
+          .chain()
+          .minimum_rune_at_height(Height(self.height)),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outpoint_to_holder: &mut outpoint_to_rune_holder,
         rune_to_id: &mut rune_to_rune_id,
         runes,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
@@ -385,6 +416,24 @@ impl Updater<'_> {
       rune_updater.update()?;
     }
 
+    if let (Some(events), Some(inscriptions)) = (
+      &event_emitter.block_events,
+      &event_emitter.block_inscriptions,
+    ) {
+      let hook_block = HookBlock {
+        events,
+        height: self.height,
+        inscriptions,
+        transactions: &block.txdata,
+      };
+
+      for hook in &self.index.hooks {
+        hook
+          .index_block(wtx, &hook_block)
+          .with_context(|| format!("`{}` hook failed at height {}", hook.name(), self.height))?;
+      }
+    }
+
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
     self.height += 1;
@@ -405,15 +454,46 @@ impl Updater<'_> {
     output_sender: &mut mpsc::Sender<OutPoint>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     wtx: &'wtx WriteTransaction,
+    event_emitter: &mut EventEmitter<'wtx>,
     inscription_id_to_sequence_number: &mut Table<'wtx, (u128, u128, u32), u32>,
     statistic_to_count: &mut Table<'wtx, u64, u64>,
     sat_ranges_written: &mut u64,
     outputs_in_block: &mut u64,
   ) -> Result<(), Error> {
+    let mut address_history_key_to_transaction = if self.index.index_address_history {
+      Some(wtx.open_table(ADDRESS_HISTORY_KEY_TO_TRANSACTION)?)
+    } else {
+      None
+    };
+    let mut content_hash_to_sequence_number = if self.index.index_content_hashes {
+      Some(wtx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?)
+    } else {
+      None
+    };
+    let mut sequence_number_to_content_hash = if self.index.index_content_hashes {
+      Some(wtx.open_table(SEQUENCE_NUMBER_TO_CONTENT_HASH)?)
+    } else {
+      None
+    };
+    let mut content_type_and_sequence_number = if self.index.index_inscription_attributes {
+      Some(wtx.open_table(CONTENT_TYPE_AND_SEQUENCE_NUMBER)?)
+    } else {
+      None
+    };
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut inscription_transfer_key_to_transfer = if self.index.index_inscription_history {
+      Some(wtx.open_table(INSCRIPTION_TRANSFER_KEY_TO_TRANSFER)?)
+    } else {
+      None
+    };
+    let mut media_and_sequence_number = if self.index.index_inscription_attributes {
+      Some(wtx.open_table(MEDIA_AND_SEQUENCE_NUMBER)?)
+    } else {
+      None
+    };
     let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
     let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
@@ -421,6 +501,11 @@ impl Updater<'_> {
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut size_bucket_and_sequence_number = if self.index.index_inscription_attributes {
+      Some(wtx.open_table(SIZE_BUCKET_AND_SEQUENCE_NUMBER)?)
+    } else {
+      None
+    };
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
     let index_inscriptions = self.height >= self.index.settings.first_inscription_height()
@@ -500,7 +585,10 @@ impl Updater<'_> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      content_hash_to_sequence_number: content_hash_to_sequence_number.as_mut(),
+      content_type_and_sequence_number: content_type_and_sequence_number.as_mut(),
       cursed_inscription_count,
+      event_emitter,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
@@ -508,14 +596,19 @@ impl Updater<'_> {
       id_to_sequence_number: inscription_id_to_sequence_number,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
+      media_and_sequence_number: media_and_sequence_number.as_mut(),
       next_sequence_number,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
+      sequence_number_to_content_hash: sequence_number_to_content_hash.as_mut(),
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      size_bucket_and_sequence_number: size_bucket_and_sequence_number.as_mut(),
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
+      transfer_key_to_transfer: inscription_transfer_key_to_transfer.as_mut(),
+      transfer_number: 0,
       unbound_inscriptions,
     };
 
@@ -635,6 +728,7 @@ impl Updater<'_> {
 
       if index_inscriptions {
         inscription_updater.index_inscriptions(
+          u32::try_from(tx_offset).unwrap(),
           tx,
           *txid,
           &input_utxo_entries,
@@ -645,6 +739,18 @@ impl Updater<'_> {
         )?;
       }
 
+      if let Some(address_history_key_to_transaction) = &mut address_history_key_to_transaction {
+        self.index_transaction_address_history(
+          u32::try_from(tx_offset).unwrap(),
+          *txid,
+          address_history_key_to_transaction,
+          inscription_updater.sequence_number_to_entry,
+          &input_utxo_entries,
+          &output_utxo_entries,
+          index_inscriptions,
+        )?;
+      }
+
       for (vout, output_utxo_entry) in output_utxo_entries.into_iter().enumerate() {
         let vout = u32::try_from(vout).unwrap();
         utxo_cache.insert(OutPoint { txid: *txid, vout }, output_utxo_entry);
@@ -716,6 +822,77 @@ impl Updater<'_> {
     Ok(())
   }
 
+  fn index_transaction_address_history(
+    &self,
+    tx_index: u32,
+    txid: Txid,
+    address_history_key_to_transaction: &mut Table<AddressHistoryKeyValue, &[u8]>,
+    sequence_number_to_inscription_entry: &Table<u32, InscriptionEntryValue>,
+    input_utxo_entries: &[ParsedUtxoEntry],
+    output_utxo_entries: &[UtxoEntryBuf],
+    index_inscriptions: bool,
+  ) -> Result {
+    let inscription_ids = |entry: &ParsedUtxoEntry| -> Result<Vec<InscriptionId>> {
+      if !index_inscriptions {
+        return Ok(Vec::new());
+      }
+
+      entry
+        .parse_inscriptions()
+        .into_iter()
+        .map(|(sequence_number, _offset)| {
+          Ok(
+            InscriptionEntry::load(
+              sequence_number_to_inscription_entry
+                .get(sequence_number)?
+                .unwrap()
+                .value(),
+            )
+            .id,
+          )
+        })
+        .collect()
+    };
+
+    let output_utxo_entries = output_utxo_entries
+      .iter()
+      .map(|entry| entry.parse(self.index))
+      .collect::<Vec<ParsedUtxoEntry>>();
+
+    let mut transactions: BTreeMap<&[u8], api::AddressTransaction> = BTreeMap::new();
+
+    for entry in input_utxo_entries {
+      let transaction = transactions
+        .entry(entry.script_pubkey())
+        .or_insert_with(|| api::AddressTransaction::new(self.height, txid));
+
+      transaction.sent += entry.total_value();
+      transaction
+        .inscriptions_sent
+        .extend(inscription_ids(entry)?);
+    }
+
+    for entry in &output_utxo_entries {
+      let transaction = transactions
+        .entry(entry.script_pubkey())
+        .or_insert_with(|| api::AddressTransaction::new(self.height, txid));
+
+      transaction.received += entry.total_value();
+      transaction
+        .inscriptions_received
+        .extend(inscription_ids(entry)?);
+    }
+
+    for (script_pubkey, transaction) in transactions {
+      address_history_key_to_transaction.insert(
+        (script_pubkey, self.height, tx_index),
+        serde_json::to_vec(&transaction)?.as_slice(),
+      )?;
+    }
+
+    Ok(())
+  }
+
   fn index_transaction_output_script_pubkeys(
     &mut self,
     tx: &Transaction,
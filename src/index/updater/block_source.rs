@@ -0,0 +1,533 @@
+use {
+  super::*,
+  std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+  },
+};
+
+/// Blocks within this many blocks of the chain tip are always fetched over
+/// RPC, since they may not yet have been flushed to disk and are the most
+/// likely to be reorged.
+const RPC_DEPTH: u32 = 100;
+
+pub(crate) trait BlockSource: Send {
+  /// Returns the block at `height`, or `None` if it doesn't exist yet. If
+  /// `header_only` is true, the returned block may have no transactions.
+  fn get_block(&mut self, height: u32, header_only: bool) -> Result<Option<Block>>;
+}
+
+pub(crate) struct RpcBlockSource {
+  client: Client,
+}
+
+impl RpcBlockSource {
+  pub(crate) fn new(client: Client) -> Self {
+    Self { client }
+  }
+}
+
+impl BlockSource for RpcBlockSource {
+  fn get_block(&mut self, height: u32, header_only: bool) -> Result<Option<Block>> {
+    let mut errors = 0;
+    loop {
+      match self
+        .client
+        .get_block_hash(height.into())
+        .into_option()
+        .and_then(|option| {
+          option
+            .map(|hash| {
+              if header_only {
+                Ok(Block {
+                  header: self.client.get_block_header(&hash)?,
+                  txdata: Vec::new(),
+                })
+              } else {
+                Ok(self.client.get_block(&hash)?)
+              }
+            })
+            .transpose()
+        }) {
+        Err(err) => {
+          if cfg!(test) {
+            return Err(err);
+          }
+
+          errors += 1;
+          let seconds = 1 << errors;
+          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
+
+          if seconds > 120 {
+            log::error!("would sleep for more than 120s, giving up");
+            return Err(err);
+          }
+
+          thread::sleep(Duration::from_secs(seconds));
+        }
+        Ok(result) => return Ok(result),
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BlockLocation {
+  file: u32,
+  hash: BlockHash,
+  offset: u64,
+  size: u32,
+}
+
+/// Index of the blocks stored in a Bitcoin Core `blocks` directory, built by
+/// scanning the headers of every record in the `blk*.dat` files, so that
+/// Bitcoin Core's LevelDB block index isn't needed.
+pub(crate) struct BlkFiles {
+  blocks: HashMap<BlockHash, (BlockHash, BlockLocation)>,
+  dir: PathBuf,
+  file: Option<(u32, File)>,
+  xor: [u8; 8],
+}
+
+impl BlkFiles {
+  /// Scans the blk files in the blocks directory of `settings`, and returns
+  /// the locations of the blocks from height `start` up to `RPC_DEPTH` blocks
+  /// below the tip, or `None` if the index is close enough to the tip that
+  /// scanning isn't worthwhile.
+  pub(crate) fn open(
+    settings: &Settings,
+    client: &Client,
+    start: u32,
+  ) -> Result<Option<(Self, Vec<BlockLocation>)>> {
+    let tip = u32::try_from(client.get_block_count()?).unwrap();
+
+    let Some(end) = tip.checked_sub(RPC_DEPTH).filter(|end| *end >= start) else {
+      return Ok(None);
+    };
+
+    let dir = settings.bitcoin_blocks_dir();
+
+    log::info!("Scanning blk files in {}", dir.display());
+
+    let blk_files = Self::scan(&dir, settings.chain())?;
+
+    let locations = blk_files.chain(start, end, client.get_block_hash(end.into())?)?;
+
+    log::info!(
+      "Reading blocks {start} through {end} from blk files in {}",
+      dir.display()
+    );
+
+    Ok(Some((blk_files, locations)))
+  }
+
+  pub(crate) fn scan(dir: &Path, chain: Chain) -> Result<Self> {
+    let xor = match fs::read(dir.join("xor.dat")) {
+      Ok(key) => key
+        .try_into()
+        .map_err(|_| anyhow!("invalid xor key in {}", dir.display()))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => [0; 8],
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut blk_files = Self {
+      blocks: HashMap::new(),
+      dir: dir.into(),
+      file: None,
+      xor,
+    };
+
+    let magic = chain.magic().to_bytes();
+
+    for file in 0.. {
+      let path = blk_files.path(file);
+
+      if !path.is_file() {
+        if file == 0 {
+          bail!("no blk files found in {}", dir.display());
+        }
+        break;
+      }
+
+      let len = fs::metadata(&path)?.len();
+      let mut offset = 0;
+
+      while offset + 88 <= len {
+        let mut record = [0; 88];
+        blk_files.read_at(file, offset, &mut record)?;
+
+        // blk files are preallocated and zero-filled past the last block
+        if record[..4] != magic {
+          break;
+        }
+
+        let size = u32::from_le_bytes(record[4..8].try_into().unwrap());
+
+        if offset + 8 + u64::from(size) > len {
+          break;
+        }
+
+        let header = consensus::deserialize::<Header>(&record[8..])?;
+
+        let hash = header.block_hash();
+
+        blk_files.blocks.insert(
+          hash,
+          (
+            header.prev_blockhash,
+            BlockLocation {
+              file,
+              hash,
+              offset: offset + 8,
+              size,
+            },
+          ),
+        );
+
+        offset += 8 + u64::from(size);
+      }
+    }
+
+    Ok(blk_files)
+  }
+
+  /// Returns the locations of the blocks from height `start` up to and
+  /// including `hash`, which must be at height `end`.
+  pub(crate) fn chain(&self, start: u32, end: u32, hash: BlockHash) -> Result<Vec<BlockLocation>> {
+    let mut locations = Vec::new();
+    let mut next = hash;
+
+    for height in (start..=end).rev() {
+      let Some((prev, location)) = self.blocks.get(&next) else {
+        bail!("block {next} at height {height} not found in blk files");
+      };
+
+      locations.push(*location);
+      next = *prev;
+    }
+
+    locations.reverse();
+
+    Ok(locations)
+  }
+
+  pub(crate) fn read_block(&mut self, location: BlockLocation, header_only: bool) -> Result<Block> {
+    let block = if header_only {
+      let mut buffer = [0; 80];
+      self.read_at(location.file, location.offset, &mut buffer)?;
+      Block {
+        header: consensus::deserialize(&buffer)?,
+        txdata: Vec::new(),
+      }
+    } else {
+      let mut buffer = vec![0; location.size.try_into().unwrap()];
+      self.read_at(location.file, location.offset, &mut buffer)?;
+      consensus::deserialize::<Block>(&buffer)?
+    };
+
+    ensure!(
+      block.block_hash() == location.hash,
+      "block in {} at offset {} does not match expected hash {}",
+      self.path(location.file).display(),
+      location.offset,
+      location.hash,
+    );
+
+    Ok(block)
+  }
+
+  fn path(&self, file: u32) -> PathBuf {
+    self.dir.join(format!("blk{file:05}.dat"))
+  }
+
+  fn read_at(&mut self, file: u32, offset: u64, buffer: &mut [u8]) -> Result {
+    if self.file.as_ref().map(|(number, _)| *number) != Some(file) {
+      self.file = Some((file, File::open(self.path(file))?));
+    }
+
+    let (_, handle) = self.file.as_mut().unwrap();
+
+    handle.seek(SeekFrom::Start(offset))?;
+    handle.read_exact(buffer)?;
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+      *byte ^= self.xor[usize::try_from((offset + i as u64) % 8).unwrap()];
+    }
+
+    Ok(())
+  }
+}
+
+/// Reads blocks from `blk*.dat` files, falling back to `fallback` for blocks
+/// past the end of `locations` and for any block that can't be read from disk.
+pub(crate) struct BlkFileSource<F> {
+  blk_files: BlkFiles,
+  fallback: F,
+  locations: Vec<BlockLocation>,
+  start: u32,
+}
+
+impl<F: BlockSource> BlkFileSource<F> {
+  pub(crate) fn new(
+    blk_files: BlkFiles,
+    start: u32,
+    locations: Vec<BlockLocation>,
+    fallback: F,
+  ) -> Self {
+    Self {
+      blk_files,
+      fallback,
+      locations,
+      start,
+    }
+  }
+}
+
+impl<F: BlockSource> BlockSource for BlkFileSource<F> {
+  fn get_block(&mut self, height: u32, header_only: bool) -> Result<Option<Block>> {
+    if let Some(location) = height
+      .checked_sub(self.start)
+      .and_then(|i| self.locations.get(usize::try_from(i).unwrap()))
+    {
+      match self.blk_files.read_block(*location, header_only) {
+        Ok(block) => return Ok(Some(block)),
+        Err(err) => log::warn!("failed to read block {height} from blk files: {err}"),
+      }
+    }
+
+    self.fallback.get_block(height, header_only)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, bitcoin::blockdata::constants::genesis_block};
+
+  struct Fixture {
+    blocks: Vec<Block>,
+    tempdir: TempDir,
+  }
+
+  impl Fixture {
+    fn new(count: usize) -> Self {
+      let mut blocks = vec![genesis_block(Network::Regtest)];
+
+      for i in 1..count {
+        let prev = blocks.last().unwrap();
+        blocks.push(Block {
+          header: Header {
+            prev_blockhash: prev.block_hash(),
+            nonce: i.try_into().unwrap(),
+            ..prev.header
+          },
+          txdata: prev.txdata.clone(),
+        });
+      }
+
+      Self {
+        blocks,
+        tempdir: TempDir::new().unwrap(),
+      }
+    }
+
+    /// Writes the blocks at `heights`, in order, to blk file `file`.
+    fn write(&self, file: u32, heights: &[usize], xor: [u8; 8]) {
+      let mut contents = Vec::new();
+
+      for height in heights {
+        let block = consensus::serialize(&self.blocks[*height]);
+        contents.extend_from_slice(&Network::Regtest.magic().to_bytes());
+        contents.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+        contents.extend_from_slice(&block);
+      }
+
+      // preallocated space at the end of the file
+      contents.extend_from_slice(&[0; 100]);
+
+      for (i, byte) in contents.iter_mut().enumerate() {
+        *byte ^= xor[i % 8];
+      }
+
+      fs::write(
+        self.tempdir.path().join(format!("blk{file:05}.dat")),
+        contents,
+      )
+      .unwrap();
+    }
+
+    fn scan(&self) -> BlkFiles {
+      BlkFiles::scan(self.tempdir.path(), Chain::Regtest).unwrap()
+    }
+
+    fn source(&self, start: u32, end: u32) -> BlkFileSource<Empty> {
+      let blk_files = self.scan();
+      let locations = blk_files
+        .chain(
+          start,
+          end,
+          self.blocks[usize::try_from(end).unwrap()].block_hash(),
+        )
+        .unwrap();
+      BlkFileSource::new(blk_files, start, locations, Empty)
+    }
+  }
+
+  struct Empty;
+
+  impl BlockSource for Empty {
+    fn get_block(&mut self, _height: u32, _header_only: bool) -> Result<Option<Block>> {
+      Ok(None)
+    }
+  }
+
+  #[test]
+  fn blocks_are_read_from_blk_files() {
+    let fixture = Fixture::new(5);
+    fixture.write(0, &[0, 1, 2], [0; 8]);
+    fixture.write(1, &[4, 3], [0; 8]);
+
+    let mut source = fixture.source(0, 4);
+
+    for (height, block) in fixture.blocks.iter().enumerate() {
+      assert_eq!(
+        source
+          .get_block(height.try_into().unwrap(), false)
+          .unwrap()
+          .unwrap(),
+        *block,
+      );
+    }
+
+    assert_eq!(source.get_block(5, false).unwrap(), None);
+  }
+
+  #[test]
+  fn header_only_blocks_have_no_transactions() {
+    let fixture = Fixture::new(2);
+    fixture.write(0, &[0, 1], [0; 8]);
+
+    let mut source = fixture.source(0, 1);
+
+    assert_eq!(
+      source.get_block(1, true).unwrap().unwrap(),
+      Block {
+        header: fixture.blocks[1].header,
+        txdata: Vec::new(),
+      },
+    );
+  }
+
+  #[test]
+  fn xored_blk_files_are_read() {
+    let fixture = Fixture::new(3);
+    let xor = [1, 2, 3, 4, 5, 6, 7, 8];
+    fs::write(fixture.tempdir.path().join("xor.dat"), xor).unwrap();
+    fixture.write(0, &[0, 1, 2], xor);
+
+    let mut source = fixture.source(1, 2);
+
+    assert_eq!(
+      source.get_block(2, false).unwrap().unwrap(),
+      fixture.blocks[2]
+    );
+    assert_eq!(source.get_block(0, false).unwrap(), None);
+  }
+
+  #[test]
+  fn stale_blocks_are_not_followed() {
+    let fixture = Fixture::new(3);
+
+    let stale = Block {
+      header: Header {
+        nonce: 1000,
+        ..fixture.blocks[2].header
+      },
+      txdata: fixture.blocks[2].txdata.clone(),
+    };
+
+    let mut contents = Vec::new();
+    for block in [
+      &fixture.blocks[0],
+      &fixture.blocks[1],
+      &stale,
+      &fixture.blocks[2],
+    ] {
+      let block = consensus::serialize(block);
+      contents.extend_from_slice(&Network::Regtest.magic().to_bytes());
+      contents.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+      contents.extend_from_slice(&block);
+    }
+    fs::write(fixture.tempdir.path().join("blk00000.dat"), contents).unwrap();
+
+    let mut source = fixture.source(0, 2);
+
+    assert_eq!(
+      source.get_block(2, false).unwrap().unwrap(),
+      fixture.blocks[2]
+    );
+  }
+
+  #[test]
+  fn missing_blocks_are_an_error() {
+    let fixture = Fixture::new(3);
+    fixture.write(0, &[0, 2], [0; 8]);
+
+    assert_eq!(
+      fixture
+        .scan()
+        .chain(0, 2, fixture.blocks[2].block_hash())
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "block {} at height 1 not found in blk files",
+        fixture.blocks[1].block_hash()
+      ),
+    );
+  }
+
+  #[test]
+  fn missing_blk_files_are_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    assert_eq!(
+      BlkFiles::scan(tempdir.path(), Chain::Regtest)
+        .err()
+        .unwrap()
+        .to_string(),
+      format!("no blk files found in {}", tempdir.path().display()),
+    );
+  }
+
+  #[test]
+  fn corrupt_blocks_fall_back() {
+    struct Fallback(Block);
+
+    impl BlockSource for Fallback {
+      fn get_block(&mut self, _height: u32, _header_only: bool) -> Result<Option<Block>> {
+        Ok(Some(self.0.clone()))
+      }
+    }
+
+    let fixture = Fixture::new(2);
+    fixture.write(0, &[0, 1], [0; 8]);
+
+    let blk_files = fixture.scan();
+    let locations = blk_files
+      .chain(0, 1, fixture.blocks[1].block_hash())
+      .unwrap();
+
+    // truncate the second block after scanning
+    let path = fixture.tempdir.path().join("blk00000.dat");
+    let contents = fs::read(&path).unwrap();
+    fs::write(&path, &contents[..contents.len() - 110]).unwrap();
+
+    let mut source =
+      BlkFileSource::new(blk_files, 0, locations, Fallback(fixture.blocks[1].clone()));
+
+    assert_eq!(
+      source.get_block(1, false).unwrap().unwrap(),
+      fixture.blocks[1]
+    );
+  }
+}
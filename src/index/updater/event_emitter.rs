@@ -0,0 +1,32 @@
+use super::*;
+
+pub(super) struct EventEmitter<'tx> {
+  pub(super) block_events: Option<Vec<(u32, Event)>>,
+  pub(super) block_inscriptions: Option<HashMap<InscriptionId, Inscription>>,
+  pub(super) event_key_to_event: Option<Table<'tx, EventKeyValue, &'static [u8]>>,
+  pub(super) event_sender: Option<mpsc::Sender<Event>>,
+  pub(super) sequence_number: u32,
+}
+
+impl EventEmitter<'_> {
+  pub(super) fn emit(&mut self, tx_index: u32, event: Event) -> Result {
+    if let Some(block_events) = &mut self.block_events {
+      block_events.push((tx_index, event.clone()));
+    }
+
+    if let Some(event_key_to_event) = &mut self.event_key_to_event {
+      event_key_to_event.insert(
+        (event.block_height(), tx_index, self.sequence_number),
+        serde_json::to_vec(&event)?.as_slice(),
+      )?;
+
+      self.sequence_number += 1;
+    }
+
+    if let Some(sender) = &self.event_sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+}
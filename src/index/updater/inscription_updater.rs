@@ -23,9 +23,12 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    attributes: Option<Attributes>,
+    content_hash: Option<[u8; 32]>,
     cursed: bool,
     fee: u64,
     hidden: bool,
+    inscription: Option<Box<Inscription>>,
     parents: Vec<InscriptionId>,
     reinscription: bool,
     unbound: bool,
@@ -37,9 +40,31 @@ enum Origin {
   },
 }
 
+/// Attributes of a new inscription which are kept in secondary indexes.
+#[derive(Debug, Clone)]
+struct Attributes {
+  content_type: Option<String>,
+  media: Media,
+  size: usize,
+}
+
+impl Attributes {
+  fn new(inscription: &Inscription) -> Self {
+    Self {
+      content_type: inscription.content_type().map(str::to_owned),
+      media: inscription.media(),
+      size: inscription.body().map(<[u8]>::len).unwrap_or_default(),
+    }
+  }
+}
+
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) content_hash_to_sequence_number:
+    Option<&'a mut MultimapTable<'tx, &'static [u8; 32], u32>>,
+  pub(super) content_type_and_sequence_number: Option<&'a mut Table<'tx, (&'static str, u32), ()>>,
   pub(super) cursed_inscription_count: u64,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
@@ -47,20 +72,27 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) id_to_sequence_number: &'a mut Table<'tx, InscriptionIdValue, u32>,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'tx, i32, u32>,
   pub(super) lost_sats: u64,
+  pub(super) media_and_sequence_number: Option<&'a mut Table<'tx, (&'static str, u32), ()>>,
   pub(super) next_sequence_number: u32,
   pub(super) reward: u64,
   pub(super) transaction_buffer: Vec<u8>,
   pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
+  pub(super) sequence_number_to_content_hash: Option<&'a mut Table<'tx, u32, &'static [u8; 32]>>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) size_bucket_and_sequence_number: Option<&'a mut Table<'tx, (u8, u32), ()>>,
   pub(super) timestamp: u32,
+  pub(super) transfer_key_to_transfer:
+    Option<&'a mut Table<'tx, InscriptionTransferKeyValue, &'static [u8]>>,
+  pub(super) transfer_number: u32,
   pub(super) unbound_inscriptions: u64,
 }
 
 impl InscriptionUpdater<'_, '_> {
   pub(super) fn index_inscriptions(
     &mut self,
+    tx_index: u32,
     tx: &Transaction,
     txid: Txid,
     input_utxo_entries: &[ParsedUtxoEntry],
@@ -196,9 +228,24 @@ impl InscriptionUpdater<'_, '_> {
           inscription_id,
           offset,
           origin: Origin::New {
+            attributes: self
+              .content_type_and_sequence_number
+              .is_some()
+              .then(|| Attributes::new(&inscription.payload)),
+            content_hash: self
+              .content_hash_to_sequence_number
+              .is_some()
+              .then(|| inscription.payload.content_hash())
+              .flatten()
+              .map(|hash| hash.to_byte_array()),
             cursed: curse.is_some() && !jubilant,
             fee: 0,
             hidden: inscription.payload.hidden(),
+            inscription: self
+              .event_emitter
+              .block_inscriptions
+              .is_some()
+              .then(|| Box::new(inscription.payload.clone())),
             parents: inscription.payload.parents(),
             reinscription: inscribed_offsets.contains_key(&offset),
             unbound: input_value == 0
@@ -306,6 +353,9 @@ impl InscriptionUpdater<'_, '_> {
         &mut output_utxo_entries[usize::try_from(new_satpoint.outpoint.vout).unwrap()];
 
       self.update_inscription_location(
+        tx_index,
+        txid,
+        Some(&tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey),
         input_sat_ranges,
         flotsam,
         new_satpoint,
@@ -323,6 +373,9 @@ impl InscriptionUpdater<'_, '_> {
           offset: self.lost_sats + flotsam.offset - output_value,
         };
         self.update_inscription_location(
+          tx_index,
+          txid,
+          None,
           input_sat_ranges,
           flotsam,
           new_satpoint,
@@ -344,6 +397,24 @@ impl InscriptionUpdater<'_, '_> {
     }
   }
 
+  fn index_attributes(&mut self, attributes: Attributes, sequence_number: u32) -> Result {
+    if let Some(content_type) = &attributes.content_type {
+      if let Some(table) = self.content_type_and_sequence_number.as_mut() {
+        table.insert((content_type.as_str(), sequence_number), ())?;
+      }
+    }
+
+    if let Some(table) = self.media_and_sequence_number.as_mut() {
+      table.insert((attributes.media.category(), sequence_number), ())?;
+    }
+
+    if let Some(table) = self.size_bucket_and_sequence_number.as_mut() {
+      table.insert((Index::size_bucket(attributes.size), sequence_number), ())?;
+    }
+
+    Ok(())
+  }
+
   fn calculate_sat(input_sat_ranges: Option<&Vec<&[u8]>>, input_offset: u64) -> Option<Sat> {
     let input_sat_ranges = input_sat_ranges?;
 
@@ -366,6 +437,9 @@ impl InscriptionUpdater<'_, '_> {
 
   fn update_inscription_location(
     &mut self,
+    tx_index: u32,
+    txid: Txid,
+    script_pubkey: Option<&Script>,
     input_sat_ranges: Option<&Vec<&[u8]>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
@@ -398,22 +472,50 @@ impl InscriptionUpdater<'_, '_> {
           )?;
         }
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
+        self.event_emitter.emit(
+          tx_index,
+          Event::InscriptionTransferred {
             block_height: self.height,
             inscription_id,
             new_location: new_satpoint,
             old_location: old_satpoint,
             sequence_number,
-          })?;
+          },
+        )?;
+
+        if let Some(transfer_key_to_transfer) = self.transfer_key_to_transfer.as_mut() {
+          let transfer = api::InscriptionTransfer {
+            address: script_pubkey.and_then(|script_pubkey| {
+              index
+                .settings
+                .chain()
+                .address_from_script(script_pubkey)
+                .ok()
+                .map(|address| uncheck(&address))
+            }),
+            height: self.height,
+            new_satpoint,
+            old_satpoint,
+            txid,
+          };
+
+          transfer_key_to_transfer.insert(
+            (sequence_number, self.height, self.transfer_number),
+            serde_json::to_vec(&transfer)?.as_slice(),
+          )?;
+
+          self.transfer_number += 1;
         }
 
         (false, sequence_number)
       }
       Origin::New {
+        attributes,
+        content_hash,
         cursed,
         fee,
         hidden,
+        inscription,
         parents,
         reinscription,
         unbound,
@@ -476,6 +578,22 @@ impl InscriptionUpdater<'_, '_> {
           self.sat_to_sequence_number.insert(&n, &sequence_number)?;
         }
 
+        if let Some(attributes) = attributes {
+          self.index_attributes(attributes, sequence_number)?;
+        }
+
+        if let Some((content_hash, table)) =
+          content_hash.zip(self.content_hash_to_sequence_number.as_mut())
+        {
+          table.insert(&content_hash, sequence_number)?;
+        }
+
+        if let Some((content_hash, table)) =
+          content_hash.zip(self.sequence_number_to_content_hash.as_mut())
+        {
+          table.insert(sequence_number, &content_hash)?;
+        }
+
         let parent_sequence_numbers = parents
           .iter()
           .map(|parent| {
@@ -493,16 +611,23 @@ impl InscriptionUpdater<'_, '_> {
           })
           .collect::<Result<Vec<u32>>>()?;
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
+        if let Some((inscription, inscriptions)) =
+          inscription.zip(self.event_emitter.block_inscriptions.as_mut())
+        {
+          inscriptions.insert(inscription_id, *inscription);
+        }
+
+        self.event_emitter.emit(
+          tx_index,
+          Event::InscriptionCreated {
             block_height: self.height,
             charms,
             inscription_id,
             location: (!unbound).then_some(new_satpoint),
             parent_inscription_ids: parents,
             sequence_number,
-          })?;
-        }
+          },
+        )?;
 
         self.sequence_number_to_entry.insert(
           sequence_number,
@@ -1,15 +1,23 @@
 use super::*;
 
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
+  pub(super) activity_key_to_activity:
+    Option<&'a mut Table<'tx, RuneActivityKeyValue, &'static [u8]>>,
+  pub(super) activity_sequence_number: u32,
+  pub(super) address_history: Option<&'a mut Table<'tx, AddressHistoryKeyValue, &'static [u8]>>,
+  pub(super) balance_to_holder: &'a mut Table<'tx, RuneBalanceValue, ()>,
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
+  pub(super) event_emitter: &'a mut EventEmitter<'tx>,
   pub(super) height: u32,
+  pub(super) holder_to_balance: &'a mut Table<'tx, RuneHolderValue, u128>,
+  pub(super) holders: HashMap<RuneId, i64>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_holder: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
@@ -21,7 +29,7 @@ impl RuneUpdater<'_, '_, '_> {
   pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
     let artifact = Runestone::decipher(tx);
 
-    let mut unallocated = self.unallocated(tx)?;
+    let (mut unallocated, sources) = self.unallocated(tx_index, txid, tx)?;
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
 
@@ -30,14 +38,25 @@ impl RuneUpdater<'_, '_, '_> {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
-          if let Some(sender) = self.event_sender {
-            sender.blocking_send(Event::RuneMinted {
+          self.record_activity(
+            tx_index,
+            txid,
+            id,
+            api::RuneActivityKind::Mint,
+            amount.n(),
+            &[],
+            None,
+          )?;
+
+          self.event_emitter.emit(
+            tx_index,
+            Event::RuneMinted {
               block_height: self.height,
               txid,
               rune_id: id,
               amount: amount.n(),
-            })?;
-          }
+            },
+          )?;
         }
       }
 
@@ -45,8 +64,21 @@ impl RuneUpdater<'_, '_, '_> {
 
       if let Artifact::Runestone(runestone) = artifact {
         if let Some((id, ..)) = etched {
-          *unallocated.entry(id).or_default() +=
-            runestone.etching.unwrap().premine.unwrap_or_default();
+          let premine = runestone.etching.unwrap().premine.unwrap_or_default();
+
+          *unallocated.entry(id).or_default() += premine;
+
+          if premine > 0 {
+            self.record_activity(
+              tx_index,
+              txid,
+              id,
+              api::RuneActivityKind::Mint,
+              premine,
+              &[],
+              None,
+            )?;
+          }
         }
 
         for Edict { id, amount, output } in runestone.edicts.iter().copied() {
@@ -195,37 +227,73 @@ impl RuneUpdater<'_, '_, '_> {
         vout: vout.try_into().unwrap(),
       };
 
+      let script_pubkey = tx.output[vout].script_pubkey.as_bytes();
+
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
+        self.update_holder_balance(script_pubkey, id, |holder_balance| {
+          holder_balance.checked_add(balance.n()).unwrap()
+        })?;
+
+        self.update_address_history(tx_index, txid, script_pubkey, |transaction| {
+          *transaction.runes_received.entry(id).or_default() += balance.n();
+        })?;
+
+        self.record_activity(
+          tx_index,
+          txid,
+          id,
+          api::RuneActivityKind::Transfer,
+          balance.n(),
+          sources.get(&id).map(Vec::as_slice).unwrap_or_default(),
+          Some(outpoint),
+        )?;
+
+        self.event_emitter.emit(
+          tx_index,
+          Event::RuneTransferred {
             outpoint,
             block_height: self.height,
             txid,
             rune_id: id,
             amount: balance.0,
-          })?;
-        }
+          },
+        )?;
       }
 
       self
         .outpoint_to_balances
         .insert(&outpoint.store(), buffer.as_slice())?;
+
+      self
+        .outpoint_to_holder
+        .insert(&outpoint.store(), script_pubkey)?;
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
+      self.record_activity(
+        tx_index,
+        txid,
+        id,
+        api::RuneActivityKind::Burn,
+        amount.n(),
+        sources.get(&id).map(Vec::as_slice).unwrap_or_default(),
+        None,
+      )?;
+
+      self.event_emitter.emit(
+        tx_index,
+        Event::RuneBurned {
           block_height: self.height,
           txid,
           rune_id: id,
           amount: amount.n(),
-        })?;
-      }
+        },
+      )?;
     }
 
     Ok(())
@@ -238,6 +306,121 @@ impl RuneUpdater<'_, '_, '_> {
       self.id_to_entry.insert(&rune_id.store(), entry.store())?;
     }
 
+    for (rune_id, holders) in self.holders {
+      if holders == 0 {
+        continue;
+      }
+
+      let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
+      entry.holders = entry.holders.checked_add_signed(holders).unwrap();
+      self.id_to_entry.insert(&rune_id.store(), entry.store())?;
+    }
+
+    Ok(())
+  }
+
+  fn record_activity(
+    &mut self,
+    tx_index: u32,
+    txid: Txid,
+    id: RuneId,
+    kind: api::RuneActivityKind,
+    amount: u128,
+    from: &[OutPoint],
+    to: Option<OutPoint>,
+  ) -> Result {
+    let Some(activity_key_to_activity) = self.activity_key_to_activity.as_mut() else {
+      return Ok(());
+    };
+
+    let activity = api::RuneActivity {
+      amount,
+      from: from.to_vec(),
+      height: self.height,
+      kind,
+      to,
+      txid,
+    };
+
+    activity_key_to_activity.insert(
+      (
+        id.store(),
+        self.height,
+        tx_index,
+        self.activity_sequence_number,
+      ),
+      serde_json::to_vec(&activity)?.as_slice(),
+    )?;
+
+    self.activity_sequence_number += 1;
+
+    Ok(())
+  }
+
+  fn update_address_history(
+    &mut self,
+    tx_index: u32,
+    txid: Txid,
+    script_pubkey: &[u8],
+    update: impl FnOnce(&mut api::AddressTransaction),
+  ) -> Result {
+    let Some(address_history) = self.address_history.as_mut() else {
+      return Ok(());
+    };
+
+    let key = (script_pubkey, self.height, tx_index);
+
+    let mut transaction = match address_history.get(key)? {
+      Some(value) => serde_json::from_slice(value.value())?,
+      None => api::AddressTransaction::new(self.height, txid),
+    };
+
+    update(&mut transaction);
+
+    address_history.insert(key, serde_json::to_vec(&transaction)?.as_slice())?;
+
+    Ok(())
+  }
+
+  fn update_holder_balance(
+    &mut self,
+    script_pubkey: &[u8],
+    id: RuneId,
+    update: impl FnOnce(u128) -> u128,
+  ) -> Result {
+    let old = self
+      .holder_to_balance
+      .get(&(script_pubkey, id.store()))?
+      .map(|balance| balance.value())
+      .unwrap_or_default();
+
+    let new = update(old);
+
+    if old > 0 {
+      self
+        .balance_to_holder
+        .remove(&(id.store(), old, script_pubkey))?;
+    }
+
+    if new > 0 {
+      self
+        .holder_to_balance
+        .insert(&(script_pubkey, id.store()), new)?;
+      self
+        .balance_to_holder
+        .insert(&(id.store(), new, script_pubkey), ())?;
+    } else {
+      self
+        .holder_to_balance
+        .remove(&(script_pubkey, id.store()))?;
+    }
+
+    match (old > 0, new > 0) {
+      (false, true) => *self.holders.entry(id).or_default() += 1,
+      (true, false) => *self.holders.entry(id).or_default() -= 1,
+      _ => {}
+    }
+
     Ok(())
   }
 
@@ -266,6 +449,7 @@ impl RuneUpdater<'_, '_, '_> {
         burned: 0,
         divisibility: 0,
         etching: txid,
+        holders: 0,
         terms: None,
         mints: 0,
         number,
@@ -291,6 +475,7 @@ impl RuneUpdater<'_, '_, '_> {
           burned: 0,
           divisibility: divisibility.unwrap_or_default(),
           etching: txid,
+          holders: 0,
           terms,
           mints: 0,
           number,
@@ -308,13 +493,14 @@ impl RuneUpdater<'_, '_, '_> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
+    self.event_emitter.emit(
+      id.tx,
+      Event::RuneEtched {
         block_height: self.height,
         txid,
         rune_id: id,
-      })?;
-    }
+      },
+    )?;
 
     let inscription_id = InscriptionId { txid, index: 0 };
 
@@ -466,26 +652,55 @@ impl RuneUpdater<'_, '_, '_> {
     Ok(false)
   }
 
-  fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
+  fn unallocated(
+    &mut self,
+    tx_index: u32,
+    txid: Txid,
+    tx: &Transaction,
+  ) -> Result<(HashMap<RuneId, Lot>, HashMap<RuneId, Vec<OutPoint>>)> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
 
+    // map of rune ID to the inputs which held that rune
+    let mut sources: HashMap<RuneId, Vec<OutPoint>> = HashMap::new();
+
     // increment unallocated runes with the runes in tx inputs
     for input in &tx.input {
-      if let Some(guard) = self
+      let Some(guard) = self
         .outpoint_to_balances
         .remove(&input.previous_output.store())?
-      {
-        let buffer = guard.value();
-        let mut i = 0;
-        while i < buffer.len() {
-          let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
-          i += len;
-          *unallocated.entry(id).or_default() += balance;
-        }
+      else {
+        continue;
+      };
+
+      let buffer = guard.value().to_vec();
+
+      drop(guard);
+
+      let script_pubkey = self
+        .outpoint_to_holder
+        .remove(&input.previous_output.store())?
+        .unwrap()
+        .value()
+        .to_vec();
+
+      let mut i = 0;
+      while i < buffer.len() {
+        let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
+        i += len;
+        *unallocated.entry(id).or_default() += balance;
+        sources.entry(id).or_default().push(input.previous_output);
+
+        self.update_holder_balance(&script_pubkey, id, |holder_balance| {
+          holder_balance.checked_sub(balance).unwrap()
+        })?;
+
+        self.update_address_history(tx_index, txid, &script_pubkey, |transaction| {
+          *transaction.runes_sent.entry(id).or_default() += balance;
+        })?;
       }
     }
 
-    Ok(unallocated)
+    Ok((unallocated, sources))
   }
 }
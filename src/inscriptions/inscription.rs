@@ -1,7 +1,10 @@
 use {
   super::*,
   anyhow::ensure,
-  bitcoin::blockdata::opcodes,
+  bitcoin::{
+    blockdata::opcodes,
+    hashes::{sha256, HashEngine},
+  },
   brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
   http::header::HeaderValue,
   io::Write,
@@ -25,6 +28,11 @@ pub struct Inscription {
 }
 
 impl Inscription {
+  /// Bodies which decode to more than this many bytes are hashed as stored,
+  /// so a small, highly compressed body can't make the indexer hash an
+  /// arbitrary amount of data.
+  pub const MAX_HASHED_DECODED_LENGTH: u64 = 4_000_000;
+
   pub fn new(
     chain: Chain,
     compress: bool,
@@ -223,6 +231,45 @@ impl Inscription {
     Some(self.body()?.len())
   }
 
+  /// Reader over the body with `br` content encoding undone, the same
+  /// decoding `/content` applies when the server decompresses.
+  pub fn decoded_body(&self) -> Option<Box<dyn Read + '_>> {
+    let body = self.body()?;
+
+    if self.content_encoding.as_deref() == Some(b"br") {
+      Some(Box::new(brotli::Decompressor::new(body, 4096)))
+    } else {
+      Some(Box::new(body))
+    }
+  }
+
+  /// SHA-256 hash of the decoded body, so compressed and uncompressed copies
+  /// of the same content hash identically. Bodies which fail to decode, or
+  /// which decode to more than `MAX_HASHED_DECODED_LENGTH` bytes, are hashed
+  /// as stored.
+  pub fn content_hash(&self) -> Option<sha256::Hash> {
+    let mut decoded = self
+      .decoded_body()?
+      .take(Self::MAX_HASHED_DECODED_LENGTH + 1);
+    let mut engine = sha256::Hash::engine();
+    let mut buffer = [0; 4096];
+    let mut length = 0;
+
+    loop {
+      match decoded.read(&mut buffer) {
+        Ok(0) if length <= Self::MAX_HASHED_DECODED_LENGTH => {
+          break Some(sha256::Hash::from_engine(engine))
+        }
+        Ok(n) if n > 0 => {
+          length += u64::try_from(n).unwrap();
+          engine.input(&buffer[..n]);
+        }
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+        Ok(_) | Err(_) => break Some(sha256::Hash::hash(self.body()?)),
+      }
+    }
+  }
+
   pub fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
@@ -904,4 +951,50 @@ mod tests {
     }
     .hidden());
   }
+
+  #[test]
+  fn content_hash_decodes_brotli() {
+    fn compressed(decoded: &[u8]) -> Inscription {
+      let mut body = Vec::new();
+
+      brotli::enc::writer::CompressorWriter::new(&mut body, 4096, 1, 22)
+        .write_all(decoded)
+        .unwrap();
+
+      Inscription {
+        body: Some(body),
+        content_encoding: Some("br".into()),
+        ..default()
+      }
+    }
+
+    assert_eq!(
+      compressed(b"foo").content_hash(),
+      Some(sha256::Hash::hash(b"foo")),
+    );
+
+    let limit = usize::try_from(Inscription::MAX_HASHED_DECODED_LENGTH).unwrap();
+
+    assert_eq!(
+      compressed(&vec![0; limit]).content_hash(),
+      Some(sha256::Hash::hash(&vec![0; limit])),
+    );
+
+    let bomb = compressed(&vec![0; limit + 1]);
+
+    assert_eq!(
+      bomb.content_hash(),
+      Some(sha256::Hash::hash(bomb.body().unwrap())),
+    );
+
+    assert_eq!(
+      Inscription {
+        body: Some(b"foo".into()),
+        content_encoding: Some("br".into()),
+        ..default()
+      }
+      .content_hash(),
+      Some(sha256::Hash::hash(b"foo")),
+    );
+  }
 }
@@ -108,6 +108,29 @@ impl Media {
     ("video/webm",                  GENERIC, Video,            &["webm"]),
   ];
 
+  pub(crate) const CATEGORIES: &'static [&'static str] = &[
+    "audio", "code", "font", "iframe", "image", "markdown", "model", "pdf", "text", "unknown",
+    "video",
+  ];
+
+  /// Returns the name of this media's category, ignoring any language or
+  /// rendering details.
+  pub(crate) fn category(self) -> &'static str {
+    match self {
+      Audio => "audio",
+      Code(_) => "code",
+      Font => "font",
+      Iframe => "iframe",
+      Image(_) => "image",
+      Markdown => "markdown",
+      Model => "model",
+      Pdf => "pdf",
+      Text => "text",
+      Unknown => "unknown",
+      Video => "video",
+    }
+  }
+
   pub(crate) fn content_type_for_path(
     path: &Path,
   ) -> Result<(&'static str, BrotliEncoderMode), Error> {
@@ -219,6 +242,15 @@ mod tests {
     assert!(Media::check_mp4_codec(Path::new("examples/av1.mp4")).is_err(),);
   }
 
+  #[test]
+  fn categories() {
+    for (_, _, media, _) in Media::TABLE {
+      assert!(Media::CATEGORIES.contains(&media.category()));
+    }
+
+    assert!(Media::CATEGORIES.contains(&Unknown.category()));
+  }
+
   #[test]
   fn no_duplicate_extensions() {
     let mut set = HashSet::new();
@@ -42,13 +42,38 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Track transaction history of addresses. Requires `--index-addresses`."
+  )]
+  pub(crate) index_address_history: bool,
   #[arg(long, help = "Track unspent output addresses.")]
   pub(crate) index_addresses: bool,
+  #[arg(
+    long,
+    help = "Read blocks from Bitcoin Core `blk*.dat` files in `--bitcoin-data-dir` during initial sync, falling back to RPC near the chain tip."
+  )]
+  pub(crate) index_blk_files: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Index inscriptions by SHA-256 hash of their content.")]
+  pub(crate) index_content_hashes: bool,
+  #[arg(long, help = "Store index events in a journal that can be replayed.")]
+  pub(crate) index_events: bool,
+  #[arg(long, help = "Index inscriptions by content type, media, and size.")]
+  pub(crate) index_inscription_attributes: bool,
+  #[arg(long, help = "Track transfer history of inscriptions.")]
+  pub(crate) index_inscription_history: bool,
+  #[arg(long, help = "Index inscriptions by metaprotocol.")]
+  pub(crate) index_metaprotocols: bool,
+  #[arg(
+    long,
+    help = "Track rune mints, transfers, and burns. Requires `--index-runes`."
+  )]
+  pub(crate) index_rune_activity: bool,
   #[arg(long, help = "Track location of runes.")]
   pub(crate) index_runes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
@@ -66,6 +91,16 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    help = "Keep <SAVEPOINT_COUNT> savepoints for reorg recovery and `ord index rollback`. [default: 2]"
+  )]
+  pub(crate) savepoint_count: Option<u32>,
+  #[arg(
+    long,
+    help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
   #[arg(
     long,
     help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
@@ -7,6 +7,7 @@ pub enum Outgoing {
   Rune { decimal: Decimal, rune: SpacedRune },
   Sat(Sat),
   SatPoint(SatPoint),
+  SatRange { start: Sat, end: Sat },
 }
 
 impl Display for Outgoing {
@@ -17,6 +18,7 @@ impl Display for Outgoing {
       Self::Rune { decimal, rune } => write!(f, "{decimal}:{rune}"),
       Self::Sat(sat) => write!(f, "{}", sat.name()),
       Self::SatPoint(satpoint) => satpoint.fmt(f),
+      Self::SatRange { start, end } => write!(f, "{start}-{end}"),
     }
   }
 }
@@ -73,6 +75,19 @@ impl FromStr for Outgoing {
           .parse()
           .snafu_context(error::SatPointParse { input })?,
       ))
+    } else if let Some(captures) = re::SAT_RANGE.captures(input) {
+      let start = captures[1]
+        .parse::<Sat>()
+        .snafu_context(error::SatParse { input })?;
+      let end = captures[2]
+        .parse::<Sat>()
+        .snafu_context(error::SatParse { input })?;
+      if start >= end {
+        return Err(SnafuError::SatRangeEmpty {
+          input: input.to_string(),
+        });
+      }
+      Ok(Outgoing::SatRange { start, end })
     } else if re::INSCRIPTION_ID.is_match(input) {
       Ok(Outgoing::InscriptionId(
         input
@@ -131,6 +146,28 @@ mod tests {
       ),
     );
 
+    case(
+      "0-100",
+      Outgoing::SatRange {
+        start: Sat(0),
+        end: Sat(100),
+      },
+    );
+    case(
+      "nvtdijuwxlp-nvtdijuwxlo",
+      Outgoing::SatRange {
+        start: "nvtdijuwxlp".parse().unwrap(),
+        end: "nvtdijuwxlo".parse().unwrap(),
+      },
+    );
+    case(
+      "1.0-1.100",
+      Outgoing::SatRange {
+        start: Sat(50 * COIN_VALUE),
+        end: Sat(50 * COIN_VALUE + 100),
+      },
+    );
+
     case("0 btc", Outgoing::Amount("0 btc".parse().unwrap()));
     case("0btc", Outgoing::Amount("0 btc".parse().unwrap()));
     case("0.0btc", Outgoing::Amount("0 btc".parse().unwrap()));
@@ -185,6 +222,19 @@ mod tests {
     );
   }
 
+  #[test]
+  fn sat_range_must_not_be_empty() {
+    assert_eq!(
+      "100-100".parse::<Outgoing>().unwrap_err().to_string(),
+      "Invalid sat range `100-100`: start must be less than end",
+    );
+
+    assert_eq!(
+      "200-100".parse::<Outgoing>().unwrap_err().to_string(),
+      "Invalid sat range `200-100`: start must be less than end",
+    );
+  }
+
   #[test]
   fn roundtrip() {
     #[track_caller]
@@ -214,6 +264,14 @@ mod tests {
       ),
     );
 
+    case(
+      "5000000000-5000000100",
+      Outgoing::SatRange {
+        start: Sat(50 * COIN_VALUE),
+        end: Sat(50 * COIN_VALUE + 100),
+      },
+    );
+
     case("0 btc", Outgoing::Amount("0 btc".parse().unwrap()));
     case(
       "1.20000000 btc",
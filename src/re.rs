@@ -16,6 +16,7 @@ lazy_static! {
   pub(crate) static ref RUNE_NUMBER: Regex = re(r"-?[0-9]+");
   pub(crate) static ref SATPOINT: Regex = re(r"[[:xdigit:]]{64}:\d+:\d+");
   pub(crate) static ref SAT_NAME: Regex = re(r"[a-z]{1,11}");
+  pub(crate) static ref SAT_RANGE: Regex = re(r"([^-\s]+)-([^-\s]+)");
   pub(crate) static ref SPACED_RUNE: Regex = re(r"[A-Z•.]+");
 }
 
@@ -860,6 +860,7 @@ mod tests {
           divisibility: 0,
           etching: txid0,
           terms: None,
+          holders: 0,
           mints: 0,
           number: 0,
           premine: 0,
@@ -6084,6 +6085,7 @@ mod tests {
             burned: 0,
             divisibility: 0,
             etching: Txid::all_zeros(),
+            holders: 0,
             mints: 0,
             number: 0,
             premine: 0,
@@ -6108,4 +6110,361 @@ mod tests {
         [],
       );
   }
+
+  #[test]
+  fn holders_are_tracked_by_script_pubkey() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          divisibility: Some(1),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let entry = RuneEntry {
+      block: id.block,
+      divisibility: 1,
+      etching: txid0,
+      spaced_rune: SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      premine: 1000,
+      timestamp: id.block,
+      ..default()
+    };
+
+    context.assert_runes(
+      [(id, entry)],
+      [(
+        OutPoint {
+          txid: txid0,
+          vout: 0,
+        },
+        vec![(id, 1000)],
+      )],
+    );
+
+    assert_eq!(context.index.runes().unwrap()[0].1.holders, 1);
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      outputs: 2,
+      p2tr: true,
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 400,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(id, entry)],
+      [
+        (
+          OutPoint {
+            txid: txid1,
+            vout: 0,
+          },
+          vec![(id, 600)],
+        ),
+        (
+          OutPoint {
+            txid: txid1,
+            vout: 1,
+          },
+          vec![(id, 400)],
+        ),
+      ],
+    );
+
+    let tx1 = context.core.tx_by_id(txid1);
+
+    assert_eq!(context.index.runes().unwrap()[0].1.holders, 2);
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 1, 0).unwrap(),
+      (vec![(tx1.output[0].script_pubkey.clone(), 600)], true),
+    );
+
+    assert_eq!(
+      context.index.get_rune_holders_paginated(id, 1, 1).unwrap(),
+      (vec![(tx1.output[1].script_pubkey.clone(), 400)], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_balances_for_script_pubkey(&tx1.output[1].script_pubkey)
+        .unwrap(),
+      [(
+        SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        Decimal {
+          value: 400,
+          scale: 1,
+        },
+        None,
+      )],
+    );
+
+    let block = usize::try_from(id.block).unwrap() + 1;
+
+    let txid2 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(block, 1, 0, Witness::new()), (block, 1, 1, Witness::new())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.assert_runes(
+      [(id, entry)],
+      [(
+        OutPoint {
+          txid: txid2,
+          vout: 0,
+        },
+        vec![(id, 1000)],
+      )],
+    );
+
+    assert_eq!(context.index.runes().unwrap()[0].1.holders, 1);
+
+    assert!(context
+      .index
+      .get_rune_balances_for_script_pubkey(&tx1.output[1].script_pubkey)
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn rune_activity_is_recorded() {
+    let context = Context::builder()
+      .arg("--index-runes")
+      .arg("--index-rune-activity")
+      .build();
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 100,
+          output: 1,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(500),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(10),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let height = u32::try_from(id.block).unwrap();
+
+    let etched = [
+      api::RuneActivity {
+        amount: 100,
+        from: Vec::new(),
+        height,
+        kind: api::RuneActivityKind::Burn,
+        to: None,
+        txid: txid0,
+      },
+      api::RuneActivity {
+        amount: 400,
+        from: Vec::new(),
+        height,
+        kind: api::RuneActivityKind::Transfer,
+        to: Some(OutPoint {
+          txid: txid0,
+          vout: 0,
+        }),
+        txid: txid0,
+      },
+      api::RuneActivity {
+        amount: 500,
+        from: Vec::new(),
+        height,
+        kind: api::RuneActivityKind::Mint,
+        to: None,
+        txid: txid0,
+      },
+    ];
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_activity_paginated(id, 100, 0)
+        .unwrap(),
+      (etched.to_vec(), false),
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid2 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      outputs: 2,
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 150,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transferred = [
+      api::RuneActivity {
+        amount: 150,
+        from: vec![OutPoint {
+          txid: txid0,
+          vout: 0,
+        }],
+        height: height + 2,
+        kind: api::RuneActivityKind::Transfer,
+        to: Some(OutPoint {
+          txid: txid2,
+          vout: 1,
+        }),
+        txid: txid2,
+      },
+      api::RuneActivity {
+        amount: 250,
+        from: vec![OutPoint {
+          txid: txid0,
+          vout: 0,
+        }],
+        height: height + 2,
+        kind: api::RuneActivityKind::Transfer,
+        to: Some(OutPoint {
+          txid: txid2,
+          vout: 0,
+        }),
+        txid: txid2,
+      },
+    ];
+
+    let minted = [
+      api::RuneActivity {
+        amount: 1000,
+        from: Vec::new(),
+        height: height + 1,
+        kind: api::RuneActivityKind::Transfer,
+        to: Some(OutPoint {
+          txid: txid1,
+          vout: 0,
+        }),
+        txid: txid1,
+      },
+      api::RuneActivity {
+        amount: 1000,
+        from: Vec::new(),
+        height: height + 1,
+        kind: api::RuneActivityKind::Mint,
+        to: None,
+        txid: txid1,
+      },
+    ];
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_activity_paginated(id, 100, 0)
+        .unwrap(),
+      (
+        transferred
+          .iter()
+          .chain(&minted)
+          .chain(&etched)
+          .cloned()
+          .collect(),
+        false
+      ),
+    );
+
+    assert_eq!(
+      context.index.get_rune_activity_paginated(id, 2, 1).unwrap(),
+      (minted.to_vec(), true),
+    );
+
+    assert_eq!(
+      context.index.get_rune_activity_paginated(id, 2, 2).unwrap(),
+      (etched[..2].to_vec(), true),
+    );
+
+    assert_eq!(
+      context.index.get_rune_activity_paginated(id, 2, 3).unwrap(),
+      (etched[2..].to_vec(), false),
+    );
+  }
+
+  #[test]
+  fn rune_activity_is_not_recorded_without_flag() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert!(!context.index.has_rune_activity_index());
+
+    assert!(context
+      .index
+      .get_rune_activity_paginated(id, 100, 0)
+      .unwrap()
+      .0
+      .is_empty());
+  }
 }
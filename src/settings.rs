@@ -1,4 +1,4 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {super::*, crate::index::Statistic, bitcoincore_rpc::Auth};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default, deny_unknown_fields)]
@@ -18,13 +18,23 @@ pub struct Settings {
   hidden: Option<HashSet<InscriptionId>>,
   http_port: Option<u16>,
   index: Option<PathBuf>,
+  index_address_history: bool,
   index_addresses: bool,
+  index_blk_files: bool,
   index_cache_size: Option<usize>,
+  index_content_hashes: bool,
+  index_events: bool,
+  index_inscription_attributes: bool,
+  index_inscription_history: bool,
+  index_metaprotocols: bool,
+  index_rune_activity: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
   integration_test: bool,
   no_index_inscriptions: bool,
+  savepoint_count: Option<u32>,
+  savepoint_interval: Option<u32>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
@@ -131,13 +141,24 @@ impl Settings {
       ),
       http_port: self.http_port.or(source.http_port),
       index: self.index.or(source.index),
+      index_address_history: self.index_address_history || source.index_address_history,
       index_addresses: self.index_addresses || source.index_addresses,
+      index_blk_files: self.index_blk_files || source.index_blk_files,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_content_hashes: self.index_content_hashes || source.index_content_hashes,
+      index_events: self.index_events || source.index_events,
+      index_inscription_attributes: self.index_inscription_attributes
+        || source.index_inscription_attributes,
+      index_inscription_history: self.index_inscription_history || source.index_inscription_history,
+      index_metaprotocols: self.index_metaprotocols || source.index_metaprotocols,
+      index_rune_activity: self.index_rune_activity || source.index_rune_activity,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
       index_transactions: self.index_transactions || source.index_transactions,
       integration_test: self.integration_test || source.integration_test,
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      savepoint_count: self.savepoint_count.or(source.savepoint_count),
+      savepoint_interval: self.savepoint_interval.or(source.savepoint_interval),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
@@ -166,13 +187,23 @@ impl Settings {
       hidden: None,
       http_port: None,
       index: options.index,
+      index_address_history: options.index_address_history,
       index_addresses: options.index_addresses,
+      index_blk_files: options.index_blk_files,
       index_cache_size: options.index_cache_size,
+      index_content_hashes: options.index_content_hashes,
+      index_events: options.index_events,
+      index_inscription_attributes: options.index_inscription_attributes,
+      index_inscription_history: options.index_inscription_history,
+      index_metaprotocols: options.index_metaprotocols,
+      index_rune_activity: options.index_rune_activity,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
       index_transactions: options.index_transactions,
       integration_test: options.integration_test,
       no_index_inscriptions: options.no_index_inscriptions,
+      savepoint_count: options.savepoint_count,
+      savepoint_interval: options.savepoint_interval,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
@@ -254,27 +285,37 @@ impl Settings {
       hidden: inscriptions("HIDDEN")?,
       http_port: get_u16("HTTP_PORT")?,
       index: get_path("INDEX"),
+      index_address_history: get_bool("INDEX_ADDRESS_HISTORY"),
       index_addresses: get_bool("INDEX_ADDRESSES"),
+      index_blk_files: get_bool("INDEX_BLK_FILES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_content_hashes: get_bool("INDEX_CONTENT_HASHES"),
+      index_events: get_bool("INDEX_EVENTS"),
+      index_inscription_attributes: get_bool("INDEX_INSCRIPTION_ATTRIBUTES"),
+      index_inscription_history: get_bool("INDEX_INSCRIPTION_HISTORY"),
+      index_metaprotocols: get_bool("INDEX_METAPROTOCOLS"),
+      index_rune_activity: get_bool("INDEX_RUNE_ACTIVITY"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
       integration_test: get_bool("INTEGRATION_TEST"),
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      savepoint_count: get_u32("SAVEPOINT_COUNT")?,
+      savepoint_interval: get_u32("SAVEPOINT_INTERVAL")?,
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
     })
   }
 
-  pub fn for_env(dir: &Path, rpc_url: &str, server_url: &str) -> Self {
+  pub fn for_env(chain: Chain, dir: &Path, rpc_url: &str, server_url: &str) -> Self {
     Self {
       bitcoin_data_dir: Some(dir.into()),
       bitcoin_rpc_password: None,
       bitcoin_rpc_url: Some(rpc_url.into()),
       bitcoin_rpc_username: None,
       bitcoin_rpc_limit: None,
-      chain: Some(Chain::Regtest),
+      chain: Some(chain),
       commit_interval: None,
       config: None,
       config_dir: None,
@@ -284,13 +325,23 @@ impl Settings {
       hidden: None,
       http_port: None,
       index: None,
+      index_address_history: false,
       index_addresses: true,
+      index_blk_files: false,
       index_cache_size: None,
+      index_content_hashes: false,
+      index_events: false,
+      index_inscription_attributes: false,
+      index_inscription_history: false,
+      index_metaprotocols: false,
+      index_rune_activity: false,
       index_runes: true,
       index_sats: true,
       index_transactions: false,
       integration_test: false,
       no_index_inscriptions: false,
+      savepoint_count: None,
+      savepoint_interval: None,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
@@ -300,6 +351,24 @@ impl Settings {
   pub fn or_defaults(self) -> Result<Self> {
     let chain = self.chain.unwrap_or_default();
 
+    let savepoint_count = self.savepoint_count.unwrap_or(2);
+    let savepoint_interval = self.savepoint_interval.unwrap_or(10);
+
+    ensure!(savepoint_count > 0, "savepoint count must be at least 1");
+
+    ensure!(
+      savepoint_interval > 0,
+      "savepoint interval must be at least 1",
+    );
+
+    // reorg depths are computed from the number of blocks covered by all
+    // savepoints, so it must fit in a block height
+    ensure!(
+      savepoint_count.checked_mul(savepoint_interval).is_some(),
+      "savepoint count times savepoint interval must be at most {}",
+      u32::MAX,
+    );
+
     let bitcoin_data_dir = match &self.bitcoin_data_dir {
       Some(bitcoin_data_dir) => bitcoin_data_dir.clone(),
       None => {
@@ -351,7 +420,9 @@ impl Settings {
       hidden: self.hidden,
       http_port: self.http_port,
       index: Some(index),
+      index_address_history: self.index_address_history,
       index_addresses: self.index_addresses,
+      index_blk_files: self.index_blk_files,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -360,11 +431,19 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_content_hashes: self.index_content_hashes,
+      index_events: self.index_events,
+      index_inscription_attributes: self.index_inscription_attributes,
+      index_inscription_history: self.index_inscription_history,
+      index_metaprotocols: self.index_metaprotocols,
+      index_rune_activity: self.index_rune_activity,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
       index_transactions: self.index_transactions,
       integration_test: self.integration_test,
       no_index_inscriptions: self.no_index_inscriptions,
+      savepoint_count: Some(savepoint_count),
+      savepoint_interval: Some(savepoint_interval),
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
@@ -379,6 +458,13 @@ impl Settings {
     )
   }
 
+  pub fn bitcoin_blocks_dir(&self) -> PathBuf {
+    self
+      .chain()
+      .join_with_data_dir(self.bitcoin_data_dir.as_ref().unwrap())
+      .join("blocks")
+  }
+
   pub fn bitcoin_credentials(&self) -> Result<Auth> {
     if let Some((user, pass)) = &self
       .bitcoin_rpc_username
@@ -427,11 +513,14 @@ impl Settings {
 
     let mut checks = 0;
     let rpc_chain = loop {
-      match client.get_blockchain_info() {
+      // rust-bitcoin cannot deserialize testnet4, so read the chain name
+      // directly from `getblockchaininfo`
+      match client.call::<serde_json::Value>("getblockchaininfo", &[]) {
         Ok(blockchain_info) => {
-          break match blockchain_info.chain.to_string().as_str() {
-            "bitcoin" => Chain::Mainnet,
-            "testnet" => Chain::Testnet,
+          break match blockchain_info["chain"].as_str().unwrap_or_default() {
+            "bitcoin" | "main" => Chain::Mainnet,
+            "testnet" | "test" => Chain::Testnet,
+            "testnet4" => Chain::Testnet4,
             "regtest" => Chain::Regtest,
             "signet" => Chain::Signet,
             other => bail!("Bitcoin RPC server on unknown chain: {other}"),
@@ -525,18 +614,57 @@ impl Settings {
     self.index.as_ref().unwrap()
   }
 
+  pub fn index_address_history_raw(&self) -> bool {
+    self.index_address_history
+  }
+
   pub fn index_addresses_raw(&self) -> bool {
     self.index_addresses
   }
 
+  pub fn index_content_hashes_raw(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub fn index_events_raw(&self) -> bool {
+    self.index_events
+  }
+
+  pub(crate) fn index_flags(&self) -> BTreeMap<String, bool> {
+    Statistic::INDEX_FLAGS
+      .into_iter()
+      .map(|(_statistic, flag, enabled)| (flag.into(), enabled(self)))
+      .collect()
+  }
+
+  pub fn index_inscription_attributes_raw(&self) -> bool {
+    self.index_inscription_attributes
+  }
+
+  pub fn index_inscription_history_raw(&self) -> bool {
+    self.index_inscription_history
+  }
+
   pub fn index_inscriptions_raw(&self) -> bool {
     !self.no_index_inscriptions
   }
 
+  pub fn index_metaprotocols_raw(&self) -> bool {
+    self.index_metaprotocols
+  }
+
+  pub fn index_rune_activity_raw(&self) -> bool {
+    self.index_rune_activity
+  }
+
   pub fn index_runes_raw(&self) -> bool {
     self.index_runes
   }
 
+  pub fn index_blk_files(&self) -> bool {
+    self.index_blk_files
+  }
+
   pub fn index_cache_size(&self) -> usize {
     self.index_cache_size.unwrap()
   }
@@ -549,6 +677,14 @@ impl Settings {
     self.index_transactions
   }
 
+  pub fn savepoint_count(&self) -> u32 {
+    self.savepoint_count.unwrap()
+  }
+
+  pub fn savepoint_interval(&self) -> u32 {
+    self.savepoint_interval.unwrap()
+  }
+
   pub fn integration_test(&self) -> bool {
     self.integration_test
   }
@@ -606,6 +742,38 @@ mod tests {
     }
   }
 
+  #[test]
+  fn savepoints_must_cover_at_most_u32_max_blocks() {
+    fn case(savepoint_count: u32, savepoint_interval: u32) -> Result<Settings> {
+      Settings {
+        savepoint_count: Some(savepoint_count),
+        savepoint_interval: Some(savepoint_interval),
+        ..default()
+      }
+      .or_defaults()
+    }
+
+    assert_eq!(
+      case(0, 10).unwrap_err().to_string(),
+      "savepoint count must be at least 1",
+    );
+
+    assert_eq!(
+      case(2, 0).unwrap_err().to_string(),
+      "savepoint interval must be at least 1",
+    );
+
+    assert_eq!(
+      case(2, u32::MAX / 2 + 1).unwrap_err().to_string(),
+      "savepoint count times savepoint interval must be at most 4294967295",
+    );
+
+    assert_eq!(
+      case(2, u32::MAX / 2).unwrap().savepoint_interval(),
+      u32::MAX / 2
+    );
+  }
+
   #[test]
   fn auth_missing_rpc_pass_is_an_error() {
     assert_eq!(
@@ -857,6 +1025,14 @@ mod tests {
         "ord/testnet3"
       },
     );
+    check_network_alias(
+      "testnet4",
+      if cfg!(windows) {
+        r"ord\testnet4"
+      } else {
+        "ord/testnet4"
+      },
+    );
   }
 
   #[test]
@@ -1016,12 +1192,22 @@ mod tests {
     ("HTTP_PORT", "8080"),
       ("INDEX", "index"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_CONTENT_HASHES", "1"),
+      ("INDEX_EVENTS", "1"),
+      ("INDEX_INSCRIPTION_ATTRIBUTES", "1"),
+      ("INDEX_INSCRIPTION_HISTORY", "1"),
+      ("INDEX_METAPROTOCOLS", "1"),
+      ("INDEX_RUNE_ACTIVITY", "1"),
+      ("INDEX_ADDRESS_HISTORY", "1"),
       ("INDEX_ADDRESSES", "1"),
+      ("INDEX_BLK_FILES", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
       ("INTEGRATION_TEST", "1"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("SAVEPOINT_COUNT", "5"),
+      ("SAVEPOINT_INTERVAL", "20"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
@@ -1059,13 +1245,23 @@ mod tests {
         ),
         http_port: Some(8080),
         index: Some("index".into()),
+        index_address_history: true,
         index_addresses: true,
+        index_blk_files: true,
         index_cache_size: Some(4),
+        index_content_hashes: true,
+        index_events: true,
+        index_inscription_attributes: true,
+        index_inscription_history: true,
+        index_metaprotocols: true,
+        index_rune_activity: true,
         index_runes: true,
         index_sats: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
+        savepoint_count: Some(5),
+        savepoint_interval: Some(20),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
@@ -1091,14 +1287,24 @@ mod tests {
           "--cookie-file=cookie file",
           "--datadir=/data/dir",
           "--height-limit=3",
+          "--index-address-history",
           "--index-addresses",
+          "--index-blk-files",
           "--index-cache-size=4",
+          "--index-content-hashes",
+          "--index-events",
+          "--index-inscription-attributes",
+          "--index-inscription-history",
+          "--index-metaprotocols",
+          "--index-rune-activity",
           "--index-runes",
           "--index-sats",
           "--index-transactions",
           "--index=index",
           "--integration-test",
           "--no-index-inscriptions",
+          "--savepoint-count=5",
+          "--savepoint-interval=20",
           "--server-password=server password",
           "--server-username=server username",
         ])
@@ -1120,13 +1326,23 @@ mod tests {
         hidden: None,
         http_port: None,
         index: Some("index".into()),
+        index_address_history: true,
         index_addresses: true,
+        index_blk_files: true,
         index_cache_size: Some(4),
+        index_content_hashes: true,
+        index_events: true,
+        index_inscription_attributes: true,
+        index_inscription_history: true,
+        index_metaprotocols: true,
+        index_rune_activity: true,
         index_runes: true,
         index_sats: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
+        savepoint_count: Some(5),
+        savepoint_interval: Some(20),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
@@ -16,6 +16,12 @@ impl Drop for KillOnDrop {
 pub(crate) struct Env {
   #[arg(default_value = "env", help = "Create env in <DIRECTORY>.")]
   directory: PathBuf,
+  #[arg(
+    long,
+    default_value_t = Chain::Regtest,
+    help = "Run env on <CHAIN>. Only `regtest` and `testnet4` are supported."
+  )]
+  pub(crate) chain: Chain,
   #[arg(
     long,
     help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
@@ -38,6 +44,13 @@ struct Info {
 
 impl Env {
   pub(crate) fn run(self) -> SubcommandResult {
+    let chain = self.chain;
+
+    ensure!(
+      matches!(chain, Chain::Regtest | Chain::Testnet4),
+      "env does not support {chain}, use regtest or testnet4",
+    );
+
     let bitcoind_port = TcpListener::bind("127.0.0.1:9000")
       .ok()
       .map(|listener| listener.local_addr().unwrap().port());
@@ -66,11 +79,11 @@ impl Env {
         bitcoin_conf,
         format!(
           "datacarriersize=1000000
-regtest=1
+{chain}=1
 datadir={absolute_str}
 listen=0
 txindex=1
-[regtest]
+[{chain}]
 rpcport={bitcoind_port}
 ",
         ),
@@ -116,7 +129,11 @@ rpcport={bitcoind_port}
     );
 
     loop {
-      if absolute.join("regtest/.cookie").try_exists()? {
+      if chain
+        .join_with_data_dir(&absolute)
+        .join(".cookie")
+        .try_exists()?
+      {
         break;
       }
     }
@@ -130,7 +147,7 @@ rpcport={bitcoind_port}
     if !config.try_exists()? {
       fs::write(
         config,
-        serde_yaml::to_string(&Settings::for_env(&absolute, &rpc_url, &server_url))?,
+        serde_yaml::to_string(&Settings::for_env(chain, &absolute, &rpc_url, &server_url))?,
       )?;
     }
 
@@ -160,7 +177,11 @@ rpcport={bitcoind_port}
 
     thread::sleep(Duration::from_millis(250));
 
-    if !absolute.join("regtest/wallets/ord").try_exists()? {
+    if !chain
+      .join_with_data_dir(&absolute)
+      .join("wallets/ord")
+      .try_exists()?
+    {
       let status = Command::new(&ord)
         .arg("--datadir")
         .arg(&absolute)
@@ -184,22 +205,33 @@ rpcport={bitcoind_port}
 
       let receive = serde_json::from_slice::<wallet::receive::Output>(&output.stdout)?;
 
-      let status = Command::new("bitcoin-cli")
-        .arg(format!("-datadir={relative}"))
-        .arg("generatetoaddress")
-        .arg("200")
-        .arg(
-          receive
-            .addresses
-            .first()
-            .cloned()
-            .unwrap()
-            .require_network(Network::Regtest)?
-            .to_string(),
-        )
-        .status()?;
-
-      ensure!(status.success(), "failed to create wallet: {status}");
+      // testnet4 blocks cannot be mined on demand, so fund the wallet from a
+      // faucet instead
+      if chain == Chain::Testnet4 {
+        eprintln!(
+          "Send testnet4 coins to {} to fund the wallet",
+          receive.addresses[0]
+            .clone()
+            .require_network(chain.network())?
+        );
+      } else {
+        let status = Command::new("bitcoin-cli")
+          .arg(format!("-datadir={relative}"))
+          .arg("generatetoaddress")
+          .arg("200")
+          .arg(
+            receive
+              .addresses
+              .first()
+              .cloned()
+              .unwrap()
+              .require_network(chain.network())?
+              .to_string(),
+          )
+          .status()?;
+
+        ensure!(status.success(), "failed to create wallet: {status}");
+      }
     }
 
     serde_json::to_writer_pretty(
@@ -1,11 +1,20 @@
-use super::*;
+use {super::*, bitcoin::hashes::sha256};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Find {
-  #[arg(help = "Find output and offset of <SAT>.")]
-  sat: Sat,
+  #[arg(
+    help = "Find output and offset of <SAT>.",
+    required_unless_present = "content"
+  )]
+  sat: Option<Sat>,
   #[clap(help = "Find output and offset of all sats in the range [<SAT>, <END>).")]
   end: Option<Sat>,
+  #[arg(
+    long,
+    conflicts_with = "sat",
+    help = "Find inscriptions with the same content as <CONTENT>."
+  )]
+  content: Option<PathBuf>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -13,6 +22,12 @@ pub struct Output {
   pub satpoint: SatPoint,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentOutput {
+  pub content_hash: sha256::Hash,
+  pub inscriptions: Vec<InscriptionId>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct FindRangeOutput {
   pub start: u64,
@@ -24,6 +39,12 @@ impl Find {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     let index = Index::open(&settings)?;
 
+    if let Some(path) = &self.content {
+      return Self::find_content(&index, path);
+    }
+
+    let sat = self.sat.unwrap();
+
     if !index.has_sat_index() {
       bail!("find requires index created with `--index-sats` flag");
     }
@@ -31,17 +52,38 @@ impl Find {
     index.update()?;
 
     match self.end {
-      Some(end) => match index.find_range(self.sat, end)? {
+      Some(end) => match index.find_range(sat, end)? {
         Some(mut results) => {
           results.sort_by_key(|find_range_output| find_range_output.start);
           Ok(Some(Box::new(results)))
         }
         None => Err(anyhow!("range has not been mined as of index height")),
       },
-      None => match index.find(self.sat)? {
+      None => match index.find(sat)? {
         Some(satpoint) => Ok(Some(Box::new(Output { satpoint }))),
         None => Err(anyhow!("sat has not been mined as of index height")),
       },
     }
   }
+
+  fn find_content(index: &Index, path: &Path) -> SubcommandResult {
+    if !index.has_content_hash_index() {
+      bail!("find --content requires index created with `--index-content-hashes` flag");
+    }
+
+    let content =
+      fs::read(path).with_context(|| format!("I/O error reading `{}`", path.display()))?;
+
+    let content_hash = sha256::Hash::hash(&content);
+
+    index.update()?;
+
+    let (inscriptions, _more) =
+      index.get_inscriptions_by_content_hash(content_hash, usize::MAX, 0)?;
+
+    Ok(Some(Box::new(ContentOutput {
+      content_hash,
+      inscriptions,
+    })))
+  }
 }
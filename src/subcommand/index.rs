@@ -1,15 +1,24 @@
 use super::*;
 
+mod events;
 mod export;
 pub mod info;
+pub mod rollback;
+pub mod snapshot;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Replay events from the index event journal")]
+  Events(events::Events),
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Roll back the index to the nearest savepoint")]
+  Rollback(rollback::Rollback),
+  #[command(subcommand, about = "Create and restore index snapshots")]
+  Snapshot(snapshot::Snapshot),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -17,8 +26,11 @@ pub(crate) enum IndexSubcommand {
 impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
+      Self::Events(events) => events.run(settings),
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Rollback(rollback) => rollback.run(settings),
+      Self::Snapshot(snapshot) => snapshot.run(settings),
       Self::Update => update::run(settings),
     }
   }
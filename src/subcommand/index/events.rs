@@ -0,0 +1,68 @@
+use {
+  super::*,
+  serde::ser::{self, SerializeSeq, Serializer},
+};
+
+const BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Events {
+  #[arg(
+    long,
+    default_value_t = 0,
+    help = "Replay events from blocks at or above <FROM_HEIGHT>."
+  )]
+  from_height: u32,
+}
+
+/// Journaled events, read from the index in batches as they are serialized.
+struct Replay {
+  from_height: u32,
+  index: Index,
+}
+
+impl Serialize for Replay {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(None)?;
+
+    let mut cursor = Index::event_cursor_before(self.from_height);
+
+    loop {
+      let events = self
+        .index
+        .get_events_after(cursor, BATCH_SIZE)
+        .map_err(ser::Error::custom)?;
+
+      for event in &events {
+        seq.serialize_element(event)?;
+      }
+
+      match events.last() {
+        Some(last) if events.len() == BATCH_SIZE => {
+          cursor = Some((last.height, last.tx_index, last.sequence_number));
+        }
+        _ => break,
+      }
+    }
+
+    seq.end()
+  }
+}
+
+impl Events {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_event_index(),
+      "`ord index events` requires index created with `--index-events` flag",
+    );
+
+    index.update()?;
+
+    Ok(Some(Box::new(Replay {
+      from_height: self.from_height,
+      index,
+    })))
+  }
+}
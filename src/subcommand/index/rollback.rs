@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Rollback {
+  #[arg(
+    long,
+    help = "Roll back to the most recent savepoint at or below <TO_HEIGHT>."
+  )]
+  to_height: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+}
+
+impl Rollback {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    let height = index.rollback(self.to_height)?;
+
+    Ok(Some(Box::new(Output { height })))
+  }
+}
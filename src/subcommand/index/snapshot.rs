@@ -0,0 +1,236 @@
+use {
+  super::*,
+  crate::index::SCHEMA_VERSION,
+  bitcoin::hashes::{sha256, HashEngine},
+  brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
+  std::{fs::File, io::Write},
+};
+
+const INDEX_FILE: &str = "index.redb.br";
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Parser)]
+pub(crate) enum Snapshot {
+  #[command(about = "Write a compressed copy of the index and its manifest to a directory")]
+  Create(Create),
+  #[command(about = "Restore the index from a snapshot directory")]
+  Restore(Restore),
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    match self {
+      Self::Create(create) => create.run(settings),
+      Self::Restore(restore) => restore.run(settings),
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Manifest {
+  pub block_hash: BlockHash,
+  pub chain: Chain,
+  pub content_hash: sha256::Hash,
+  pub height: u32,
+  pub index_flags: BTreeMap<String, bool>,
+  pub schema_version: u64,
+  pub table_lengths: BTreeMap<String, u64>,
+}
+
+impl Manifest {
+  fn for_index(index: &Index, settings: &Settings, content_hash: sha256::Hash) -> Result<Self> {
+    let Some(height) = index.block_height()? else {
+      bail!("index has no blocks");
+    };
+
+    Ok(Self {
+      block_hash: index.block_hash(Some(height.n()))?.unwrap(),
+      chain: settings.chain(),
+      content_hash,
+      height: height.n(),
+      index_flags: index.index_flags()?,
+      schema_version: SCHEMA_VERSION,
+      table_lengths: index.table_lengths()?,
+    })
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+  #[arg(help = "Write snapshot to <DIR>.")]
+  dir: PathBuf,
+}
+
+impl Create {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    ensure!(
+      !self.dir.exists(),
+      "snapshot directory `{}` already exists",
+      self.dir.display(),
+    );
+
+    // Opening the index takes its lock, so this fails if another ord process
+    // is using it. Dropping it afterwards closes the database cleanly, so the
+    // file on disk reflects the last committed height.
+    let index = Index::open(&settings)?;
+    index.update()?;
+    let manifest = Manifest::for_index(&index, &settings, sha256::Hash::all_zeros())?;
+    drop(index);
+
+    fs::create_dir_all(&self.dir)?;
+
+    let mut input = File::open(settings.index())?;
+    let len = input.metadata()?.len();
+
+    let mut engine = sha256::Hash::engine();
+
+    {
+      let mut output = CompressorWriter::with_params(
+        File::create(self.dir.join(INDEX_FILE))?,
+        1 << 20,
+        &BrotliEncoderParams {
+          quality: 5,
+          size_hint: len.try_into().unwrap_or(usize::MAX),
+          ..default()
+        },
+      );
+
+      let mut buffer = vec![0; 1 << 20];
+
+      loop {
+        let n = input.read(&mut buffer)?;
+
+        if n == 0 {
+          break;
+        }
+
+        engine.input(&buffer[..n]);
+        output.write_all(&buffer[..n])?;
+      }
+
+      output.flush()?;
+    }
+
+    let manifest = Manifest {
+      content_hash: sha256::Hash::from_engine(engine),
+      ..manifest
+    };
+
+    fs::write(
+      self.dir.join(MANIFEST_FILE),
+      serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(Some(Box::new(manifest)))
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Restore {
+  #[arg(help = "Restore snapshot from <DIR>.")]
+  dir: PathBuf,
+}
+
+impl Restore {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let manifest = serde_json::from_slice::<Manifest>(&fs::read(self.dir.join(MANIFEST_FILE))?)?;
+
+    ensure!(
+      manifest.chain == settings.chain(),
+      "snapshot is for {} but ord is on {}",
+      manifest.chain,
+      settings.chain(),
+    );
+
+    ensure!(
+      manifest.schema_version == SCHEMA_VERSION,
+      "snapshot has index schema {} but ord has schema {SCHEMA_VERSION}",
+      manifest.schema_version,
+    );
+
+    for (flag, enabled) in settings.index_flags() {
+      let snapshot = manifest.index_flags.get(&flag).copied().unwrap_or_default();
+      ensure!(
+        snapshot == enabled,
+        "snapshot was created {} `--{}`, which does not match settings",
+        if snapshot { "with" } else { "without" },
+        flag.replace('_', "-"),
+      );
+    }
+
+    let path = settings.index();
+
+    ensure!(
+      !path.exists(),
+      "index file `{}` already exists",
+      path.display(),
+    );
+
+    let client = settings.bitcoin_rpc_client(None)?;
+
+    let block_hash = client
+      .get_block_hash(manifest.height.into())
+      .into_option()?;
+
+    ensure!(
+      block_hash == Some(manifest.block_hash),
+      "snapshot block {} at height {} is not in the active chain",
+      manifest.block_hash,
+      manifest.height,
+    );
+
+    let partial = path.with_extension("redb.partial");
+
+    let result = Self::decompress(&self.dir.join(INDEX_FILE), &partial).and_then(|content_hash| {
+      ensure!(
+        content_hash == manifest.content_hash,
+        "snapshot content hash {content_hash} does not match manifest content hash {}",
+        manifest.content_hash,
+      );
+
+      fs::rename(&partial, path)?;
+
+      let index = Index::open(&settings)?;
+
+      let restored = Manifest::for_index(&index, &settings, content_hash)?;
+
+      ensure!(
+        restored == manifest,
+        "restored index does not match snapshot manifest",
+      );
+
+      Ok(())
+    });
+
+    if let Err(err) = result {
+      fs::remove_file(&partial).ok();
+      fs::remove_file(path).ok();
+      return Err(err);
+    }
+
+    Ok(Some(Box::new(manifest)))
+  }
+
+  fn decompress(input: &Path, output: &Path) -> Result<sha256::Hash> {
+    let mut input = brotli::Decompressor::new(File::open(input)?, 1 << 20);
+    let mut output = File::create(output)?;
+
+    let mut engine = sha256::Hash::engine();
+    let mut buffer = vec![0; 1 << 20];
+
+    loop {
+      let n = input.read(&mut buffer)?;
+
+      if n == 0 {
+        break;
+      }
+
+      engine.input(&buffer[..n]);
+      output.write_all(&buffer[..n])?;
+    }
+
+    output.sync_all()?;
+
+    Ok(sha256::Hash::from_engine(engine))
+  }
+}
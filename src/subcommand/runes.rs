@@ -11,6 +11,7 @@ pub struct RuneInfo {
   pub burned: u128,
   pub divisibility: u8,
   pub etching: Txid,
+  pub holders: u64,
   pub id: RuneId,
   pub mints: u128,
   pub number: u64,
@@ -46,6 +47,7 @@ pub(crate) fn run(settings: Settings) -> SubcommandResult {
             burned,
             divisibility,
             etching,
+            holders,
             mints,
             number,
             premine,
@@ -63,6 +65,7 @@ pub(crate) fn run(settings: Settings) -> SubcommandResult {
               burned,
               divisibility,
               etching,
+              holders,
               id,
               mints,
               number,
@@ -3,25 +3,33 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    events::{EventFilter, EventsQuery},
+    mempool::Mempool,
+    webhooks::{Dispatcher, WebhooksConfig},
   },
   super::*,
+  crate::index::event::Event,
   crate::templates::{
-    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
-    InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
-    PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml, RunesHtml, SatHtml, TransactionHtml,
+    AddressHistoryHtml, AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg,
+    CollectionsHtml, HomeHtml, InputHtml, InscriptionHistoryHtml, InscriptionHtml,
+    InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml, ParentsHtml,
+    PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
+    PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
+    RareTxt, RuneActivityHtml, RuneHtml, RuneNotFoundHtml, RunesHtml, SatHtml, TransactionHtml,
   },
   axum::{
     body,
     extract::{DefaultBodyLimit, Extension, Json, Path, Query},
     http::{header, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      sse::{KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::{get, post},
     Router,
   },
   axum_server::Handle,
-  brotli::Decompressor,
+  bitcoin::hashes::sha256,
   rust_embed::RustEmbed,
   rustls_acme::{
     acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -30,9 +38,13 @@ use {
     AcmeConfig,
   },
   std::{str, sync::Arc},
+  tokio::sync::{broadcast, mpsc},
   tokio_stream::StreamExt,
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, NotForContentType, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
@@ -44,8 +56,11 @@ pub use server_config::ServerConfig;
 mod accept_encoding;
 mod accept_json;
 mod error;
+mod events;
+mod mempool;
 pub mod query;
 mod server_config;
+mod webhooks;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -59,6 +74,74 @@ pub(crate) struct OutputsQuery {
   pub(crate) ty: Option<OutputType>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct InscriptionsQuery {
+  pub(crate) content_type: Option<String>,
+  pub(crate) media: Option<String>,
+  pub(crate) metaprotocol: Option<String>,
+  pub(crate) size_bucket: Option<u8>,
+}
+
+impl InscriptionsQuery {
+  pub(crate) fn is_empty(&self) -> bool {
+    self.content_type.is_none()
+      && self.media.is_none()
+      && self.metaprotocol.is_none()
+      && self.size_bucket.is_none()
+  }
+
+  fn check(&self, index: &Index) -> ServerResult<()> {
+    if (self.content_type.is_some() || self.media.is_some() || self.size_bucket.is_some())
+      && !index.has_inscription_attribute_index()
+    {
+      return Err(ServerError::BadRequest(
+        "this server has no inscription attribute index".into(),
+      ));
+    }
+
+    if self.metaprotocol.is_some() && !index.has_metaprotocol_index() {
+      return Err(ServerError::BadRequest(
+        "this server has no metaprotocol index".into(),
+      ));
+    }
+
+    if let Some(media) = &self.media {
+      if !Media::CATEGORIES.contains(&media.as_str()) {
+        return Err(ServerError::BadRequest(format!(
+          "unknown media `{media}`, expected one of: {}",
+          Media::CATEGORIES.join(", "),
+        )));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Formats as a URL query string, including the leading `?`, so that
+/// pagination links preserve filters.
+impl Display for InscriptionsQuery {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let size_bucket = self.size_bucket.map(|size_bucket| size_bucket.to_string());
+
+    let mut separator = '?';
+
+    for (key, value) in [
+      ("content_type", &self.content_type),
+      ("media", &self.media),
+      ("metaprotocol", &self.metaprotocol),
+      ("size_bucket", &size_bucket),
+    ] {
+      if let Some(value) = value {
+        write!(f, "{separator}{key}={}", urlencoding::encode(value))?;
+        separator = '&';
+      }
+    }
+
+    Ok(())
+  }
+}
+
 #[derive(Clone, Copy, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum OutputType {
@@ -102,6 +185,11 @@ pub struct Server {
   pub(crate) decompress: bool,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(
+    long,
+    help = "Serve a stream of index events as server-sent events at `/events`."
+  )]
+  pub(crate) events: bool,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -123,6 +211,11 @@ pub struct Server {
   pub(crate) https: bool,
   #[arg(long, help = "Redirect HTTP traffic to HTTPS.")]
   pub(crate) redirect_http_to_https: bool,
+  #[arg(
+    long,
+    help = "Track unconfirmed inscriptions and rune transfers in the mempool and serve them at `/mempool`."
+  )]
+  pub(crate) mempool: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
   #[arg(
@@ -136,10 +229,53 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Deliver index events to webhooks configured in <WEBHOOKS>. Requires index created with `--index-events`."
+  )]
+  pub(crate) webhooks: Option<PathBuf>,
 }
 
 impl Server {
-  pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  pub fn event_channel(&self) -> (Option<mpsc::Sender<Event>>, Option<mpsc::Receiver<Event>>) {
+    if self.events {
+      let (sender, receiver) = mpsc::channel(1024);
+      (Some(sender), Some(receiver))
+    } else {
+      (None, None)
+    }
+  }
+
+  pub fn run(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    handle: Handle,
+    event_receiver: Option<mpsc::Receiver<Event>>,
+  ) -> SubcommandResult {
+    let polling_interval = if settings.integration_test() {
+      Duration::from_millis(100)
+    } else {
+      self.polling_interval.into()
+    };
+
+    if let Some(path) = &self.webhooks {
+      let dispatcher = Dispatcher::new(index.clone(), &settings, WebhooksConfig::load(path)?)?;
+
+      thread::spawn(move || dispatcher.run(polling_interval));
+    }
+
+    let mempool = if self.mempool {
+      let mempool = Arc::new(Mempool::new(index.clone(), &settings)?);
+
+      let poller = mempool.clone();
+      thread::spawn(move || poller.run(polling_interval));
+
+      Some(mempool)
+    } else {
+      None
+    };
+
     Runtime::new()?.block_on(async {
       let index_clone = index.clone();
       let integration_test = settings.integration_test();
@@ -164,6 +300,19 @@ impl Server {
 
       INDEXER.lock().unwrap().replace(index_thread);
 
+      let events = event_receiver.map(|mut receiver| {
+        let (sender, _) = broadcast::channel(1024);
+
+        let forwarder = sender.clone();
+        tokio::spawn(async move {
+          while let Some(event) = receiver.recv().await {
+            forwarder.send(event).ok();
+          }
+        });
+
+        sender
+      });
+
       let settings = Arc::new(settings);
       let acme_domains = self.acme_domains()?;
 
@@ -180,6 +329,12 @@ impl Server {
       let router = Router::new()
         .route("/", get(Self::home))
         .route("/address/:address", get(Self::address))
+        .route("/address/:address/history", get(Self::address_history))
+        .route(
+          "/address/:address/history/:page",
+          get(Self::address_history_paginated),
+        )
+        .route("/address/:address/runes", get(Self::address_runes))
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -206,6 +361,14 @@ impl Server {
           "/inscription/:inscription_query/:child",
           get(Self::inscription_child),
         )
+        .route(
+          "/inscription/:inscription_query/history",
+          get(Self::inscription_history),
+        )
+        .route(
+          "/inscription/:inscription_query/history/:page",
+          get(Self::inscription_history_paginated),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions", post(Self::inscriptions_json))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
@@ -218,6 +381,8 @@ impl Server {
           get(Self::inscriptions_in_block_paginated),
         )
         .route("/install.sh", get(Self::install_script))
+        .route("/mempool", get(Self::mempool))
+        .route("/mempool/:txid", get(Self::mempool_transaction))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/outputs", post(Self::outputs))
@@ -240,6 +405,14 @@ impl Server {
           "/r/inscription/:inscription_id",
           get(Self::inscription_recursive),
         )
+        .route(
+          "/r/inscription/:inscription_id/history",
+          get(Self::inscription_history_recursive),
+        )
+        .route(
+          "/r/inscription/:inscription_id/history/:page",
+          get(Self::inscription_history_recursive_paginated),
+        )
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
@@ -253,6 +426,11 @@ impl Server {
           "/r/children/:inscription_id/inscriptions/:page",
           get(Self::child_inscriptions_recursive_paginated),
         )
+        .route("/r/content-hash/:hash", get(Self::content_hash))
+        .route(
+          "/r/content-hash/:hash/:page",
+          get(Self::content_hash_paginated),
+        )
         .route(
           "/r/undelegated-content/:inscription_id",
           get(Self::undelegated_content),
@@ -274,6 +452,16 @@ impl Server {
         )
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
+        .route("/rune/:rune/activity", get(Self::rune_activity))
+        .route(
+          "/rune/:rune/activity/:page",
+          get(Self::rune_activity_paginated),
+        )
+        .route("/rune/:rune/holders", get(Self::rune_holders))
+        .route(
+          "/rune/:rune/holders/:page",
+          get(Self::rune_holders_paginated),
+        )
         .route("/runes", get(Self::runes))
         .route("/runes/:page", get(Self::runes_paginated))
         .route("/sat/:sat", get(Self::sat))
@@ -284,9 +472,14 @@ impl Server {
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
         .route("/decode/:txid", get(Self::decode))
+        .route("/events", get(Self::events))
+        .route("/events/:height", get(Self::events_journal))
+        .route("/events/:height/:page", get(Self::events_journal_paginated))
         .route("/update", get(Self::update))
         .fallback(Self::fallback)
         .layer(Extension(index))
+        .layer(Extension(events))
+        .layer(Extension(mempool))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -302,7 +495,9 @@ impl Server {
             .allow_methods([http::Method::GET])
             .allow_origin(Any),
         )
-        .layer(CompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(
+          DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
         .with_state(server_config.clone());
 
       let router = if server_config.json_api_enabled {
@@ -633,14 +828,17 @@ impl Server {
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     Path(outpoint): Path<OutPoint>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (output_info, txout) = index
+      let (mut output_info, txout) = index
         .get_output_info(outpoint)?
         .ok_or_not_found(|| format!("output {outpoint}"))?;
 
+      output_info.pending_spend = mempool.and_then(|mempool| mempool.spender(outpoint));
+
       Ok(if accept_json {
         Json(output_info).into_response()
       } else {
@@ -815,7 +1013,7 @@ impl Server {
             StatusCode::NOT_FOUND,
             RuneNotFoundHtml {
               rune,
-              unlock_height: rune.unlock_height(server_config.chain.network()),
+              unlock_height: server_config.chain.rune_unlock_height(rune),
             }
             .page(server_config),
           )
@@ -848,6 +1046,148 @@ impl Server {
     })
   }
 
+  async fn rune_activity(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::rune_activity_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((rune_query, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn rune_activity_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), page_index)): Path<(
+      DeserializeFromStr<query::Rune>,
+      u32,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      if !index.has_rune_activity_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune activity index".to_string(),
+        ));
+      }
+
+      let rune = match rune_query {
+        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+        query::Rune::Id(rune_id) => index
+          .get_rune_by_id(rune_id)?
+          .ok_or_not_found(|| format!("rune {rune_id}"))?,
+        query::Rune::Number(number) => index
+          .get_rune_by_number(usize::try_from(number).unwrap())?
+          .ok_or_not_found(|| format!("rune number {number}"))?,
+      };
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let (activity, more) =
+        index.get_rune_activity_paginated(id, 100, page_index.try_into().unwrap())?;
+
+      Ok(if accept_json {
+        Json(api::RuneActivities {
+          activity,
+          more,
+          page_index,
+        })
+        .into_response()
+      } else {
+        RuneActivityHtml {
+          activity,
+          entry,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn rune_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::rune_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((rune_query, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn rune_holders_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), page_index)): Path<(
+      DeserializeFromStr<query::Rune>,
+      u32,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let rune = match rune_query {
+        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+        query::Rune::Id(rune_id) => index
+          .get_rune_by_id(rune_id)?
+          .ok_or_not_found(|| format!("rune {rune_id}"))?,
+        query::Rune::Number(number) => index
+          .get_rune_by_number(usize::try_from(number).unwrap())?
+          .ok_or_not_found(|| format!("rune number {number}"))?,
+      };
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let (holders, more) =
+        index.get_rune_holders_paginated(id, 100, page_index.try_into().unwrap())?;
+
+      Ok(
+        Json(api::RuneHolders {
+          holders: holders
+            .into_iter()
+            .map(|(script_pubkey, balance)| {
+              api::RuneHolder::new(server_config.chain, script_pubkey, entry.pile(balance))
+            })
+            .collect(),
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -988,6 +1328,106 @@ impl Server {
     })
   }
 
+  async fn address_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::address_history_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((address, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn address_history_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((address, page_index)): Path<(Address<NetworkUnchecked>, u32)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_address_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address history index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let (history, more) = index.get_address_history_paginated(
+        &address.script_pubkey(),
+        100,
+        page_index.try_into().unwrap(),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::AddressHistory {
+          history,
+          more,
+          page_index,
+        })
+        .into_response()
+      } else {
+        let mut runes = BTreeMap::new();
+
+        for transaction in &history {
+          for id in transaction
+            .runes_received
+            .keys()
+            .chain(transaction.runes_sent.keys())
+          {
+            if !runes.contains_key(id) {
+              if let Some(entry) = index.get_rune_entry(*id)? {
+                runes.insert(*id, entry);
+              }
+            }
+          }
+        }
+
+        AddressHistoryHtml {
+          address,
+          history,
+          next_page: more.then_some(page_index + 1),
+          prev_page: page_index.checked_sub(1),
+          runes,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn address_runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      Ok(Json(index.get_rune_balances_for_script_pubkey(&address.script_pubkey())?).into_response())
+    })
+  }
+
   async fn block(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1119,10 +1559,116 @@ impl Server {
     })
   }
 
-  async fn metadata(
+  async fn events(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(events): Extension<Option<broadcast::Sender<Event>>>,
+    Query(query): Query<EventsQuery>,
+  ) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Err(ServerError::NotFound("JSON API disabled".into()));
+    }
+
+    let events = events.ok_or_not_found(|| "event stream")?;
+
+    let filter = EventFilter::from_query(query, server_config.chain)?;
+
+    let receiver = events.subscribe();
+
+    let stream = task::block_in_place(|| events::stream(index, receiver, filter))?;
+
+    Ok(
+      Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response(),
+    )
+  }
+
+  async fn mempool(
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
+  ) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Err(ServerError::NotFound("JSON API disabled".into()));
+    }
+
+    let mempool = mempool.ok_or_not_found(|| "mempool")?;
+
+    Ok(
+      Json(api::Mempool {
+        transactions: mempool.transactions(),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn mempool_transaction(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
+    Path(txid): Path<Txid>,
+  ) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Err(ServerError::NotFound("JSON API disabled".into()));
+    }
+
+    let transaction = mempool
+      .ok_or_not_found(|| "mempool")?
+      .transaction(txid)
+      .ok_or_not_found(|| format!("mempool transaction {txid}"))?;
+
+    Ok(Json(transaction).into_response())
+  }
+
+  async fn events_journal(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(from_height): Path<u32>,
+  ) -> ServerResult {
+    Self::events_journal_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((from_height, 0)),
+    )
+    .await
+  }
+
+  async fn events_journal_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((from_height, page_index)): Path<(u32, u32)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !server_config.json_api_enabled {
+        return Err(ServerError::NotFound("JSON API disabled".into()));
+      }
+
+      if !index.has_event_index() {
+        return Err(ServerError::NotFound(
+          "this server has no event index".to_string(),
+        ));
+      }
+
+      let (events, more) = index.get_events_paginated(
+        from_height,
+        100,
+        usize::try_from(page_index).unwrap_or(usize::MAX),
+      )?;
+
+      Ok(
+        Json(api::Events {
+          events,
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn metadata(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
     task::block_in_place(|| {
       let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
@@ -1665,13 +2211,13 @@ impl Server {
       if accept_encoding.is_acceptable(&content_encoding) {
         headers.insert(header::CONTENT_ENCODING, content_encoding);
       } else if server_config.decompress && content_encoding == "br" {
-        let Some(body) = inscription.into_body() else {
+        let Some(mut body) = inscription.decoded_body() else {
           return Ok(None);
         };
 
         let mut decompressed = Vec::new();
 
-        Decompressor::new(body.as_slice(), 4096)
+        body
           .read_to_end(&mut decompressed)
           .map_err(|err| ServerError::Internal(err.into()))?;
 
@@ -1780,24 +2326,131 @@ impl Server {
   async fn inscription(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, None).await
+    Self::inscription_inner(server_config, &index, mempool, accept_json, query, None).await
   }
 
   async fn inscription_child(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
     Path((DeserializeFromStr(query), child)): Path<(DeserializeFromStr<query::Inscription>, usize)>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, Some(child)).await
+    Self::inscription_inner(
+      server_config,
+      &index,
+      mempool,
+      accept_json,
+      query,
+      Some(child),
+    )
+    .await
+  }
+
+  async fn inscription_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::inscription_history_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn inscription_history_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page_index)): Path<(InscriptionId, u32)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (number, history, more) =
+        Self::inscription_history_page(&index, inscription_id, page_index)?;
+
+      Ok(if accept_json {
+        Json(api::InscriptionHistory {
+          history,
+          more,
+          page_index,
+        })
+        .into_response()
+      } else {
+        InscriptionHistoryHtml {
+          history,
+          id: inscription_id,
+          next_page: more.then_some(page_index + 1),
+          number,
+          prev_page: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscription_history_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    Self::inscription_history_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn inscription_history_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page_index)): Path<(InscriptionId, u32)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (_number, history, more) =
+        Self::inscription_history_page(&index, inscription_id, page_index)?;
+
+      Ok(
+        Json(api::InscriptionHistory {
+          history,
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  fn inscription_history_page(
+    index: &Index,
+    inscription_id: InscriptionId,
+    page_index: u32,
+  ) -> ServerResult<(i32, Vec<api::InscriptionTransfer>, bool)> {
+    if !index.has_inscription_history_index() {
+      return Err(ServerError::NotFound(
+        "this server has no inscription history index".to_string(),
+      ));
+    }
+
+    let entry = index
+      .get_inscription_entry(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let (history, more) = index.get_inscription_history_paginated(
+      entry.sequence_number,
+      100,
+      page_index.try_into().unwrap(),
+    )?;
+
+    Ok((entry.inscription_number, history, more))
   }
 
   async fn inscription_inner(
     server_config: Arc<ServerConfig>,
     index: &Index,
+    mempool: Option<Arc<Mempool>>,
     accept_json: bool,
     query: query::Inscription,
     child: Option<usize>,
@@ -1809,10 +2462,12 @@ impl Server {
         }
       }
 
-      let (info, txout, inscription) = index
+      let (mut info, txout, inscription) = index
         .inscription_info(query, child)?
         .ok_or_not_found(|| format!("inscription {query}"))?;
 
+      info.pending_transfer = mempool.and_then(|mempool| mempool.spender(info.satpoint.outpoint));
+
       Ok(if accept_json {
         Json(info).into_response()
       } else {
@@ -1824,7 +2479,10 @@ impl Server {
           })),
           child_count: info.child_count,
           children: info.children,
+          content_duplicates: info.content_duplicates,
+          content_hash: info.content_hash,
           fee: info.fee,
+          first_with_content: info.first_with_content,
           height: info.height,
           inscription,
           id: info.id,
@@ -2047,12 +2705,14 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     accept_json: AcceptJson,
+    query: Query<InscriptionsQuery>,
   ) -> ServerResult {
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
       accept_json,
+      query,
     )
     .await
   }
@@ -2062,9 +2722,21 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<u32>,
     AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<InscriptionsQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
+      query.check(&index)?;
+
+      let (inscriptions, more) = if query.is_empty() {
+        index.get_inscriptions_paginated(100, page_index)?
+      } else {
+        index.get_inscriptions_filtered(
+          &query,
+          None,
+          100,
+          page_index.try_into().unwrap_or(usize::MAX),
+        )?
+      };
 
       let prev = page_index.checked_sub(1);
 
@@ -2082,6 +2754,7 @@ impl Server {
           inscriptions,
           next,
           prev,
+          query,
         }
         .page(server_config)
         .into_response()
@@ -2094,12 +2767,14 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(block_height): Path<u32>,
     AcceptJson(accept_json): AcceptJson,
+    query: Query<InscriptionsQuery>,
   ) -> ServerResult {
     Self::inscriptions_in_block_paginated(
       Extension(server_config),
       Extension(index),
       Path((block_height, 0)),
       AcceptJson(accept_json),
+      query,
     )
     .await
   }
@@ -2109,25 +2784,39 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path((block_height, page_index)): Path<(u32, u32)>,
     AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<InscriptionsQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
+      query.check(&index)?;
+
       let page_size = 100;
 
       let page_index_usize = usize::try_from(page_index).unwrap_or(usize::MAX);
       let page_size_usize = usize::try_from(page_size).unwrap_or(usize::MAX);
 
-      let mut inscriptions = index
-        .get_inscriptions_in_block(block_height)?
-        .into_iter()
-        .skip(page_index_usize.saturating_mul(page_size_usize))
-        .take(page_size_usize.saturating_add(1))
-        .collect::<Vec<InscriptionId>>();
+      let (inscriptions, more) = if query.is_empty() {
+        let mut inscriptions = index
+          .get_inscriptions_in_block(block_height)?
+          .into_iter()
+          .skip(page_index_usize.saturating_mul(page_size_usize))
+          .take(page_size_usize.saturating_add(1))
+          .collect::<Vec<InscriptionId>>();
 
-      let more = inscriptions.len() > page_size_usize;
+        let more = inscriptions.len() > page_size_usize;
 
-      if more {
-        inscriptions.pop();
-      }
+        if more {
+          inscriptions.pop();
+        }
+
+        (inscriptions, more)
+      } else {
+        index.get_inscriptions_filtered(
+          &query,
+          Some(block_height),
+          page_size_usize,
+          page_index_usize,
+        )?
+      };
 
       Ok(if accept_json {
         Json(api::Inscriptions {
@@ -2143,6 +2832,7 @@ impl Server {
           inscriptions,
           more,
           page_index,
+          query,
         )?
         .page(server_config)
         .into_response()
@@ -2225,6 +2915,41 @@ impl Server {
     })
   }
 
+  async fn content_hash(
+    Extension(index): Extension<Arc<Index>>,
+    Path(hash): Path<sha256::Hash>,
+  ) -> ServerResult {
+    Self::content_hash_paginated(Extension(index), Path((hash, 0))).await
+  }
+
+  async fn content_hash_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((hash, page_index)): Path<(sha256::Hash, u32)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_content_hash_index() {
+        return Err(ServerError::NotFound(
+          "this server has no content hash index".to_string(),
+        ));
+      }
+
+      let (ids, more) = index.get_inscriptions_by_content_hash(
+        hash,
+        100,
+        page_index.try_into().unwrap_or(usize::MAX),
+      )?;
+
+      Ok(
+        Json(api::Inscriptions {
+          ids,
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
   async fn sat_inscriptions(
     Extension(index): Extension<Arc<Index>>,
     Path(sat): Path<u64>,
@@ -2281,7 +3006,8 @@ impl Server {
 #[cfg(test)]
 mod tests {
   use {
-    super::*, reqwest::Url, serde::de::DeserializeOwned, std::net::TcpListener, tempfile::TempDir,
+    super::*, reqwest::Url, serde::de::DeserializeOwned, std::io::Write, std::net::TcpListener,
+    tempfile::TempDir,
   };
 
   const RUNE: u128 = 99246114928149462;
@@ -2413,13 +3139,19 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = server.event_channel();
+
+      let index = Arc::new(Index::open_with_event_sender(&settings, event_sender).unwrap());
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(settings, index, ord_server_handle, event_receiver)
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -3309,6 +4041,7 @@ mod tests {
         RuneEntry {
           block: id.block,
           etching: txid,
+          holders: 1,
           spaced_rune: SpacedRune {
             rune: Rune(RUNE),
             spacers: 0
@@ -3347,7 +4080,7 @@ mod tests {
   }
 
   #[test]
-  fn runes_are_displayed_on_rune_page() {
+  fn rune_holders_json() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
@@ -3357,8 +4090,6 @@ mod tests {
 
     let rune = Rune(RUNE);
 
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
-
     let (txid, id) = server.etch(
       Runestone {
         edicts: vec![Edict {
@@ -3368,80 +4099,67 @@ mod tests {
         }],
         etching: Some(Etching {
           rune: Some(rune),
-          symbol: Some('%'),
           premine: Some(u128::MAX),
-          turbo: true,
+          symbol: Some('%'),
           ..default()
         }),
         ..default()
       },
       1,
-      Some(
-        Inscription {
-          content_type: Some("text/plain".into()),
-          body: Some("hello".into()),
-          rune: Some(rune.commitment()),
-          ..default()
-        }
-        .to_witness(),
-      ),
+      None,
     );
 
-    let entry = RuneEntry {
-      block: id.block,
-      etching: txid,
-      spaced_rune: SpacedRune { rune, spacers: 0 },
-      premine: u128::MAX,
-      symbol: Some('%'),
-      timestamp: id.block,
-      turbo: true,
-      ..default()
-    };
+    server.mine_blocks(1);
 
-    assert_eq!(server.index.runes().unwrap(), [(id, entry)]);
+    let holders = api::RuneHolders {
+      holders: vec![api::RuneHolder::new(
+        Chain::Regtest,
+        server.core.tx_by_id(txid).output[0].script_pubkey.clone(),
+        Pile {
+          amount: u128::MAX,
+          divisibility: 0,
+          symbol: Some('%'),
+        },
+      )],
+      more: false,
+      page_index: 0,
+    };
 
-    assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{id}/holders")),
+      holders,
     );
 
-    let parent = InscriptionId { txid, index: 0 };
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{rune}/holders/0")),
+      holders,
+    );
 
-    server.assert_html(
-      format!("/rune/{rune}"),
-      RuneHtml {
-        id,
-        entry,
-        mintable: false,
-        parent: Some(parent),
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{rune}/holders/1")),
+      api::RuneHolders {
+        holders: Vec::new(),
+        more: false,
+        page_index: 1,
       },
     );
 
-    server.assert_response_regex(
-      format!("/inscription/{parent}"),
-      StatusCode::OK,
-      ".*
-<dl>
-  <dt>rune</dt>
-  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
-  .*
-</dl>
-.*",
-    );
+    server.assert_response(format!("/rune/{id}/holders"), StatusCode::NOT_FOUND, "");
   }
 
   #[test]
-  fn etched_runes_are_displayed_on_block_page() {
+  fn rune_activity() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
+      .ord_flag("--index-rune-activity")
       .build();
 
     server.mine_blocks(1);
 
-    let rune0 = Rune(RUNE);
+    let rune = Rune(RUNE);
 
-    let (_txid, id) = server.etch(
+    let (txid, id) = server.etch(
       Runestone {
         edicts: vec![Edict {
           id: RuneId::default(),
@@ -3449,7 +4167,9 @@ mod tests {
           output: 0,
         }],
         etching: Some(Etching {
-          rune: Some(rune0),
+          rune: Some(rune),
+          premine: Some(u128::MAX),
+          symbol: Some('%'),
           ..default()
         }),
         ..default()
@@ -3458,33 +4178,389 @@ mod tests {
       None,
     );
 
-    assert_eq!(
-      server.index.get_runes_in_block(id.block - 1).unwrap().len(),
-      0
+    server.mine_blocks(1);
+
+    let activity = api::RuneActivities {
+      activity: vec![
+        api::RuneActivity {
+          amount: u128::MAX,
+          from: Vec::new(),
+          height: id.block.try_into().unwrap(),
+          kind: api::RuneActivityKind::Transfer,
+          to: Some(OutPoint { txid, vout: 0 }),
+          txid,
+        },
+        api::RuneActivity {
+          amount: u128::MAX,
+          from: Vec::new(),
+          height: id.block.try_into().unwrap(),
+          kind: api::RuneActivityKind::Mint,
+          to: None,
+          txid,
+        },
+      ],
+      more: false,
+      page_index: 0,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneActivities>(format!("/rune/{id}/activity")),
+      activity,
     );
-    assert_eq!(server.index.get_runes_in_block(id.block).unwrap().len(), 1);
-    assert_eq!(
-      server.index.get_runes_in_block(id.block + 1).unwrap().len(),
-      0
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneActivities>(format!("/rune/{rune}/activity/0")),
+      activity,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneActivities>(format!("/rune/{rune}/activity/1")),
+      api::RuneActivities {
+        activity: Vec::new(),
+        more: false,
+        page_index: 1,
+      },
     );
 
     server.assert_response_regex(
-      format!("/block/{}", id.block),
+      format!("/rune/{id}/activity"),
       StatusCode::OK,
-      format!(".*<h2>1 Rune</h2>.*<li><a href=/rune/{rune0}>{rune0}</a></li>.*"),
+      format!(
+        ".*<title>Rune {rune} Activity</title>.*
+<h1><a href=/rune/{rune}>{rune}</a> Activity</h1>
+<table>
+.*
+    <td><a href=/block/{}>{}</a></td>
+    <td>transfer</td>
+    <td>{}\u{A0}%</td>
+    <td><a class=collapse href=/tx/{txid}>{txid}</a></td>
+    <td>
+    </td>
+    <td>
+      <a class=collapse href=/output/{txid}:0>{txid}:0</a>
+    </td>
+  </tr>
+  <tr>
+    <td><a href=/block/{}>{}</a></td>
+    <td>mint</td>
+    <td>{}\u{A0}%</td>
+    <td><a class=collapse href=/tx/{txid}>{txid}</a></td>
+    <td>
+    </td>
+    <td>
+    </td>
+.*",
+        id.block,
+        id.block,
+        u128::MAX,
+        id.block,
+        id.block,
+        u128::MAX,
+      ),
     );
   }
 
   #[test]
-  fn runes_are_spaced() {
+  fn rune_activity_requires_rune_activity_index() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
       .build();
 
-    server.mine_blocks(1);
-
-    let rune = Rune(RUNE);
+    server.assert_response(
+      "/rune/1:0/activity",
+      StatusCode::NOT_FOUND,
+      "this server has no rune activity index",
+    );
+  }
+
+  #[test]
+  fn address_runes_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (txid, _id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(u128::MAX),
+          symbol: Some('%'),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&server.core.tx_by_id(txid).output[0].script_pubkey)
+      .unwrap();
+
+    pretty_assert_eq!(
+      server
+        .get_json::<Vec<(SpacedRune, Decimal, Option<char>)>>(format!("/address/{address}/runes")),
+      [(
+        SpacedRune { rune, spacers: 0 },
+        Decimal {
+          value: u128::MAX,
+          scale: 0,
+        },
+        Some('%'),
+      )],
+    );
+  }
+
+  #[test]
+  fn address_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-addresses")
+      .ord_flag("--index-address-history")
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(u128::MAX),
+          symbol: Some('%'),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.mine_blocks(1);
+
+    let output = server.core.tx_by_id(txid).output[0].clone();
+
+    let address = Chain::Regtest
+      .address_from_script(&output.script_pubkey)
+      .unwrap();
+
+    let history = server.get_json::<api::AddressHistory>(format!("/address/{address}/history"));
+
+    assert!(!history.more);
+
+    pretty_assert_eq!(
+      history.history[0],
+      api::AddressTransaction {
+        received: output.value.to_sat(),
+        runes_received: [(id, u128::MAX)].into(),
+        ..api::AddressTransaction::new(id.block.try_into().unwrap(), txid)
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/address/{address}/history"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Address {address} History</title>.*
+    <td><a class=collapse href=/tx/{txid}>{txid}</a></td>
+    <td>{}</td>
+    <td>0</td>
+    <td>
+    </td>
+    <td>
+      <a href=/rune/{}>\\+{}\u{A0}%</a>
+    </td>
+.*",
+        output.value.to_sat(),
+        Rune(RUNE),
+        u128::MAX,
+      ),
+    );
+  }
+
+  #[test]
+  fn address_history_requires_address_history_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .build()
+      .assert_response(
+        "/address/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202/history",
+        StatusCode::NOT_FOUND,
+        "this server has no address history index",
+      );
+  }
+
+  #[test]
+  fn address_runes_requires_rune_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/address/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202/runes",
+        StatusCode::NOT_FOUND,
+        "this server has no rune index",
+      );
+  }
+
+  #[test]
+  fn runes_are_displayed_on_rune_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          symbol: Some('%'),
+          premine: Some(u128::MAX),
+          turbo: true,
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      Some(
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          rune: Some(rune.commitment()),
+          ..default()
+        }
+        .to_witness(),
+      ),
+    );
+
+    let entry = RuneEntry {
+      block: id.block,
+      etching: txid,
+      holders: 1,
+      spaced_rune: SpacedRune { rune, spacers: 0 },
+      premine: u128::MAX,
+      symbol: Some('%'),
+      timestamp: id.block,
+      turbo: true,
+      ..default()
+    };
+
+    assert_eq!(server.index.runes().unwrap(), [(id, entry)]);
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    let parent = InscriptionId { txid, index: 0 };
+
+    server.assert_html(
+      format!("/rune/{rune}"),
+      RuneHtml {
+        id,
+        entry,
+        mintable: false,
+        parent: Some(parent),
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{parent}"),
+      StatusCode::OK,
+      ".*
+<dl>
+  <dt>rune</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+  .*
+</dl>
+.*",
+    );
+  }
+
+  #[test]
+  fn etched_runes_are_displayed_on_block_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune0 = Rune(RUNE);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune0),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    assert_eq!(
+      server.index.get_runes_in_block(id.block - 1).unwrap().len(),
+      0
+    );
+    assert_eq!(server.index.get_runes_in_block(id.block).unwrap().len(), 1);
+    assert_eq!(
+      server.index.get_runes_in_block(id.block + 1).unwrap().len(),
+      0
+    );
+
+    server.assert_response_regex(
+      format!("/block/{}", id.block),
+      StatusCode::OK,
+      format!(".*<h2>1 Rune</h2>.*<li><a href=/rune/{rune0}>{rune0}</a></li>.*"),
+    );
+  }
+
+  #[test]
+  fn runes_are_spaced() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
 
     server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
 
@@ -3523,6 +4599,7 @@ mod tests {
         RuneEntry {
           block: id.block,
           etching: txid,
+          holders: 1,
           spaced_rune: SpacedRune { rune, spacers: 1 },
           premine: u128::MAX,
           symbol: Some('%'),
@@ -3614,6 +4691,7 @@ mod tests {
         RuneEntry {
           block: id.block,
           etching: txid,
+          holders: 1,
           spaced_rune: SpacedRune {
             rune: Rune(RUNE),
             spacers: 0
@@ -3680,6 +4758,7 @@ mod tests {
           block: id.block,
           divisibility: 1,
           etching: txid,
+          holders: 1,
           spaced_rune: SpacedRune { rune, spacers: 0 },
           premine: u128::MAX,
           timestamp: id.block,
@@ -3730,6 +4809,7 @@ mod tests {
         indexed: true,
         inscriptions: Vec::new(),
         outpoint: output,
+        pending_spend: None,
         runes: vec![(
           SpacedRune {
             rune: Rune(RUNE),
@@ -4966,24 +6046,137 @@ mod tests {
   }
 
   #[test]
-  fn inscription_page_title() {
+  fn inscription_history() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
-      .index_sats()
+      .ord_flag("--index-inscription-history")
       .build();
+
     server.mine_blocks(1);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
+    let txid0 = server.core.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
+    let txid1 = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId {
+      txid: txid0,
+      index: 0,
+    };
+
+    let address = Chain::Regtest
+      .address_from_script(&server.core.tx_by_id(txid1).output[0].script_pubkey)
+      .unwrap();
+
+    let history = api::InscriptionHistory {
+      history: vec![api::InscriptionTransfer {
+        address: Some(uncheck(&address)),
+        height: 3,
+        new_satpoint: SatPoint {
+          outpoint: OutPoint::new(txid1, 0),
+          offset: 0,
+        },
+        old_satpoint: SatPoint {
+          outpoint: OutPoint::new(txid0, 0),
+          offset: 0,
+        },
+        txid: txid1,
+      }],
+      more: false,
+      page_index: 0,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/inscription/{id}/history")),
+      history,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history")),
+      history,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history/1")),
+      api::InscriptionHistory {
+        history: Vec::new(),
+        more: false,
+        page_index: 1,
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}/history"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 History</title>.*
+    <td><a href=/block/3>3</a></td>
+    <td><a class=collapse href=/tx/{txid1}>{txid1}</a></td>
+    <td><a class=collapse href=/satpoint/{txid0}:0:0>{txid0}:0:0</a></td>
+    <td><a class=collapse href=/satpoint/{txid1}:0:0>{txid1}:0:0</a></td>
+    <td><a class=collapse href=/address/{address}>{address}</a></td>
+.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_history_requires_inscription_history_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response(
+      format!("/inscription/{id}/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{id}/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+  }
+
+  #[test]
+  fn inscription_page_title() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
     );
   }
 
@@ -6696,6 +7889,329 @@ next
     );
   }
 
+  #[test]
+  fn inscriptions_can_be_filtered() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-attributes")
+      .ord_flag("--index-metaprotocols")
+      .build();
+
+    server.mine_blocks(3);
+
+    let text = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    let image = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("image/png", [1; 100]).to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    let metaprotocol = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          3,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("world".into()),
+            metaprotocol: Some("foo".into()),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    #[track_caller]
+    fn case(server: &TestServer, path: &str, expected: &[InscriptionId]) {
+      pretty_assert_eq!(
+        server.get_json::<api::Inscriptions>(path).ids,
+        expected,
+        "{path}",
+      );
+    }
+
+    case(&server, "/inscriptions?media=text", &[metaprotocol, text]);
+    case(&server, "/inscriptions?media=image", &[image]);
+    case(&server, "/inscriptions?content_type=image/png", &[image]);
+    case(&server, "/inscriptions?content_type=image/webp", &[]);
+    case(&server, "/inscriptions?metaprotocol=foo", &[metaprotocol]);
+    case(
+      &server,
+      "/inscriptions?media=text&metaprotocol=foo",
+      &[metaprotocol],
+    );
+    case(&server, "/inscriptions?media=image&metaprotocol=foo", &[]);
+    case(
+      &server,
+      "/inscriptions?size_bucket=3",
+      &[metaprotocol, text],
+    );
+    case(&server, "/inscriptions?size_bucket=7", &[image]);
+    case(
+      &server,
+      "/inscriptions/block/4?media=text",
+      &[text, metaprotocol],
+    );
+    case(&server, "/inscriptions/block/3?media=text", &[]);
+
+    let later = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(4, 0, 0, inscription("text/plain", "later").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    case(&server, "/inscriptions/block/5?media=text", &[later]);
+    case(
+      &server,
+      "/inscriptions/block/4?media=text",
+      &[text, metaprotocol],
+    );
+    case(
+      &server,
+      "/inscriptions?media=text",
+      &[later, metaprotocol, text],
+    );
+
+    server.assert_response_regex(
+      "/inscriptions?media=image",
+      StatusCode::OK,
+      format!(".*<h1>All Inscriptions</h1>.*<a href=/inscription/{image}>.*"),
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/block/4?content_type=image/png",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscriptions in <a href=/block/4>Block 4</a></h1>.*<a href=/inscription/{image}>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_filters_require_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/inscriptions?media=image",
+      StatusCode::BAD_REQUEST,
+      "this server has no inscription attribute index",
+    );
+
+    server.assert_response(
+      "/inscriptions/block/0?metaprotocol=foo",
+      StatusCode::BAD_REQUEST,
+      "this server has no metaprotocol index",
+    );
+  }
+
+  #[test]
+  fn inscription_media_filter_must_be_valid() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-attributes")
+      .build()
+      .assert_response(
+        "/inscriptions?media=foo",
+        StatusCode::BAD_REQUEST,
+        "unknown media `foo`, expected one of: audio, code, font, iframe, image, markdown, model, \
+        pdf, text, unknown, video",
+      );
+  }
+
+  #[test]
+  fn inscriptions_query_display() {
+    assert_eq!(InscriptionsQuery::default().to_string(), "");
+    assert_eq!(
+      InscriptionsQuery {
+        content_type: Some("text/plain;charset=utf-8".into()),
+        size_bucket: Some(3),
+        ..default()
+      }
+      .to_string(),
+      "?content_type=text%2Fplain%3Bcharset%3Dutf-8&size_bucket=3",
+    );
+  }
+
+  #[test]
+  fn content_hash_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-content-hashes")
+      .build();
+
+    server.mine_blocks(3);
+
+    let mut ids = Vec::new();
+
+    for (i, body) in ["foo", "bar", "foo"].into_iter().enumerate() {
+      ids.push(InscriptionId {
+        txid: server.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", body).to_witness())],
+          ..default()
+        }),
+        index: 0,
+      });
+
+      server.mine_blocks(1);
+    }
+
+    let hash = sha256::Hash::hash(b"foo");
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/content-hash/{hash}")),
+      api::Inscriptions {
+        ids: vec![ids[0], ids[2]],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/content-hash/{hash}/1")),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+      },
+    );
+
+    let first = server.get_json::<api::Inscription>(format!("/inscription/{}", ids[0]));
+
+    assert_eq!(first.content_hash, Some(hash));
+    assert_eq!(first.content_duplicates, Some(1));
+    assert_eq!(first.first_with_content, Some(ids[0]));
+
+    let unique = server.get_json::<api::Inscription>(format!("/inscription/{}", ids[1]));
+
+    assert_eq!(unique.content_duplicates, Some(0));
+    assert_eq!(unique.first_with_content, Some(ids[1]));
+
+    let duplicate = server.get_json::<api::Inscription>(format!("/inscription/{}", ids[2]));
+
+    assert_eq!(duplicate.content_duplicates, Some(1));
+    assert_eq!(duplicate.first_with_content, Some(ids[0]));
+
+    server.assert_response_regex(
+      format!("/inscription/{}", ids[2]),
+      StatusCode::OK,
+      format!(
+        ".*<dt>content hash</dt>\\s*<dd class=collapse>{hash}</dd>\\s*\
+        <dt>first with this content</dt>\\s*\
+        <dd><a class=collapse href=/inscription/{0}>{0}</a></dd>\\s*\
+        <dt>content duplicates</dt>\\s*\
+        <dd><a href=/r/content-hash/{hash}>1</a></dd>.*",
+        ids[0],
+      ),
+    );
+  }
+
+  #[test]
+  fn content_hash_index_decodes_brotli() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-content-hashes")
+      .build();
+
+    server.mine_blocks(2);
+
+    let mut compressed = Vec::new();
+
+    brotli::enc::writer::CompressorWriter::new(&mut compressed, 4096, 11, 22)
+      .write_all(b"foo")
+      .unwrap();
+
+    let mut ids = Vec::new();
+
+    for (i, inscription) in [
+      Inscription {
+        content_type: Some("text/plain".into()),
+        content_encoding: Some("br".into()),
+        body: Some(compressed),
+        ..default()
+      },
+      inscription("text/plain", "foo"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      ids.push(InscriptionId {
+        txid: server.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription.to_witness())],
+          ..default()
+        }),
+        index: 0,
+      });
+
+      server.mine_blocks(1);
+    }
+
+    let hash = sha256::Hash::hash(b"foo");
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/content-hash/{hash}")),
+      api::Inscriptions {
+        ids: ids.clone(),
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    let compressed = server.get_json::<api::Inscription>(format!("/inscription/{}", ids[0]));
+
+    assert_eq!(compressed.content_hash, Some(hash));
+    assert_eq!(compressed.content_duplicates, Some(1));
+
+    let uncompressed = server.get_json::<api::Inscription>(format!("/inscription/{}", ids[1]));
+
+    assert_eq!(uncompressed.content_duplicates, Some(1));
+    assert_eq!(uncompressed.first_with_content, Some(ids[0]));
+  }
+
+  #[test]
+  fn content_hash_requires_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      format!("/r/content-hash/{}", sha256::Hash::hash(b"foo")),
+      StatusCode::NOT_FOUND,
+      "this server has no content hash index",
+    );
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription = server.get_json::<api::Inscription>(format!("/inscription/{txid}i0"));
+
+    assert_eq!(inscription.content_hash, None);
+    assert_eq!(inscription.content_duplicates, None);
+    assert_eq!(inscription.first_with_content, None);
+  }
+
   #[test]
   fn inscription_query_display() {
     assert_eq!(
@@ -7420,4 +8936,338 @@ next
       "output 0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef:123 not found",
     );
   }
+
+  #[test]
+  fn events_are_not_found_unless_enabled() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.assert_response("/events", StatusCode::NOT_FOUND, "event stream not found");
+  }
+
+  #[test]
+  fn events_rejects_invalid_kind() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--events")
+      .build();
+
+    server.assert_response(
+      "/events?kind=foo",
+      StatusCode::BAD_REQUEST,
+      "invalid event kind `foo`",
+    );
+  }
+
+  #[test]
+  fn events_stream() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let response =
+      reqwest::blocking::get(server.join_url("/events?kind=inscription_created")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let mut lines = io::BufRead::lines(BufReader::new(response));
+
+    let mut received = Vec::new();
+
+    while received.len() < 2 {
+      let line = lines.next().unwrap().unwrap();
+      if !line.is_empty() && !line.starts_with(':') {
+        received.push(line);
+      }
+    }
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      received,
+      [
+        "event:inscription_created".to_string(),
+        format!(
+          "data:{}",
+          serde_json::to_string(&Event::InscriptionCreated {
+            block_height: 2,
+            charms: 0,
+            inscription_id,
+            location: Some(SatPoint {
+              outpoint: OutPoint { txid, vout: 0 },
+              offset: 0,
+            }),
+            parent_inscription_ids: Vec::new(),
+            sequence_number: 0,
+          })
+          .unwrap()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn events_journal() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let event = api::JournaledEvent {
+      event: Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        inscription_id: InscriptionId { txid, index: 0 },
+        location: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 0,
+      },
+      height: 2,
+      sequence_number: 0,
+      tx_index: 1,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/0"),
+      api::Events {
+        events: vec![event],
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/3"),
+      api::Events {
+        events: Vec::new(),
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/0/1"),
+      api::Events {
+        events: Vec::new(),
+        more: false,
+        page_index: 1,
+      }
+    );
+  }
+
+  #[test]
+  fn events_journal_requires_event_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/events/0",
+      StatusCode::NOT_FOUND,
+      "this server has no event index",
+    );
+  }
+
+  #[test]
+  fn events_stream_replays_journal() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .server_flag("--events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::get(server.join_url("/events?from_height=2")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut lines = io::BufRead::lines(BufReader::new(response));
+
+    assert_eq!(lines.next().unwrap().unwrap(), "event:inscription_created");
+    assert!(lines
+      .next()
+      .unwrap()
+      .unwrap()
+      .contains(&InscriptionId { txid, index: 0 }.to_string()));
+  }
+
+  #[test]
+  fn webhooks_require_event_index() {
+    let server = TestServer::new();
+
+    assert_eq!(
+      Dispatcher::new(
+        server.index.clone(),
+        &Settings::default(),
+        WebhooksConfig {
+          webhooks: Vec::new()
+        },
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      "`--webhooks` requires index created with `--index-events` flag",
+    );
+  }
+
+  #[test]
+  fn webhooks_deliver_events_with_retries() {
+    // other tests pick ephemeral ports by binding to port 0 and releasing
+    // them, so use a port outside the ephemeral range, which no other test
+    // will connect to
+    let listener = (10_000..20_000)
+      .find_map(|port| TcpListener::bind(("127.0.0.1", port)).ok())
+      .unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    thread::spawn(move || {
+      let mut i = 0;
+
+      for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        // the client may open a connection and close it without sending a
+        // request, for example when it times out under load
+        let mut request_line = String::new();
+        if io::BufRead::read_line(&mut reader, &mut request_line).unwrap_or_default() == 0 {
+          continue;
+        }
+        assert_eq!(request_line, "POST /hook HTTP/1.1\r\n");
+
+        let mut headers = BTreeMap::new();
+        loop {
+          let mut line = String::new();
+          io::BufRead::read_line(&mut reader, &mut line).unwrap();
+          let Some((name, value)) = line.trim_end().split_once(": ") else {
+            break;
+          };
+          headers.insert(name.to_lowercase(), value.to_string());
+        }
+
+        let mut body = vec![0; headers["content-length"].parse().unwrap()];
+        reader.read_exact(&mut body).unwrap();
+
+        let status = if i == 0 {
+          "500 Internal Server Error"
+        } else {
+          "200 OK"
+        };
+
+        io::Write::write_all(
+          &mut stream,
+          format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n").as_bytes(),
+        )
+        .unwrap();
+
+        sender.send((headers, body)).unwrap();
+
+        i += 1;
+      }
+    });
+
+    let tempdir = TempDir::new().unwrap();
+    let config = tempdir.path().join("webhooks.yaml");
+
+    fs::write(
+      &config,
+      format!(
+        "webhooks:
+- url: http://127.0.0.1:{port}/hook
+  secret: foo
+  kinds: [inscription_created]
+  from_height: 0
+"
+      ),
+    )
+    .unwrap();
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .server_option("--webhooks", config.to_str().unwrap())
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let failed = receiver.recv_timeout(Duration::from_secs(30)).unwrap();
+    let (headers, body) = receiver.recv_timeout(Duration::from_secs(30)).unwrap();
+
+    assert_eq!(failed, (headers.clone(), body.clone()));
+
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(headers["x-ord-event-id"], "2:1:0");
+    assert_eq!(
+      headers["x-ord-signature"],
+      format!("sha256={}", webhooks::signature("foo", &body)),
+    );
+
+    let journaled = serde_json::from_slice::<api::JournaledEvent>(&body).unwrap();
+
+    assert_eq!(
+      journaled.event.inscription_id(),
+      Some(InscriptionId { txid, index: 0 })
+    );
+
+    let cursors = server
+      .tempdir
+      .path()
+      .join("regtest")
+      .join("webhook-cursors.json");
+
+    for attempt in 0.. {
+      let cursor = serde_json::from_slice::<serde_json::Value>(&fs::read(&cursors).unwrap())
+        .unwrap()[format!("http://127.0.0.1:{port}/hook")]
+      .clone();
+
+      if cursor["height"] == 2 {
+        break;
+      }
+
+      assert!(attempt < 100, "cursor was not persisted: {cursor}");
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    assert!(receiver.try_recv().is_err());
+  }
 }
@@ -0,0 +1,250 @@
+use {
+  super::*,
+  crate::index::{
+    entry::EventKeyValue,
+    event::{Event, EventKind},
+  },
+  axum::response::sse,
+  tokio::sync::broadcast::{self, error::RecvError},
+};
+
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct EventsQuery {
+  pub(super) address: Option<Address<NetworkUnchecked>>,
+  pub(super) from_height: Option<u32>,
+  pub(super) inscription: Option<InscriptionId>,
+  pub(super) kind: Option<String>,
+  pub(super) parent: Option<InscriptionId>,
+  pub(super) rune: Option<RuneId>,
+}
+
+const REPLAY_BATCH_SIZE: usize = 100;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EventFilter {
+  pub(crate) from_height: Option<u32>,
+  pub(crate) inscription: Option<InscriptionId>,
+  pub(crate) kinds: Option<BTreeSet<EventKind>>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) rune: Option<RuneId>,
+  pub(crate) script_pubkey: Option<ScriptBuf>,
+}
+
+impl EventFilter {
+  pub(super) fn from_query(query: EventsQuery, chain: Chain) -> ServerResult<Self> {
+    let kinds = query
+      .kind
+      .map(|kinds| {
+        kinds
+          .split(',')
+          .map(|kind| kind.trim().parse::<EventKind>())
+          .collect::<Result<BTreeSet<EventKind>>>()
+      })
+      .transpose()
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let script_pubkey = query
+      .address
+      .map(|address| address.require_network(chain.network()))
+      .transpose()
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?
+      .map(|address| address.script_pubkey());
+
+    Ok(Self {
+      from_height: query.from_height,
+      inscription: query.inscription,
+      kinds,
+      parent: query.parent,
+      rune: query.rune,
+      script_pubkey,
+    })
+  }
+
+  pub(crate) fn matches(&self, index: &Index, event: &Event) -> Result<bool> {
+    if let Some(from_height) = self.from_height {
+      if event.block_height() < from_height {
+        return Ok(false);
+      }
+    }
+
+    if let Some(kinds) = &self.kinds {
+      if !kinds.contains(&event.kind()) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(inscription) = self.inscription {
+      if event.inscription_id() != Some(inscription) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(parent) = self.parent {
+      if !Self::has_parent(index, event, parent)? {
+        return Ok(false);
+      }
+    }
+
+    if let Some(rune) = self.rune {
+      if event.rune_id() != Some(rune) {
+        return Ok(false);
+      }
+    }
+
+    if let Some(script_pubkey) = &self.script_pubkey {
+      let Some(outpoint) = event.outpoint() else {
+        return Ok(false);
+      };
+
+      let Some(transaction) = index.get_transaction(outpoint.txid)? else {
+        return Ok(false);
+      };
+
+      let Some(output) = transaction.output.get(outpoint.vout.into_usize()) else {
+        return Ok(false);
+      };
+
+      if output.script_pubkey != *script_pubkey {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  fn has_parent(index: &Index, event: &Event, parent: InscriptionId) -> Result<bool> {
+    match event {
+      Event::InscriptionCreated {
+        parent_inscription_ids,
+        ..
+      } => Ok(parent_inscription_ids.contains(&parent)),
+      Event::InscriptionTransferred { inscription_id, .. } => {
+        let Some(parent) = index.get_inscription_entry(parent)? else {
+          return Ok(false);
+        };
+
+        let Some(entry) = index.get_inscription_entry(*inscription_id)? else {
+          return Ok(false);
+        };
+
+        Ok(entry.parents.contains(&parent.sequence_number))
+      }
+      _ => Ok(false),
+    }
+  }
+}
+
+pub(super) fn sse_event(event: &Event) -> Result<sse::Event> {
+  Ok(
+    sse::Event::default()
+      .event(event.kind().name())
+      .json_data(event)?,
+  )
+}
+
+/// Stream events matching `filter`. If `filter` has a starting height and the
+/// index has an event journal, journaled events are replayed in batches before
+/// live events, and live events for blocks already replayed are skipped.
+pub(super) fn stream(
+  index: Arc<Index>,
+  receiver: broadcast::Receiver<Event>,
+  filter: EventFilter,
+) -> Result<impl futures::Stream<Item = Result<sse::Event, Error>>> {
+  let replayed_through = match filter.from_height {
+    Some(_) if index.has_event_index() => index.block_height()?.map(|height| height.n()),
+    _ => None,
+  };
+
+  let replay = futures::StreamExt::flatten(futures::stream::unfold(
+    replayed_through.map(|through| {
+      (
+        index.clone(),
+        filter.clone(),
+        filter.from_height.and_then(Index::event_cursor_before),
+        through,
+      )
+    }),
+    |state| async move {
+      let (index, filter, cursor, through) = state?;
+
+      match task::block_in_place(|| replay_batch(&index, &filter, cursor, through)) {
+        Ok((events, next)) => Some((
+          futures::stream::iter(events),
+          next.map(|cursor| (index, filter, Some(cursor), through)),
+        )),
+        Err(err) => Some((futures::stream::iter(vec![Err(err)]), None)),
+      }
+    },
+  ));
+
+  let live = futures::stream::unfold(
+    (index, receiver, filter),
+    move |(index, mut receiver, filter)| async move {
+      loop {
+        let event = match receiver.recv().await {
+          Ok(event) => event,
+          Err(RecvError::Lagged(skipped)) => {
+            log::warn!("event stream subscriber lagged, skipped {skipped} events");
+            continue;
+          }
+          Err(RecvError::Closed) => return None,
+        };
+
+        if replayed_through.is_some_and(|height| event.block_height() <= height) {
+          continue;
+        }
+
+        match task::block_in_place(|| filter.matches(&index, &event)) {
+          Ok(true) => {}
+          Ok(false) => continue,
+          Err(err) => {
+            log::warn!("failed to filter event: {err}");
+            continue;
+          }
+        }
+
+        return Some((sse_event(&event), (index, receiver, filter)));
+      }
+    },
+  );
+
+  Ok(replay.chain(live))
+}
+
+/// Returns the events matching `filter` in the batch of journaled events after
+/// `cursor`, and the cursor of the next batch, if any events in blocks up to
+/// `through` remain.
+fn replay_batch(
+  index: &Index,
+  filter: &EventFilter,
+  cursor: Option<EventKeyValue>,
+  through: u32,
+) -> Result<(Vec<Result<sse::Event>>, Option<EventKeyValue>)> {
+  let journaled = index.get_events_after(cursor, REPLAY_BATCH_SIZE)?;
+
+  let mut next = (journaled.len() == REPLAY_BATCH_SIZE)
+    .then(|| journaled.last())
+    .flatten()
+    .map(|journaled| {
+      (
+        journaled.height,
+        journaled.tx_index,
+        journaled.sequence_number,
+      )
+    });
+
+  let mut events = Vec::new();
+
+  for journaled in journaled {
+    if journaled.height > through {
+      next = None;
+      break;
+    }
+
+    if filter.matches(index, &journaled.event)? {
+      events.push(sse_event(&journaled.event));
+    }
+  }
+
+  Ok((events, next))
+}
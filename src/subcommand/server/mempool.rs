@@ -0,0 +1,468 @@
+use {super::*, std::sync::RwLock};
+
+#[derive(Clone)]
+struct Entry {
+  inputs: Vec<OutPoint>,
+  transaction: Option<api::MempoolTransaction>,
+}
+
+#[derive(Default)]
+struct State {
+  entries: BTreeMap<Txid, Entry>,
+  spends: BTreeMap<OutPoint, Txid>,
+  tip: Option<(u32, BlockHash)>,
+}
+
+/// In-memory overlay of unconfirmed transactions, refreshed by polling
+/// Bitcoin Core's mempool. Transactions which create or move inscriptions, or
+/// which spend runes or contain runestones, are annotated.
+pub(super) struct Mempool {
+  client: Client,
+  index: Arc<Index>,
+  state: RwLock<State>,
+}
+
+impl Mempool {
+  pub(super) fn new(index: Arc<Index>, settings: &Settings) -> Result<Self> {
+    Ok(Self {
+      client: settings.bitcoin_rpc_client(None)?,
+      index,
+      state: RwLock::new(State::default()),
+    })
+  }
+
+  pub(super) fn run(&self, polling_interval: Duration) {
+    loop {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      if let Err(error) = self.update() {
+        log::warn!("Updating mempool: {error}");
+      }
+
+      thread::sleep(polling_interval);
+    }
+  }
+
+  pub(super) fn transactions(&self) -> Vec<api::MempoolTransaction> {
+    self
+      .state
+      .read()
+      .unwrap()
+      .entries
+      .values()
+      .filter_map(|entry| entry.transaction.clone())
+      .collect()
+  }
+
+  pub(super) fn transaction(&self, txid: Txid) -> Option<api::MempoolTransaction> {
+    self
+      .state
+      .read()
+      .unwrap()
+      .entries
+      .get(&txid)
+      .and_then(|entry| entry.transaction.clone())
+  }
+
+  /// Returns the unconfirmed transaction which spends `outpoint`, if any.
+  pub(super) fn spender(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.state.read().unwrap().spends.get(&outpoint).copied()
+  }
+
+  fn update(&self) -> Result {
+    let txids = self
+      .client
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<BTreeSet<Txid>>();
+
+    let tip = self
+      .index
+      .block_height()?
+      .map(|height| {
+        self
+          .index
+          .block_hash(Some(height.n()))
+          .map(|hash| (height.n(), hash.unwrap()))
+      })
+      .transpose()?;
+
+    let mut entries = {
+      let state = self.state.read().unwrap();
+
+      // annotations of confirmed inputs only change if their block is
+      // reorged, in which case every annotation is recomputed
+      let reorged = match state.tip {
+        Some((height, hash)) => self.index.block_hash(Some(height))? != Some(hash),
+        None => false,
+      };
+
+      if reorged {
+        BTreeMap::new()
+      } else {
+        state.entries.clone()
+      }
+    };
+
+    // transactions which left the mempool, by confirming or being evicted,
+    // change the inputs of their descendants, which are annotated again
+    let mut removed = entries
+      .keys()
+      .filter(|txid| !txids.contains(*txid))
+      .copied()
+      .collect::<BTreeSet<Txid>>();
+
+    loop {
+      let invalidated = entries
+        .iter()
+        .filter(|(txid, entry)| {
+          !removed.contains(*txid)
+            && entry
+              .inputs
+              .iter()
+              .any(|outpoint| removed.contains(&outpoint.txid))
+        })
+        .map(|(txid, _entry)| *txid)
+        .collect::<Vec<Txid>>();
+
+      if invalidated.is_empty() {
+        break;
+      }
+
+      removed.extend(invalidated);
+    }
+
+    entries.retain(|txid, _entry| !removed.contains(txid));
+
+    // while the index lags behind Core, inputs of new transactions may have
+    // been confirmed in blocks the index has not seen, and would be annotated
+    // as empty, so they are annotated once the index catches up
+    let lagging =
+      tip.map(|(height, _hash)| u64::from(height)) < Some(self.client.get_block_count()?);
+
+    let mut pending = Vec::new();
+
+    if !lagging {
+      for txid in &txids {
+        if !entries.contains_key(txid) {
+          if let Some(transaction) = self.index.get_transaction(*txid)? {
+            pending.push(transaction);
+          }
+        }
+      }
+    }
+
+    let mut failed = BTreeSet::new();
+
+    // annotate parents before children, so that inscriptions can be followed
+    // through chains of unconfirmed transactions
+    while !pending.is_empty() {
+      let unannotated = pending
+        .iter()
+        .map(|transaction| transaction.compute_txid())
+        .collect::<BTreeSet<Txid>>();
+
+      let (ready, waiting) = pending
+        .into_iter()
+        .partition::<Vec<Transaction>, _>(|transaction| {
+          transaction
+            .input
+            .iter()
+            .all(|input| !unannotated.contains(&input.previous_output.txid))
+        });
+
+      if ready.is_empty() {
+        break;
+      }
+
+      for transaction in ready {
+        let txid = transaction.compute_txid();
+
+        // children of transactions which failed to annotate are retried with
+        // them on the next update
+        if transaction
+          .input
+          .iter()
+          .any(|input| failed.contains(&input.previous_output.txid))
+        {
+          failed.insert(txid);
+          continue;
+        }
+
+        match self.annotate(&transaction, &entries) {
+          Ok(entry) => {
+            entries.insert(txid, entry);
+          }
+          Err(error) => {
+            log::warn!("Annotating mempool transaction {txid}: {error}");
+            failed.insert(txid);
+          }
+        }
+      }
+
+      pending = waiting;
+    }
+
+    let spends = entries
+      .iter()
+      .flat_map(|(txid, entry)| entry.inputs.iter().map(|outpoint| (*outpoint, *txid)))
+      .collect();
+
+    *self.state.write().unwrap() = State {
+      entries,
+      spends,
+      tip,
+    };
+
+    Ok(())
+  }
+
+  fn annotate(&self, transaction: &Transaction, entries: &BTreeMap<Txid, Entry>) -> Result<Entry> {
+    let txid = transaction.compute_txid();
+
+    let inputs = transaction
+      .input
+      .iter()
+      .map(|input| input.previous_output)
+      .collect::<Vec<OutPoint>>();
+
+    let mut moved = Vec::new();
+    let mut runes = BTreeMap::<SpacedRune, Pile>::new();
+
+    for (input, outpoint) in inputs.iter().enumerate() {
+      if let Some(parent) = entries.get(&outpoint.txid) {
+        for inscription in parent
+          .transaction
+          .iter()
+          .flat_map(|transaction| &transaction.inscriptions)
+        {
+          if let Some(satpoint) = inscription
+            .new_satpoint
+            .filter(|satpoint| satpoint.outpoint == *outpoint)
+          {
+            moved.push((input, inscription.id, satpoint));
+          }
+        }
+
+        continue;
+      }
+
+      for (satpoint, id) in self
+        .index
+        .get_inscriptions_on_output_with_satpoints(*outpoint)?
+      {
+        moved.push((input, id, satpoint));
+      }
+
+      for (rune, pile) in self.index.get_rune_balances_for_output(*outpoint)? {
+        runes
+          .entry(rune)
+          .and_modify(|balance| balance.amount += pile.amount)
+          .or_insert(pile);
+      }
+    }
+
+    let envelopes = ParsedEnvelope::from_transaction(transaction);
+
+    let runestone = Runestone::decipher(transaction);
+
+    if moved.is_empty() && envelopes.is_empty() && runes.is_empty() && runestone.is_none() {
+      return Ok(Entry {
+        inputs,
+        transaction: None,
+      });
+    }
+
+    let mut starts = Vec::new();
+
+    if !moved.is_empty() || !envelopes.is_empty() {
+      let mut total = 0;
+
+      for outpoint in &inputs {
+        starts.push(total);
+
+        total += self
+          .index
+          .get_transaction(outpoint.txid)?
+          .and_then(|parent| {
+            parent
+              .output
+              .get(usize::try_from(outpoint.vout).unwrap())
+              .map(|tx_out| tx_out.value.to_sat())
+          })
+          .ok_or_else(|| anyhow!("output {outpoint} not found"))?;
+      }
+    }
+
+    let total_output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .sum::<u64>();
+
+    let mut inscriptions = moved
+      .into_iter()
+      .map(|(input, id, old_satpoint)| api::MempoolInscription {
+        id,
+        new_satpoint: Self::locate(transaction, txid, starts[input] + old_satpoint.offset),
+        old_satpoint: Some(old_satpoint),
+      })
+      .collect::<Vec<api::MempoolInscription>>();
+
+    for (index, envelope) in envelopes.into_iter().enumerate() {
+      let offset = envelope
+        .payload
+        .pointer()
+        .filter(|&pointer| pointer < total_output_value)
+        .unwrap_or(starts[usize::try_from(envelope.input).unwrap()]);
+
+      inscriptions.push(api::MempoolInscription {
+        id: InscriptionId {
+          txid,
+          index: index.try_into().unwrap(),
+        },
+        new_satpoint: Self::locate(transaction, txid, offset),
+        old_satpoint: None,
+      });
+    }
+
+    Ok(Entry {
+      inputs,
+      transaction: Some(api::MempoolTransaction {
+        inscriptions,
+        runes,
+        runestone,
+        txid,
+      }),
+    })
+  }
+
+  /// Returns the satpoint of the sat at `offset` among the outputs of
+  /// `transaction`, or `None` if it would be paid as fee.
+  fn locate(transaction: &Transaction, txid: Txid, offset: u64) -> Option<SatPoint> {
+    let mut start = 0;
+
+    for (vout, tx_out) in transaction.output.iter().enumerate() {
+      let end = start + tx_out.value.to_sat();
+
+      if offset < end {
+        return Some(SatPoint {
+          outpoint: OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          },
+          offset: offset - start,
+        });
+      }
+
+      start = end;
+    }
+
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context, bitcoincore_rpc::Auth};
+
+  #[test]
+  fn transactions_are_not_annotated_while_index_lags() {
+    let Context {
+      core,
+      index,
+      tempdir,
+    } = Context::builder().build();
+
+    let index = Arc::new(index);
+
+    let mempool = Mempool {
+      client: Client::new(&core.url(), Auth::CookieFile(tempdir.path().join("cookie"))).unwrap(),
+      index: index.clone(),
+      state: RwLock::new(State::default()),
+    };
+
+    core.mine_blocks(1);
+    index.update().unwrap();
+
+    let reveal = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let send = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    mempool.update().unwrap();
+
+    assert_eq!(mempool.transaction(send), None);
+
+    index.update().unwrap();
+
+    mempool.update().unwrap();
+
+    assert_eq!(
+      mempool.transaction(send).unwrap().inscriptions,
+      [api::MempoolInscription {
+        id: InscriptionId {
+          txid: reveal,
+          index: 0,
+        },
+        new_satpoint: Some(SatPoint {
+          outpoint: OutPoint {
+            txid: send,
+            vout: 0,
+          },
+          offset: 0,
+        }),
+        old_satpoint: Some(SatPoint {
+          outpoint: OutPoint {
+            txid: reveal,
+            vout: 0,
+          },
+          offset: 0,
+        }),
+      }],
+    );
+  }
+
+  #[test]
+  fn locate() {
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          value: Amount::from_sat(100),
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: Amount::from_sat(50),
+          script_pubkey: ScriptBuf::new(),
+        },
+      ],
+    };
+
+    let txid = transaction.compute_txid();
+
+    let satpoint = |vout, offset| {
+      Some(SatPoint {
+        outpoint: OutPoint { txid, vout },
+        offset,
+      })
+    };
+
+    assert_eq!(Mempool::locate(&transaction, txid, 0), satpoint(0, 0));
+    assert_eq!(Mempool::locate(&transaction, txid, 99), satpoint(0, 99));
+    assert_eq!(Mempool::locate(&transaction, txid, 100), satpoint(1, 0));
+    assert_eq!(Mempool::locate(&transaction, txid, 149), satpoint(1, 49));
+    assert_eq!(Mempool::locate(&transaction, txid, 150), None);
+  }
+}
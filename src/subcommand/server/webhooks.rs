@@ -0,0 +1,534 @@
+use {
+  super::*,
+  crate::index::{entry::EventKeyValue, event::EventKind},
+  bitcoin::hashes::{hmac, sha256, HashEngine},
+};
+
+const BATCH_SIZE: usize = 100;
+const MAX_BACKOFF: Duration = Duration::from_secs(256);
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct WebhooksConfig {
+  pub(crate) webhooks: Vec<WebhookConfig>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct WebhookConfig {
+  pub(crate) address: Option<Address<NetworkUnchecked>>,
+  pub(crate) from_height: Option<u32>,
+  pub(crate) inscription: Option<InscriptionId>,
+  pub(crate) kinds: Option<BTreeSet<EventKind>>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) rune: Option<RuneId>,
+  pub(crate) secret: String,
+  pub(crate) url: String,
+}
+
+impl WebhooksConfig {
+  pub(crate) fn load(path: &std::path::Path) -> Result<Self> {
+    let config: Self = serde_yaml::from_reader(File::open(path).context(anyhow!(
+      "failed to open webhooks config file `{}`",
+      path.display()
+    ))?)
+    .context(anyhow!(
+      "failed to deserialize webhooks config file `{}`",
+      path.display()
+    ))?;
+
+    let mut urls = HashSet::new();
+    for webhook in &config.webhooks {
+      ensure!(
+        urls.insert(webhook.url.as_str()),
+        "duplicate webhook url `{}`",
+        webhook.url
+      );
+    }
+
+    Ok(config)
+  }
+}
+
+/// Position of the last event handled by a webhook. Events after the cursor
+/// are delivered, so an event is only skipped once its delivery succeeds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct DeliveryCursor {
+  block_hash: Option<BlockHash>,
+  height: u32,
+  sequence_number: u32,
+  tx_index: u32,
+}
+
+impl DeliveryCursor {
+  fn new(index: &Index, (height, tx_index, sequence_number): EventKeyValue) -> Result<Self> {
+    Ok(Self {
+      block_hash: index.block_hash(Some(height))?,
+      height,
+      sequence_number,
+      tx_index,
+    })
+  }
+
+  /// Cursor positioned after every event in blocks below `height`.
+  fn before(index: &Index, height: u32) -> Result<Option<Self>> {
+    Index::event_cursor_before(height)
+      .map(|key| Self::new(index, key))
+      .transpose()
+  }
+
+  fn key(self) -> EventKeyValue {
+    (self.height, self.tx_index, self.sequence_number)
+  }
+}
+
+struct Webhook {
+  failures: u32,
+  filter: EventFilter,
+  retry_at: Option<Instant>,
+  secret: String,
+  url: Url,
+}
+
+pub(super) struct Dispatcher {
+  client: reqwest::blocking::Client,
+  cursors: BTreeMap<String, DeliveryCursor>,
+  cursors_path: PathBuf,
+  index: Arc<Index>,
+  initial_backoff: Duration,
+  reorg_rewind: u32,
+  webhooks: Vec<Webhook>,
+}
+
+impl Dispatcher {
+  pub(super) fn new(
+    index: Arc<Index>,
+    settings: &Settings,
+    config: WebhooksConfig,
+  ) -> Result<Self> {
+    ensure!(
+      index.has_event_index(),
+      "`--webhooks` requires index created with `--index-events` flag",
+    );
+
+    let cursors_path = settings.data_dir().join("webhook-cursors.json");
+
+    let mut cursors = if cursors_path.exists() {
+      serde_json::from_slice::<BTreeMap<String, DeliveryCursor>>(&fs::read(&cursors_path)?)
+        .context(anyhow!(
+          "failed to deserialize webhook cursors `{}`",
+          cursors_path.display()
+        ))?
+    } else {
+      BTreeMap::new()
+    };
+
+    let mut webhooks = Vec::new();
+
+    for webhook in config.webhooks {
+      let url = webhook
+        .url
+        .parse::<Url>()
+        .context(anyhow!("invalid webhook url `{}`", webhook.url))?;
+
+      if !cursors.contains_key(url.as_str()) {
+        let cursor = match webhook.from_height {
+          Some(height) => DeliveryCursor::before(&index, height)?,
+          None => index
+            .last_event_key()?
+            .map(|key| DeliveryCursor::new(&index, key))
+            .transpose()?,
+        };
+
+        if let Some(cursor) = cursor {
+          cursors.insert(url.to_string(), cursor);
+        }
+      }
+
+      webhooks.push(Webhook {
+        failures: 0,
+        filter: EventFilter {
+          from_height: webhook.from_height,
+          inscription: webhook.inscription,
+          kinds: webhook.kinds,
+          parent: webhook.parent,
+          rune: webhook.rune,
+          script_pubkey: webhook
+            .address
+            .map(|address| address.require_network(settings.chain().network()))
+            .transpose()?
+            .map(|address| address.script_pubkey()),
+        },
+        retry_at: None,
+        secret: webhook.secret,
+        url,
+      });
+    }
+
+    let dispatcher = Self {
+      client: reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?,
+      cursors,
+      cursors_path,
+      index,
+      initial_backoff: if settings.integration_test() {
+        Duration::from_millis(100)
+      } else {
+        Duration::from_secs(1)
+      },
+      // savepoints only allow the index to roll back this many blocks, so
+      // rewinding a cursor this far past a reorg is enough to redeliver every
+      // replaced event
+      reorg_rewind: settings
+        .savepoint_count()
+        .saturating_mul(settings.savepoint_interval()),
+      webhooks,
+    };
+
+    dispatcher.save_cursors()?;
+
+    Ok(dispatcher)
+  }
+
+  pub(super) fn run(mut self, polling_interval: Duration) {
+    while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      self.dispatch();
+
+      thread::sleep(polling_interval);
+    }
+  }
+
+  fn dispatch(&mut self) {
+    for i in 0..self.webhooks.len() {
+      if self.webhooks[i]
+        .retry_at
+        .is_some_and(|retry_at| retry_at > Instant::now())
+      {
+        continue;
+      }
+
+      // errors, for example from a webhook's filter, only delay that webhook,
+      // so that they can't keep later webhooks from receiving events
+      if let Err(err) = self.dispatch_webhook(i) {
+        let backoff = self.back_off(i);
+
+        log::warn!(
+          "Dispatching events to webhook `{}` failed, retrying in {}: {err}",
+          self.webhooks[i].url,
+          humantime::format_duration(backoff)
+        );
+      }
+    }
+  }
+
+  /// Records a failure of webhook `i` and returns how long to wait before
+  /// retrying it, which doubles with each consecutive failure.
+  fn back_off(&mut self, i: usize) -> Duration {
+    let webhook = &mut self.webhooks[i];
+
+    let backoff = self
+      .initial_backoff
+      .saturating_mul(2u32.saturating_pow(webhook.failures))
+      .min(MAX_BACKOFF);
+
+    webhook.failures = webhook.failures.saturating_add(1);
+    webhook.retry_at = Some(Instant::now() + backoff);
+
+    backoff
+  }
+
+  fn dispatch_webhook(&mut self, i: usize) -> Result {
+    let url = self.webhooks[i].url.to_string();
+
+    if let Some(cursor) = self.cursors.get(&url).copied() {
+      if cursor.block_hash.is_some()
+        && cursor.block_hash != self.index.block_hash(Some(cursor.height))?
+      {
+        log::warn!(
+          "Block {} was reorged, rewinding webhook `{url}` by {} blocks",
+          cursor.height,
+          self.reorg_rewind,
+        );
+
+        let height = cursor.height.saturating_sub(self.reorg_rewind);
+
+        match DeliveryCursor::before(&self.index, height)? {
+          Some(cursor) => self.cursors.insert(url.clone(), cursor),
+          None => self.cursors.remove(&url),
+        };
+
+        self.save_cursors()?;
+      }
+    }
+
+    loop {
+      let cursor = self.cursors.get(&url).map(|cursor| cursor.key());
+
+      let events = self.index.get_events_after(cursor, BATCH_SIZE)?;
+
+      if events.is_empty() {
+        return Ok(());
+      }
+
+      for journaled in events {
+        let webhook = &self.webhooks[i];
+
+        if webhook.filter.matches(&self.index, &journaled.event)? {
+          if let Err(err) = self.deliver(webhook, &journaled) {
+            let backoff = self.back_off(i);
+
+            log::warn!(
+              "Delivering event to webhook `{url}` failed, retrying in {}: {err}",
+              humantime::format_duration(backoff)
+            );
+
+            return self.save_cursors();
+          }
+
+          let webhook = &mut self.webhooks[i];
+          webhook.failures = 0;
+          webhook.retry_at = None;
+        }
+
+        self.cursors.insert(
+          url.clone(),
+          DeliveryCursor::new(
+            &self.index,
+            (
+              journaled.height,
+              journaled.tx_index,
+              journaled.sequence_number,
+            ),
+          )?,
+        );
+      }
+
+      self.save_cursors()?;
+    }
+  }
+
+  fn deliver(&self, webhook: &Webhook, journaled: &api::JournaledEvent) -> Result {
+    let body = serde_json::to_vec(journaled)?;
+
+    let response = self
+      .client
+      .post(webhook.url.clone())
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .header(
+        "X-Ord-Event-Id",
+        format!(
+          "{}:{}:{}",
+          journaled.height, journaled.tx_index, journaled.sequence_number
+        ),
+      )
+      .header(
+        "X-Ord-Signature",
+        format!("sha256={}", signature(&webhook.secret, &body)),
+      )
+      .body(body)
+      .send()?;
+
+    ensure!(
+      response.status().is_success(),
+      "webhook responded with {}",
+      response.status()
+    );
+
+    Ok(())
+  }
+
+  fn save_cursors(&self) -> Result {
+    let tmp = self.cursors_path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec(&self.cursors)?)?;
+    fs::rename(&tmp, &self.cursors_path)?;
+    Ok(())
+  }
+}
+
+pub(crate) fn signature(secret: &str, body: &[u8]) -> hmac::Hmac<sha256::Hash> {
+  let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+  engine.input(body);
+  hmac::Hmac::from_engine(engine)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  #[test]
+  fn signature_is_hmac_sha256() {
+    assert_eq!(
+      signature("key", b"The quick brown fox jumps over the lazy dog").to_string(),
+      "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+    );
+  }
+
+  #[test]
+  fn config_is_parsed() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("webhooks.yaml");
+
+    fs::write(
+      &path,
+      "webhooks:
+- url: http://localhost:8000/hook
+  secret: foo
+  kinds: [inscription_created, rune_minted]
+  parent: 6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0
+  rune: 1:2
+  from_height: 100
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      WebhooksConfig::load(&path).unwrap(),
+      WebhooksConfig {
+        webhooks: vec![WebhookConfig {
+          address: None,
+          from_height: Some(100),
+          inscription: None,
+          kinds: Some([EventKind::InscriptionCreated, EventKind::RuneMinted].into()),
+          parent: Some(
+            "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0"
+              .parse()
+              .unwrap()
+          ),
+          rune: Some(RuneId { block: 1, tx: 2 }),
+          secret: "foo".into(),
+          url: "http://localhost:8000/hook".into(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn webhook_errors_do_not_block_other_webhooks() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    let tempdir = TempDir::new().unwrap();
+    let cookie_file = tempdir.path().join("cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    let settings = Settings::from_options(
+      Options::try_parse_from([
+        "ord".as_ref(),
+        "--regtest".as_ref(),
+        "--index-events".as_ref(),
+        "--bitcoin-rpc-url".as_ref(),
+        core.url().as_ref(),
+        "--cookie-file".as_ref(),
+        cookie_file.as_os_str(),
+        "--datadir".as_ref(),
+        tempdir.path().as_os_str(),
+      ])
+      .unwrap(),
+    )
+    .or_defaults()
+    .unwrap();
+
+    let index = Arc::new(Index::open(&settings).unwrap());
+
+    core.mine_blocks(1);
+
+    core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    index.update().unwrap();
+
+    // address filters look up transactions in Core, so they fail once it's
+    // gone
+    drop(core);
+
+    let mut dispatcher = Dispatcher::new(
+      index.clone(),
+      &settings,
+      WebhooksConfig {
+        webhooks: vec![
+          WebhookConfig {
+            address: Some(
+              "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+                .parse()
+                .unwrap(),
+            ),
+            from_height: Some(0),
+            inscription: None,
+            kinds: None,
+            parent: None,
+            rune: None,
+            secret: "foo".into(),
+            url: "http://localhost/a".into(),
+          },
+          WebhookConfig {
+            address: None,
+            from_height: Some(0),
+            inscription: None,
+            kinds: Some([EventKind::RuneMinted].into()),
+            parent: None,
+            rune: None,
+            secret: "foo".into(),
+            url: "http://localhost/b".into(),
+          },
+        ],
+      },
+    )
+    .unwrap();
+
+    dispatcher.dispatch();
+
+    assert_eq!(dispatcher.webhooks[0].failures, 1);
+    assert!(dispatcher.webhooks[0].retry_at.is_some());
+
+    assert_eq!(dispatcher.webhooks[1].failures, 0);
+    assert_eq!(
+      dispatcher.cursors["http://localhost/b"].key(),
+      index.last_event_key().unwrap().unwrap(),
+    );
+  }
+
+  #[test]
+  fn example_config_is_valid() {
+    WebhooksConfig::load("webhooks.yaml".as_ref()).unwrap();
+  }
+
+  #[test]
+  fn config_rejects_unknown_fields() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("webhooks.yaml");
+
+    fs::write(
+      &path,
+      "webhooks:\n- url: http://localhost/\n  secret: foo\n  bar: baz\n",
+    )
+    .unwrap();
+
+    assert!(WebhooksConfig::load(&path).is_err());
+  }
+
+  #[test]
+  fn config_rejects_duplicate_urls() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("webhooks.yaml");
+
+    fs::write(
+      &path,
+      "webhooks:
+- url: http://localhost/
+  secret: foo
+- url: http://localhost/
+  secret: bar
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      WebhooksConfig::load(&path).unwrap_err().to_string(),
+      "duplicate webhook url `http://localhost/`",
+    );
+  }
+}
@@ -7,14 +7,20 @@ use {
 pub mod addresses;
 pub mod balance;
 mod batch_command;
+pub mod broadcast;
+pub mod bump;
 pub mod burn;
+pub mod cancel;
 pub mod cardinals;
+pub mod consolidate;
 pub mod create;
 pub mod dump;
+pub mod extract;
 pub mod inscribe;
 pub mod inscriptions;
 mod label;
 pub mod mint;
+pub mod offer;
 pub mod outputs;
 pub mod pending;
 pub mod receive;
@@ -23,6 +29,7 @@ pub mod resume;
 pub mod runics;
 pub mod sats;
 pub mod send;
+pub mod send_many;
 mod shared_args;
 pub mod sign;
 pub mod split;
@@ -52,14 +59,24 @@ pub(crate) enum Subcommand {
   Balance,
   #[command(about = "Create inscriptions and runes")]
   Batch(batch_command::Batch),
+  #[command(about = "Broadcast an externally signed PSBT")]
+  Broadcast(broadcast::Broadcast),
+  #[command(about = "Bump fee of an unconfirmed transaction")]
+  Bump(bump::Bump),
   #[command(about = "Burn an inscription")]
   Burn(burn::Burn),
+  #[command(about = "Cancel an unconfirmed transaction")]
+  Cancel(cancel::Cancel),
   #[command(about = "List unspent cardinal outputs in wallet")]
   Cardinals,
+  #[command(about = "Consolidate small cardinal outputs and runic outputs")]
+  Consolidate(consolidate::Consolidate),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Dump wallet descriptors")]
   Dump,
+  #[command(about = "Move inscriptions sharing an output to separate outputs")]
+  Extract(extract::Extract),
   #[command(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
@@ -68,6 +85,8 @@ pub(crate) enum Subcommand {
   Label,
   #[command(about = "Mint a rune")]
   Mint(mint::Mint),
+  #[command(subcommand, about = "Create, inspect, and accept offers")]
+  Offer(offer::Offer),
   #[command(about = "List all unspent outputs in wallet")]
   Outputs(outputs::Outputs),
   #[command(about = "List pending etchings")]
@@ -84,6 +103,8 @@ pub(crate) enum Subcommand {
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
+  #[command(about = "Send to many recipients")]
+  SendMany(send_many::SendMany),
   #[command(about = "Sign message")]
   Sign(sign::Sign),
   #[command(about = "Split outputs")]
@@ -114,18 +135,38 @@ impl WalletCommand {
         .context("invalid server URL")?,
     )?;
 
+    if wallet.watch_only() {
+      if let Subcommand::Batch(_)
+      | Subcommand::Inscribe(_)
+      | Subcommand::Mint(_)
+      | Subcommand::Resume(_)
+      | Subcommand::Sign(_) = self.subcommand
+      {
+        bail!(
+          "wallet `{}` is watch-only, and this command requires private keys",
+          self.name
+        );
+      }
+    }
+
     match self.subcommand {
       Subcommand::Addresses => addresses::run(wallet),
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Batch(batch) => batch.run(wallet),
+      Subcommand::Broadcast(broadcast) => broadcast.run(wallet),
+      Subcommand::Bump(bump) => bump.run(wallet),
       Subcommand::Burn(burn) => burn.run(wallet),
+      Subcommand::Cancel(cancel) => cancel.run(wallet),
       Subcommand::Cardinals => cardinals::run(wallet),
+      Subcommand::Consolidate(consolidate) => consolidate.run(wallet),
       Subcommand::Create(_) | Subcommand::Restore(_) => unreachable!(),
       Subcommand::Dump => dump::run(wallet),
+      Subcommand::Extract(extract) => extract.run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
       Subcommand::Label => label::run(wallet),
       Subcommand::Mint(mint) => mint.run(wallet),
+      Subcommand::Offer(offer) => offer.run(wallet),
       Subcommand::Outputs(outputs) => outputs.run(wallet),
       Subcommand::Pending(pending) => pending.run(wallet),
       Subcommand::Receive(receive) => receive.run(wallet),
@@ -133,6 +174,7 @@ impl WalletCommand {
       Subcommand::Runics => runics::run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
+      Subcommand::SendMany(send_many) => send_many.run(wallet),
       Subcommand::Sign(sign) => sign.run(wallet),
       Subcommand::Split(split) => split.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
@@ -126,7 +126,7 @@ impl Batch {
 
     let reveal_height = current_height + u32::from(Runestone::COMMIT_CONFIRMATIONS);
 
-    let first_rune_height = Rune::first_rune_height(wallet.chain().into());
+    let first_rune_height = wallet.chain().first_rune_height();
 
     ensure!(
       reveal_height >= first_rune_height,
@@ -170,7 +170,7 @@ impl Batch {
       );
     }
 
-    let minimum = Rune::minimum_at_height(wallet.chain().into(), Height(reveal_height));
+    let minimum = wallet.chain().minimum_rune_at_height(Height(reveal_height));
 
     ensure!(
       rune >= minimum,
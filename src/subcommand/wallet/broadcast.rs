@@ -0,0 +1,54 @@
+use {
+  super::*,
+  base64::{engine::general_purpose, Engine},
+  bitcoin::psbt::Psbt,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Broadcast {
+  #[arg(
+    long,
+    help = "Allow up to <AMOUNT> to be burned by OP_RETURN outputs. [default: 0 sat]",
+    value_name = "AMOUNT"
+  )]
+  burn_amount: Option<Amount>,
+  #[arg(long, help = "Finalize and broadcast signed <PSBT>.")]
+  psbt: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+}
+
+impl Broadcast {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let psbt = Psbt::deserialize(
+      &general_purpose::STANDARD
+        .decode(&self.psbt)
+        .context("PSBT is not valid base64")?,
+    )
+    .context("PSBT is not valid")?;
+
+    let burned = psbt
+      .unsigned_tx
+      .output
+      .iter()
+      .filter(|tx_out| tx_out.script_pubkey.is_op_return())
+      .map(|tx_out| tx_out.value)
+      .sum::<Amount>();
+
+    let burn_amount = self.burn_amount.unwrap_or(Amount::ZERO);
+
+    ensure!(
+      burned <= burn_amount,
+      "PSBT burns {} sat to OP_RETURN outputs, more than `--burn-amount` of {} sat",
+      burned.to_sat(),
+      burn_amount.to_sat(),
+    );
+
+    let txid = wallet.finalize_and_broadcast(&self.psbt, self.burn_amount)?;
+
+    Ok(Some(Box::new(Output { txid })))
+  }
+}
@@ -0,0 +1,260 @@
+use {super::*, bitcoincore_rpc::json::GetMempoolEntryResult};
+
+/// Bitcoin Core's default `-incrementalrelayfee`, which a replacement must pay
+/// on top of the fee of the transaction it replaces.
+pub(super) const INCREMENTAL_RELAY_FEE_RATE: u64 = 1;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Bump {
+  #[arg(
+    long,
+    help = "Spend a wallet output of the transaction in a child transaction instead of replacing it"
+  )]
+  cpfp: bool,
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Bump fee of unconfirmed transaction <TXID>")]
+  txid: Txid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+impl Bump {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let entry = wallet
+      .bitcoin_client()
+      .get_mempool_entry(&self.txid)
+      .map_err(|_| anyhow!("transaction {} is not in the mempool", self.txid))?;
+
+    let transaction = wallet
+      .bitcoin_client()
+      .get_raw_transaction(&self.txid, None)?;
+
+    let (unsigned_transaction, fee) = if self.cpfp {
+      self.create_child_transaction(&wallet, &transaction, &entry)?
+    } else {
+      self.create_replacement_transaction(&wallet, &transaction, &entry)?
+    };
+
+    let (txid, psbt) = wallet.sign_and_broadcast(unsigned_transaction, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee: fee.to_sat(),
+    })))
+  }
+
+  /// Builds a replacement which keeps every input and output of the original
+  /// transaction in place, and pays the higher fee from cardinal inputs and a
+  /// change output appended to the end. Since the original outputs still
+  /// receive exactly the same sats, inscription satpoints, postage, and rune
+  /// edicts are unaffected.
+  fn create_replacement_transaction(
+    &self,
+    wallet: &Wallet,
+    transaction: &Transaction,
+    entry: &GetMempoolEntryResult,
+  ) -> Result<(Transaction, Amount)> {
+    ensure!(
+      transaction.is_explicitly_rbf(),
+      "transaction {} does not signal replaceability, use `--cpfp` to bump it instead",
+      self.txid,
+    );
+
+    ensure!(
+      entry.spent_by.is_empty(),
+      "transaction {} has unconfirmed children which a replacement would evict, use `--cpfp` to \
+      bump it instead",
+      self.txid,
+    );
+
+    if let Some(Artifact::Runestone(runestone)) = Runestone::decipher(transaction) {
+      let outputs = u32::try_from(transaction.output.len())?;
+
+      ensure!(
+        runestone.edicts.iter().all(|edict| edict.output != outputs),
+        "transaction {} splits runes across all outputs, which an additional change output would \
+        alter, use `--cpfp` to bump it instead",
+        self.txid,
+      );
+    }
+
+    let current_fee = entry.fees.base;
+
+    Self::ensure_fee_rate_exceeds(self.fee_rate, current_fee, entry.vsize, self.txid)?;
+
+    let input_value = current_fee
+      + transaction
+        .output
+        .iter()
+        .map(|tx_out| tx_out.value)
+        .sum::<Amount>();
+
+    wallet.fund_transaction(
+      transaction.clone(),
+      input_value,
+      |vsize| {
+        self.fee_rate.fee(vsize).max(
+          current_fee
+            + Amount::from_sat(INCREMENTAL_RELAY_FEE_RATE * u64::try_from(vsize).unwrap()),
+        )
+      },
+      &Self::spent_outputs(transaction),
+    )
+  }
+
+  /// Builds a child which spends a wallet output of the stuck transaction,
+  /// paying enough that the package reaches the target fee rate. If the
+  /// spent output may carry inscriptions or runes, it is passed through
+  /// unchanged to a new wallet address as the first output of the child.
+  fn create_child_transaction(
+    &self,
+    wallet: &Wallet,
+    transaction: &Transaction,
+    entry: &GetMempoolEntryResult,
+  ) -> Result<(Transaction, Amount)> {
+    Self::ensure_fee_rate_exceeds(
+      self.fee_rate,
+      entry.fees.ancestor,
+      entry.ancestor_size,
+      self.txid,
+    )?;
+
+    let mut spent = BTreeSet::new();
+
+    for child in &entry.spent_by {
+      for txin in wallet
+        .bitcoin_client()
+        .get_raw_transaction(child, None)?
+        .input
+      {
+        spent.insert(txin.previous_output);
+      }
+    }
+
+    let mut output = None;
+
+    for (vout, tx_out) in transaction.output.iter().enumerate().rev() {
+      let outpoint = OutPoint {
+        txid: self.txid,
+        vout: vout.try_into()?,
+      };
+
+      if spent.contains(&outpoint) {
+        continue;
+      }
+
+      let Ok(address) = wallet.chain().address_from_script(&tx_out.script_pubkey) else {
+        continue;
+      };
+
+      if wallet
+        .bitcoin_client()
+        .get_address_info(&address)?
+        .is_mine
+        .unwrap_or_default()
+      {
+        output = Some((outpoint, tx_out.clone()));
+        break;
+      }
+    }
+
+    let Some((outpoint, tx_out)) = output else {
+      bail!(
+        "transaction {} has no unspent wallet outputs to spend",
+        self.txid
+      );
+    };
+
+    let pass_through = Self::may_carry_ordinals(wallet, transaction)?;
+
+    let child = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::from_slice(&[&[0; 64]]),
+      }],
+      output: if pass_through {
+        vec![TxOut {
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+          value: tx_out.value,
+        }]
+      } else {
+        Vec::new()
+      },
+    };
+
+    wallet.fund_transaction(
+      child,
+      tx_out.value,
+      |vsize| {
+        self
+          .fee_rate
+          .fee(usize::try_from(entry.ancestor_size).unwrap() + vsize)
+          .checked_sub(entry.fees.ancestor)
+          .unwrap_or_default()
+          .max(Amount::from_sat(u64::try_from(vsize).unwrap()))
+      },
+      &Self::spent_outputs(transaction),
+    )
+  }
+
+  pub(super) fn ensure_fee_rate_exceeds(
+    fee_rate: FeeRate,
+    fee: Amount,
+    vsize: u64,
+    txid: Txid,
+  ) -> Result {
+    #[allow(clippy::cast_precision_loss)]
+    let current = fee.to_sat() as f64 / vsize as f64;
+
+    ensure!(
+      fee_rate.n() > current,
+      "fee rate must be greater than current fee rate of transaction {txid}: {current:.2} sat/vB",
+    );
+
+    Ok(())
+  }
+
+  /// Returns true unless all inputs of `transaction` are known to the index
+  /// and hold neither inscriptions nor runes, and the transaction neither
+  /// inscribes nor carries a runestone.
+  fn may_carry_ordinals(wallet: &Wallet, transaction: &Transaction) -> Result<bool> {
+    if Runestone::decipher(transaction).is_some()
+      || !ParsedEnvelope::from_transaction(transaction).is_empty()
+    {
+      return Ok(true);
+    }
+
+    let inputs = transaction
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>();
+
+    Ok(
+      wallet.get_output_info(&inputs)?.iter().any(|output| {
+        !output.indexed || !output.inscriptions.is_empty() || !output.runes.is_empty()
+      }),
+    )
+  }
+
+  pub(super) fn spent_outputs(transaction: &Transaction) -> BTreeSet<OutPoint> {
+    transaction
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect()
+  }
+}
@@ -0,0 +1,149 @@
+use {
+  super::*,
+  bitcoincore_rpc::json::GetMempoolEntryResult,
+  bump::{Bump, INCREMENTAL_RELAY_FEE_RATE},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Cancel {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Cancel unconfirmed transaction <TXID>")]
+  txid: Txid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+impl Cancel {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let entry = wallet
+      .bitcoin_client()
+      .get_mempool_entry(&self.txid)
+      .map_err(|_| anyhow!("transaction {} is not in the mempool", self.txid))?;
+
+    let transaction = wallet
+      .bitcoin_client()
+      .get_raw_transaction(&self.txid, None)?;
+
+    ensure!(
+      transaction.is_explicitly_rbf(),
+      "transaction {} does not signal replaceability and cannot be canceled, wait for it to \
+      confirm or use `ord wallet bump --cpfp` to speed it up",
+      self.txid,
+    );
+
+    Bump::ensure_fee_rate_exceeds(self.fee_rate, entry.fees.base, entry.vsize, self.txid)?;
+
+    let (unsigned_transaction, fee) =
+      self.create_cancel_transaction(&wallet, &transaction, &entry)?;
+
+    let (txid, psbt) = wallet.sign_and_broadcast(unsigned_transaction, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee: fee.to_sat(),
+    })))
+  }
+
+  /// Builds a transaction which spends every input of the stuck transaction
+  /// back to the wallet. Inputs holding runes come first, followed by inputs
+  /// holding inscriptions, and each one's sats are returned to a new wallet
+  /// output of the same value, so inscriptions keep their offsets and
+  /// postage. The cancel transaction has no runestone, so every rune from
+  /// every input is allocated to the first output, which belongs to a runic
+  /// input. The remaining cardinal inputs pay the fee, with the rest going to
+  /// a single change output.
+  fn create_cancel_transaction(
+    &self,
+    wallet: &Wallet,
+    transaction: &Transaction,
+    entry: &GetMempoolEntryResult,
+  ) -> Result<(Transaction, Amount)> {
+    let inputs = transaction
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>();
+
+    let mut runic = Vec::new();
+    let mut inscribed = Vec::new();
+    let mut cardinal = Vec::new();
+
+    let mut input_value = Amount::ZERO;
+
+    for (txin, output) in transaction
+      .input
+      .iter()
+      .zip(wallet.get_output_info(&inputs)?)
+    {
+      let value = Amount::from_sat(output.value);
+
+      input_value += value;
+
+      if !output.indexed {
+        bail!(
+          "input {} of transaction {} is not indexed by the ord server",
+          txin.previous_output,
+          self.txid,
+        );
+      } else if !output.runes.is_empty() {
+        runic.push((txin.clone(), value));
+      } else if !output.inscriptions.is_empty() {
+        inscribed.push((txin.clone(), value));
+      } else {
+        cardinal.push((txin.clone(), value));
+      }
+    }
+
+    let mut cancel = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    for (txin, value) in runic.into_iter().chain(inscribed) {
+      cancel.input.push(TxIn {
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        ..txin
+      });
+
+      cancel.output.push(TxOut {
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        value,
+      });
+    }
+
+    for (txin, _) in cardinal {
+      cancel.input.push(TxIn {
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        ..txin
+      });
+    }
+
+    // A replacement must pay for all transactions it evicts, which includes
+    // any descendants of the stuck transaction, plus the incremental relay fee
+    // for its own size.
+    let evicted_fee = entry.fees.descendant;
+
+    wallet.fund_transaction(
+      cancel,
+      input_value,
+      |vsize| {
+        self.fee_rate.fee(vsize).max(
+          evicted_fee
+            + Amount::from_sat(INCREMENTAL_RELAY_FEE_RATE * u64::try_from(vsize).unwrap()),
+        )
+      },
+      &Bump::spent_outputs(transaction),
+    )
+  }
+}
@@ -0,0 +1,205 @@
+use {super::*, split::Split};
+
+#[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("consolidate")
+    .required(true)
+    .multiple(true)
+    .args(&["threshold", "runes"])))
+]
+pub(crate) struct Consolidate {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    default_value_t = 500,
+    help = "Spend at most <MAX_INPUTS> consolidated outputs."
+  )]
+  max_inputs: usize,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with each consolidated runic output. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Merge runic outputs holding only the same rune into a single output per rune."
+  )]
+  runes: bool,
+  #[arg(
+    long,
+    help = "Merge cardinal outputs with value below <THRESHOLD> into a single output.",
+    value_name = "THRESHOLD"
+  )]
+  threshold: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+impl Consolidate {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      !self.runes || wallet.has_rune_index(),
+      "consolidating runes with `--runes` requires index created with `--index-runes`",
+    );
+
+    ensure!(self.max_inputs >= 2, "`--max-inputs` must be at least 2");
+
+    let mut inputs = Vec::new();
+    let mut runestone = Runestone::default();
+    let mut outputs = Vec::new();
+
+    if self.runes {
+      let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+      let change = wallet.get_change_address()?.script_pubkey();
+
+      ensure!(
+        postage >= change.minimal_non_dust(),
+        "postage value {postage} below dust threshold {}",
+        change.minimal_non_dust(),
+      );
+
+      for (rune, group) in Self::runic_groups(&wallet)? {
+        let budget = self.max_inputs - inputs.len();
+
+        if budget < 2 {
+          break;
+        }
+
+        let (id, _entry, _parent) = wallet
+          .get_rune(rune)?
+          .with_context(|| format!("rune `{rune}` has not been etched"))?;
+
+        let mut edicts = runestone.edicts.clone();
+
+        edicts.push(Edict {
+          id,
+          amount: 0,
+          output: (outputs.len() + 1).try_into().unwrap(),
+        });
+
+        if (Runestone {
+          edicts: edicts.clone(),
+          ..default()
+        })
+        .encipher()
+        .len()
+          > MAX_STANDARD_OP_RETURN_SIZE
+        {
+          break;
+        }
+
+        runestone.edicts = edicts;
+
+        inputs.extend(group.into_iter().take(budget));
+
+        outputs.push(TxOut {
+          script_pubkey: change.clone(),
+          value: postage,
+        });
+      }
+    }
+
+    if let Some(threshold) = self.threshold {
+      let runic = wallet.get_runic_outputs()?;
+
+      let inscribed = wallet
+        .inscriptions()
+        .keys()
+        .map(|satpoint| satpoint.outpoint)
+        .collect::<BTreeSet<OutPoint>>();
+
+      let mut cardinals = wallet
+        .utxos()
+        .iter()
+        .filter(|(outpoint, tx_out)| {
+          tx_out.value < threshold && !inscribed.contains(outpoint) && !runic.contains(outpoint)
+        })
+        .collect::<Vec<(&OutPoint, &TxOut)>>();
+
+      cardinals.sort_by_key(|(outpoint, tx_out)| (tx_out.value, **outpoint));
+
+      inputs.extend(
+        cardinals
+          .into_iter()
+          .take(self.max_inputs - inputs.len())
+          .map(|(outpoint, _)| *outpoint),
+      );
+    }
+
+    ensure!(
+      inputs.len() >= 2,
+      "wallet contains fewer than two outputs to consolidate",
+    );
+
+    if !runestone.edicts.is_empty() {
+      outputs.insert(
+        0,
+        TxOut {
+          script_pubkey: runestone.encipher(),
+          value: Amount::ZERO,
+        },
+      );
+    }
+
+    let input_value = inputs
+      .iter()
+      .map(|outpoint| wallet.utxos()[outpoint].value)
+      .sum::<Amount>();
+
+    let (unsigned_transaction, _fee) = wallet.fund_transaction(
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: inputs
+          .iter()
+          .map(|outpoint| TxIn {
+            previous_output: *outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output: outputs,
+      },
+      input_value,
+      |vsize| self.fee_rate.fee(vsize),
+      &inputs.iter().copied().collect(),
+    )?;
+
+    let (txid, psbt, fee) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output { txid, psbt, fee })))
+  }
+
+  /// Groups uninscribed runic outputs which hold a single rune by that rune,
+  /// keeping only groups of at least two outputs, which can be merged.
+  fn runic_groups(wallet: &Wallet) -> Result<BTreeMap<Rune, Vec<OutPoint>>> {
+    let mut groups = BTreeMap::<Rune, Vec<OutPoint>>::new();
+
+    for (outpoint, balances) in Split::rune_balances(wallet)? {
+      if let [(rune, _)] = balances
+        .into_iter()
+        .collect::<Vec<(Rune, u128)>>()
+        .as_slice()
+      {
+        groups.entry(*rune).or_default().push(outpoint);
+      }
+    }
+
+    groups.retain(|_, group| group.len() >= 2);
+
+    Ok(groups)
+  }
+}
@@ -0,0 +1,247 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Extract {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Extract inscriptions from <OUTPUT>.")]
+  output: OutPoint,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with each extracted inscription. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub inscriptions: BTreeMap<InscriptionId, SatPoint>,
+}
+
+impl Extract {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let Some(tx_out) = wallet.utxos().get(&self.output) else {
+      bail!("output {} not in wallet", self.output);
+    };
+
+    ensure!(
+      wallet
+        .get_runes_balances_in_output(&self.output)?
+        .is_empty(),
+      "output {} holds runes, which cannot be extracted",
+      self.output,
+    );
+
+    let mut offsets = BTreeMap::<u64, Vec<InscriptionId>>::new();
+
+    for id in wallet.get_inscriptions_in_output(&self.output) {
+      offsets
+        .entry(Self::offset(&wallet, id)?)
+        .or_default()
+        .push(id);
+    }
+
+    ensure!(
+      !offsets.is_empty(),
+      "output {} holds no inscriptions",
+      self.output,
+    );
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    let dust_limit = change.minimal_non_dust();
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    ensure!(
+      postage >= dust_limit,
+      "postage value {postage} below dust threshold {dust_limit}",
+    );
+
+    let layout = Self::layout(&offsets, postage.to_sat(), dust_limit.to_sat())?;
+
+    let (unsigned_transaction, _fee) = wallet.fund_transaction(
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: self.output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        }],
+        output: layout
+          .iter()
+          .map(|(value, _inscriptions)| TxOut {
+            script_pubkey: change.clone(),
+            value: Amount::from_sat(*value),
+          })
+          .collect(),
+      },
+      tx_out.value,
+      |vsize| self.fee_rate.fee(vsize),
+      &[self.output].into(),
+    )?;
+
+    let (txid, psbt, fee) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+
+    let mut inscriptions = BTreeMap::new();
+
+    let mut start = 0;
+
+    for (vout, (value, _inscriptions)) in layout.into_iter().enumerate() {
+      for (offset, ids) in offsets.range(start..start + value) {
+        for id in ids {
+          inscriptions.insert(
+            *id,
+            SatPoint {
+              outpoint: OutPoint {
+                txid,
+                vout: vout.try_into().unwrap(),
+              },
+              offset: offset - start,
+            },
+          );
+        }
+      }
+
+      start += value;
+    }
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee,
+      inscriptions,
+    })))
+  }
+
+  /// Returns the offset of `inscription` within its output, computed from the
+  /// output's sat ranges if the index tracks sats, and from the inscription's
+  /// satpoint otherwise.
+  fn offset(wallet: &Wallet, inscription: InscriptionId) -> Result<u64> {
+    let info = wallet
+      .inscription_info()
+      .get(&inscription)
+      .cloned()
+      .ok_or_else(|| anyhow!("inscription {inscription} not in wallet"))?;
+
+    if let (true, Some(sat)) = (wallet.has_sat_index(), info.sat) {
+      let satpoint = wallet.find_sat_in_outputs(sat)?;
+
+      ensure!(
+        satpoint == info.satpoint,
+        "index reports inscription {inscription} at {} but its sat {sat} at {satpoint}",
+        info.satpoint,
+      );
+    }
+
+    Ok(info.satpoint.offset)
+  }
+
+  /// Returns the value of each output of a transaction which isolates the
+  /// items at each of `offsets`, like inscriptions or sats, and the items it
+  /// receives. Sats before each item go to a cardinal output if they would
+  /// not be dust, and are otherwise added to the item's output. Item outputs
+  /// hold `postage` sats after their item, but end before the next item. The
+  /// remaining sats are left for change.
+  pub(super) fn layout<T: Clone + Display>(
+    offsets: &BTreeMap<u64, Vec<T>>,
+    postage: u64,
+    dust_limit: u64,
+  ) -> Result<Vec<(u64, Vec<T>)>> {
+    let mut outputs = Vec::new();
+
+    let mut start = 0;
+
+    let mut offsets = offsets.iter().peekable();
+
+    while let Some((&offset, items)) = offsets.next() {
+      if offset - start >= dust_limit {
+        outputs.push((offset - start, Vec::new()));
+        start = offset;
+      }
+
+      let end = match offsets.peek() {
+        Some((&next, next_items)) => {
+          ensure!(
+            next - offset >= dust_limit,
+            "{} and {} are less than {dust_limit} sats apart and cannot be separated",
+            items[0],
+            next_items[0],
+          );
+
+          next.min(offset + postage)
+        }
+        None => offset + postage,
+      };
+
+      outputs.push((end - start, items.clone()));
+
+      start = end;
+    }
+
+    Ok(outputs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn layout() {
+    #[track_caller]
+    fn case(offsets: &[(u64, u32)], expected: &[(u64, &[u32])]) {
+      let mut map = BTreeMap::<u64, Vec<InscriptionId>>::new();
+
+      for (offset, n) in offsets {
+        map.entry(*offset).or_default().push(inscription_id(*n));
+      }
+
+      assert_eq!(
+        Extract::layout(&map, 10_000, 330).unwrap(),
+        expected
+          .iter()
+          .map(|(value, ns)| (*value, ns.iter().map(|n| inscription_id(*n)).collect()))
+          .collect::<Vec<(u64, Vec<InscriptionId>)>>(),
+      );
+    }
+
+    case(&[(0, 1)], &[(10_000, &[1])]);
+    case(&[(0, 1), (0, 2)], &[(10_000, &[1, 2])]);
+    case(&[(0, 1), (10_000, 2)], &[(10_000, &[1]), (10_000, &[2])]);
+    case(&[(0, 1), (5_000, 2)], &[(5_000, &[1]), (10_000, &[2])]);
+    case(&[(100, 1)], &[(10_100, &[1])]);
+    case(
+      &[(100, 1), (50_000, 2)],
+      &[(10_100, &[1]), (39_900, &[]), (10_000, &[2])],
+    );
+    case(&[(50_000, 1)], &[(50_000, &[]), (10_000, &[1])]);
+  }
+
+  #[test]
+  fn layout_requires_separable_inscriptions() {
+    assert_eq!(
+      Extract::layout(
+        &[(0, vec![inscription_id(1)]), (100, vec![inscription_id(2)])].into(),
+        10_000,
+        330,
+      )
+      .unwrap_err()
+      .to_string(),
+      format!(
+        "{} and {} are less than 330 sats apart and cannot be separated",
+        inscription_id(1),
+        inscription_id(2),
+      ),
+    );
+  }
+}
@@ -14,6 +14,7 @@ pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
     Chain::Regtest => "http://localhost/inscription/",
     Chain::Signet => "https://signet.ordinals.com/inscription/",
     Chain::Testnet => "https://testnet.ordinals.com/inscription/",
+    Chain::Testnet4 => "https://testnet4.ordinals.com/inscription/",
   };
 
   let mut output = Vec::new();
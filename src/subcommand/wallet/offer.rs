@@ -0,0 +1,87 @@
+use {
+  super::*,
+  base64::{engine::general_purpose, Engine},
+  bitcoin::{psbt::Psbt, sighash::TapSighashType},
+};
+
+pub mod accept;
+pub mod create;
+pub mod inspect;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Offer {
+  #[command(about = "Buy what an offer sells by funding and broadcasting it")]
+  Accept(accept::Accept),
+  #[command(about = "Create an offer selling an inscription or runes")]
+  Create(create::Create),
+  #[command(about = "Show what an offer sells and at what price")]
+  Inspect(inspect::Inspect),
+}
+
+impl Offer {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    match self {
+      Self::Accept(accept) => accept.run(wallet),
+      Self::Create(create) => create.run(wallet),
+      Self::Inspect(inspect) => inspect.run(wallet),
+    }
+  }
+
+  fn decode(psbt: &str) -> Result<Psbt> {
+    Psbt::deserialize(
+      &general_purpose::STANDARD
+        .decode(psbt)
+        .context("offer is not valid base64")?,
+    )
+    .context("offer is not a valid PSBT")
+  }
+
+  fn encode(psbt: &Psbt) -> String {
+    general_purpose::STANDARD.encode(psbt.serialize())
+  }
+
+  /// Checks that `psbt` spends a single output to a single payment output,
+  /// and that the seller has signed its input with
+  /// `SIGHASH_SINGLE|SIGHASH_ANYONECANPAY`, so that a buyer can add inputs
+  /// and outputs without invalidating the signature, as long as the input
+  /// and payment output keep matching indices.
+  fn check(psbt: &Psbt) -> Result {
+    ensure!(
+      psbt.unsigned_tx.input.len() == 1 && psbt.unsigned_tx.output.len() == 1,
+      "offer must have exactly one input and one output",
+    );
+
+    let Some(witness) = &psbt.inputs[0].final_script_witness else {
+      bail!("offer input is not signed");
+    };
+
+    ensure!(
+      witness.len() == 1
+        && witness.nth(0).is_some_and(|signature| {
+          signature.len() == 65 && signature[64] == TapSighashType::SinglePlusAnyoneCanPay as u8
+        }),
+      "offer input must be a taproot key path spend signed with \
+      SIGHASH_SINGLE|SIGHASH_ANYONECANPAY",
+    );
+
+    Ok(())
+  }
+}
+
+enum Asset {
+  Inscription(InscriptionId),
+  Rune { decimal: Decimal, rune: SpacedRune },
+}
+
+impl Asset {
+  fn new(inscription: Option<InscriptionId>, rune: Option<Outgoing>) -> Result<Self> {
+    match (inscription, rune) {
+      (Some(inscription), None) => Ok(Self::Inscription(inscription)),
+      (None, Some(Outgoing::Rune { decimal, rune })) => Ok(Self::Rune { decimal, rune }),
+      (None, Some(outgoing)) => {
+        bail!("`--rune` must be of the form DECIMAL:RUNE, not `{outgoing}`")
+      }
+      _ => unreachable!(),
+    }
+  }
+}
@@ -0,0 +1,164 @@
+use {super::*, bitcoincore_rpc::json::AddressType};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+#[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("asset")
+    .required(true)
+    .args(&["inscription", "rune"])))
+]
+pub(crate) struct Accept {
+  #[arg(long, help = "Accept offer only if its price is <AMOUNT>.")]
+  amount: Amount,
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Accept offer only if it sells exactly <INSCRIPTION>.")]
+  inscription: Option<InscriptionId>,
+  #[arg(long, help = "Accept offer <PSBT>.")]
+  psbt: String,
+  #[arg(
+    long,
+    help = "Accept offer only if it sells exactly <DECIMAL:RUNE>.",
+    value_name = "DECIMAL:RUNE"
+  )]
+  rune: Option<Outgoing>,
+}
+
+impl Accept {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let offer = Offer::decode(&self.psbt)?;
+
+    let inspection = inspect::Inspect::inspect(&wallet, &offer)?;
+
+    let outpoint = inspection.outpoint;
+
+    ensure!(
+      !wallet.utxos().contains_key(&outpoint),
+      "offer output {outpoint} is already in wallet",
+    );
+
+    ensure!(
+      inspection.price == self.amount.to_sat(),
+      "offer price {} does not match expected price {}",
+      Amount::from_sat(inspection.price),
+      self.amount,
+    );
+
+    match Asset::new(self.inscription, self.rune.clone())? {
+      Asset::Inscription(inscription) => {
+        ensure!(
+          inspection.inscriptions == [inscription],
+          "offer output {outpoint} does not hold exactly inscription {inscription}",
+        );
+
+        ensure!(
+          inspection.runes.is_empty(),
+          "offer output {outpoint} also holds runes",
+        );
+      }
+      Asset::Rune { decimal, rune } => {
+        ensure!(
+          inspection.inscriptions.is_empty(),
+          "offer output {outpoint} also holds inscriptions",
+        );
+
+        ensure!(
+          inspection.runes.len() == 1
+            && inspection.runes.iter().all(|(spaced_rune, offered)| {
+              spaced_rune.rune == rune.rune
+                && decimal.to_integer(offered.scale).ok() == Some(offered.value)
+            }),
+          "offer output {outpoint} does not hold exactly {decimal}:{rune}",
+        );
+      }
+    }
+
+    let (unsigned_transaction, fee) = self.create_transaction(&wallet, &offer, &inspection)?;
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+    psbt.inputs[1] = offer.inputs[0].clone();
+
+    let (txid, psbt) = wallet.sign_and_broadcast_psbt(psbt, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee: fee.to_sat(),
+    })))
+  }
+
+  /// Builds the purchase transaction. The seller signed its input and
+  /// payment output with `SIGHASH_SINGLE|SIGHASH_ANYONECANPAY`, which for
+  /// taproot commits to neither's index, only that they match. The seller's
+  /// input and payment output are placed second, after a cardinal input of
+  /// the buyer's, so that the sold inscriptions or runes go to the first
+  /// output, which pays the buyer the value of both inputs.
+  fn create_transaction(
+    &self,
+    wallet: &Wallet,
+    offer: &Psbt,
+    inspection: &inspect::Output,
+  ) -> Result<(Transaction, Amount)> {
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?;
+
+    let Some((cardinal, cardinal_output)) = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _)| !inscribed.contains(outpoint) && !runic.contains(outpoint))
+      .min_by_key(|(_, tx_out)| tx_out.value)
+    else {
+      bail!("wallet contains no cardinal utxos");
+    };
+
+    let input_value = cardinal_output.value + Amount::from_sat(inspection.value);
+
+    let buyer = wallet
+      .bitcoin_client()
+      .get_new_address(None, Some(AddressType::Bech32m))?
+      .require_network(wallet.chain().network())?;
+
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: *cardinal,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+        offer.unsigned_tx.input[0].clone(),
+      ],
+      output: vec![
+        TxOut {
+          script_pubkey: buyer.script_pubkey(),
+          value: input_value,
+        },
+        offer.unsigned_tx.output[0].clone(),
+      ],
+    };
+
+    wallet.fund_transaction(
+      transaction,
+      input_value,
+      |vsize| self.fee_rate.fee(vsize),
+      &[*cardinal].into(),
+    )
+  }
+}
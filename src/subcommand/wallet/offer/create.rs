@@ -0,0 +1,168 @@
+use {
+  super::*,
+  bitcoin::sighash::EcdsaSighashType,
+  bitcoincore_rpc::json::{AddressType, SigHashType},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub outpoint: OutPoint,
+  pub price: u64,
+  pub psbt: String,
+}
+
+#[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("asset")
+    .required(true)
+    .args(&["inscription", "rune"])))
+]
+pub(crate) struct Create {
+  #[arg(long, help = "Sell for <AMOUNT>.")]
+  amount: Amount,
+  #[arg(long, help = "Sell <INSCRIPTION>.")]
+  inscription: Option<InscriptionId>,
+  #[arg(
+    long,
+    help = "Sell <DECIMAL:RUNE>. Wallet must contain an output holding exactly this amount \
+    and nothing else.",
+    value_name = "DECIMAL:RUNE"
+  )]
+  rune: Option<Outgoing>,
+}
+
+impl Create {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let outpoint = match Asset::new(self.inscription, self.rune)? {
+      Asset::Inscription(inscription) => Self::inscription_output(&wallet, inscription)?,
+      Asset::Rune { decimal, rune } => Self::rune_output(&wallet, decimal, rune)?,
+    };
+
+    let seller = wallet
+      .bitcoin_client()
+      .get_new_address(None, Some(AddressType::Bech32m))?
+      .require_network(wallet.chain().network())?;
+
+    let threshold = seller.script_pubkey().minimal_non_dust();
+
+    ensure!(
+      self.amount >= threshold,
+      "offer amount {} below dust threshold {threshold}",
+      self.amount,
+    );
+
+    let unsigned_transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        script_pubkey: seller.script_pubkey(),
+        value: self.amount,
+      }],
+    };
+
+    let psbt = if wallet.watch_only() {
+      let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+      psbt.inputs[0].sighash_type = Some(TapSighashType::SinglePlusAnyoneCanPay.into());
+
+      let psbt = wallet
+        .bitcoin_client()
+        .wallet_process_psbt(&Offer::encode(&psbt), Some(false), None, None)?
+        .psbt;
+
+      let mut psbt = Offer::decode(&psbt)?;
+
+      wallet.annotate(&mut psbt)?;
+
+      Offer::encode(&psbt)
+    } else {
+      let psbt = wallet
+        .bitcoin_client()
+        .wallet_process_psbt(
+          &Offer::encode(&Psbt::from_unsigned_tx(unsigned_transaction)?),
+          Some(true),
+          Some(SigHashType::from(EcdsaSighashType::SinglePlusAnyoneCanPay)),
+          None,
+        )?
+        .psbt;
+
+      Offer::check(&Offer::decode(&psbt)?)?;
+
+      psbt
+    };
+
+    Ok(Some(Box::new(Output {
+      outpoint,
+      price: self.amount.to_sat(),
+      psbt,
+    })))
+  }
+
+  fn inscription_output(wallet: &Wallet, inscription: InscriptionId) -> Result<OutPoint> {
+    let outpoint = wallet
+      .inscription_info()
+      .get(&inscription)
+      .ok_or_else(|| anyhow!("inscription {inscription} not in wallet"))?
+      .satpoint
+      .outpoint;
+
+    ensure!(
+      wallet.get_inscriptions_in_output(&outpoint) == [inscription],
+      "output {outpoint} holding inscription {inscription} also holds other inscriptions",
+    );
+
+    ensure!(
+      wallet.get_runes_balances_in_output(&outpoint)?.is_empty(),
+      "output {outpoint} holding inscription {inscription} also holds runes",
+    );
+
+    Ok(outpoint)
+  }
+
+  fn rune_output(wallet: &Wallet, decimal: Decimal, spaced_rune: SpacedRune) -> Result<OutPoint> {
+    ensure!(
+      wallet.has_rune_index(),
+      "selling runes with `ord wallet offer create` requires index created with `--index-runes`",
+    );
+
+    let (_id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+    let amount = decimal.to_integer(entry.divisibility)?;
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    for outpoint in wallet.get_runic_outputs()? {
+      if inscribed.contains(&outpoint) {
+        continue;
+      }
+
+      let balances = wallet.get_runes_balances_in_output(&outpoint)?;
+
+      if balances.len() == 1
+        && balances
+          .iter()
+          .all(|(rune, pile)| rune.rune == spaced_rune.rune && pile.amount == amount)
+      {
+        return Ok(outpoint);
+      }
+    }
+
+    bail!(
+      "wallet contains no output holding exactly {decimal}:{spaced_rune} and nothing else, \
+      use `ord wallet split` to create one"
+    );
+  }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub inscriptions: Vec<InscriptionId>,
+  pub outpoint: OutPoint,
+  pub price: u64,
+  pub runes: BTreeMap<SpacedRune, Decimal>,
+  pub seller: Option<Address<NetworkUnchecked>>,
+  pub value: u64,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Inspect {
+  #[arg(long, help = "Inspect offer <PSBT>.")]
+  psbt: String,
+}
+
+impl Inspect {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let psbt = Offer::decode(&self.psbt)?;
+
+    Ok(Some(Box::new(Self::inspect(&wallet, &psbt)?)))
+  }
+
+  /// Looks up the output sold by `psbt` in the index, so that what the offer
+  /// sells is taken from the chain rather than from the seller.
+  pub(super) fn inspect(wallet: &Wallet, psbt: &Psbt) -> Result<Output> {
+    Offer::check(psbt)?;
+
+    let outpoint = psbt.unsigned_tx.input[0].previous_output;
+
+    let payment = &psbt.unsigned_tx.output[0];
+
+    let output = wallet
+      .get_output_info(&[outpoint])?
+      .into_iter()
+      .next()
+      .unwrap();
+
+    ensure!(output.indexed, "offer output {outpoint} is not indexed");
+
+    ensure!(!output.spent, "offer output {outpoint} has been spent");
+
+    Ok(Output {
+      inscriptions: output.inscriptions,
+      outpoint,
+      price: payment.value.to_sat(),
+      runes: output
+        .runes
+        .into_iter()
+        .map(|(spaced_rune, pile)| {
+          (
+            spaced_rune,
+            Decimal {
+              value: pile.amount,
+              scale: pile.divisibility,
+            },
+          )
+        })
+        .collect(),
+      seller: wallet
+        .chain()
+        .address_from_script(&payment.script_pubkey)
+        .ok()
+        .map(|address| address.as_unchecked().clone()),
+      value: output.value,
+    })
+  }
+}
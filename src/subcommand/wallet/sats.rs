@@ -1,6 +1,9 @@
 use super::*;
 
+pub mod extract;
+
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub(crate) struct Sats {
   #[arg(
     long,
@@ -14,6 +17,14 @@ pub(crate) struct Sats {
     help = "Display list of all sat ranges in wallet."
   )]
   all: bool,
+  #[command(subcommand)]
+  subcommand: Option<Subcommand>,
+}
+
+#[derive(Debug, Parser)]
+enum Subcommand {
+  #[command(about = "Move rare or listed sats to their own outputs")]
+  Extract(extract::Extract),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -37,7 +48,11 @@ pub struct OutputAll {
 }
 
 impl Sats {
-  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    if let Some(Subcommand::Extract(extract)) = self.subcommand {
+      return extract.run(wallet);
+    }
+
     ensure!(
       wallet.has_sat_index(),
       "sats requires index created with `--index-sats` flag"
@@ -0,0 +1,245 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Extract {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with each extracted sat. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Extract satoshis listed in first column of tab-separated value file <TSV> instead \
+    of rare satoshis."
+  )]
+  tsv: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub outputs: Vec<PlannedOutput>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedOutput {
+  pub output: OutPoint,
+  pub value: u64,
+  pub sats: Vec<Sat>,
+  pub ranges: Vec<String>,
+}
+
+impl Extract {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_sat_index(),
+      "sats requires index created with `--index-sats` flag"
+    );
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?;
+
+    let haystacks = wallet
+      .get_wallet_sat_ranges()?
+      .into_iter()
+      .filter(|(outpoint, _)| !inscribed.contains(outpoint) && !runic.contains(outpoint))
+      .collect::<Vec<(OutPoint, Vec<(u64, u64)>)>>();
+
+    let targets = if let Some(path) = &self.tsv {
+      let tsv = fs::read_to_string(path)
+        .with_context(|| format!("I/O error reading `{}`", path.display()))?;
+
+      Sats::find(&Sats::needles(&tsv)?, &haystacks)
+        .into_iter()
+        .map(|(sat, satpoint)| Ok((satpoint.outpoint, satpoint.offset, sat.parse::<Sat>()?)))
+        .collect::<Result<Vec<(OutPoint, u64, Sat)>>>()?
+    } else {
+      Sats::rare_sats(haystacks)
+        .into_iter()
+        .map(|(outpoint, sat, offset, _rarity)| (outpoint, offset, sat))
+        .collect()
+    };
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    let dust_limit = change.minimal_non_dust();
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    ensure!(
+      postage >= dust_limit,
+      "postage value {postage} below dust threshold {dust_limit}",
+    );
+
+    let mut by_output = BTreeMap::<OutPoint, BTreeMap<u64, Vec<Sat>>>::new();
+
+    for (outpoint, offset, sat) in targets {
+      by_output
+        .entry(outpoint)
+        .or_default()
+        .entry(offset)
+        .or_default()
+        .push(sat);
+    }
+
+    // outputs which already hold a single target sat at their start, and no
+    // more than postage, are already extracted
+    by_output.retain(|outpoint, offsets| {
+      !(offsets.len() == 1 && offsets.contains_key(&0) && wallet.utxos()[outpoint].value <= postage)
+    });
+
+    ensure!(!by_output.is_empty(), "wallet contains no sats to extract");
+
+    let mut offsets = BTreeMap::new();
+    let mut input_value = Amount::ZERO;
+
+    for (outpoint, sats) in &by_output {
+      for (offset, sats) in sats {
+        offsets.insert(input_value.to_sat() + offset, sats.clone());
+      }
+
+      input_value += wallet.utxos()[outpoint].value;
+    }
+
+    let layout = crate::subcommand::wallet::extract::Extract::layout(
+      &offsets,
+      postage.to_sat(),
+      dust_limit.to_sat(),
+    )?;
+
+    let inputs = by_output.keys().copied().collect::<Vec<OutPoint>>();
+
+    let (unsigned_transaction, _fee) = wallet.fund_transaction(
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: inputs
+          .iter()
+          .map(|outpoint| TxIn {
+            previous_output: *outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output: layout
+          .iter()
+          .map(|(value, _sats)| TxOut {
+            script_pubkey: change.clone(),
+            value: Amount::from_sat(*value),
+          })
+          .collect(),
+      },
+      input_value,
+      |vsize| self.fee_rate.fee(vsize),
+      &inputs.iter().copied().collect(),
+    )?;
+
+    let mut input_ranges = Vec::new();
+
+    for txin in &unsigned_transaction.input {
+      input_ranges.extend(wallet.get_output_sat_ranges(&txin.previous_output)?);
+    }
+
+    let output_ranges = Self::plan(
+      &input_ranges,
+      unsigned_transaction
+        .output
+        .iter()
+        .map(|tx_out| tx_out.value.to_sat()),
+    );
+
+    let (txid, psbt, fee) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+
+    let outputs = output_ranges
+      .into_iter()
+      .enumerate()
+      .map(|(vout, ranges)| PlannedOutput {
+        output: OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        value: ranges.iter().map(|(start, end)| end - start).sum(),
+        sats: offsets
+          .values()
+          .flatten()
+          .filter(|sat| {
+            ranges
+              .iter()
+              .any(|(start, end)| (*start..*end).contains(&sat.n()))
+          })
+          .copied()
+          .collect(),
+        ranges: ranges
+          .iter()
+          .map(|(start, end)| format!("{start}-{end}"))
+          .collect(),
+      })
+      .collect();
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee,
+      outputs,
+    })))
+  }
+
+  /// Assigns the sat ranges of a transaction's inputs to outputs with the
+  /// given values, in order, leaving the remainder to the fee.
+  fn plan(inputs: &[(u64, u64)], outputs: impl Iterator<Item = u64>) -> Vec<Vec<(u64, u64)>> {
+    let mut inputs = inputs.iter().copied();
+    let mut current = None;
+
+    outputs
+      .map(|value| {
+        let mut ranges = Vec::new();
+        let mut remaining = value;
+
+        while remaining > 0 {
+          let Some((start, end)) = current.take().or_else(|| inputs.next()) else {
+            break;
+          };
+
+          let taken = remaining.min(end - start);
+
+          ranges.push((start, start + taken));
+
+          if start + taken < end {
+            current = Some((start + taken, end));
+          }
+
+          remaining -= taken;
+        }
+
+        ranges
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn plan() {
+    assert_eq!(
+      Extract::plan(&[(0, 100), (500, 600)], [50, 100, 30].into_iter()),
+      [vec![(0, 50)], vec![(50, 100), (500, 550)], vec![(550, 580)],],
+    );
+  }
+}
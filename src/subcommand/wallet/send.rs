@@ -15,10 +15,10 @@ pub(crate) struct Send {
   #[arg(help = "Recipient address")]
   address: Address<NetworkUnchecked>,
   #[arg(
-    help = "Outgoing asset formatted as a bitcoin amount, rune amount, sat name, satpoint, or \
-    inscription ID. Bitcoin amounts are `DECIMAL UNIT` where `UNIT` is one of \
+    help = "Outgoing asset formatted as a bitcoin amount, rune amount, sat name, satpoint, sat \
+    range, or inscription ID. Bitcoin amounts are `DECIMAL UNIT` where `UNIT` is one of \
     `bit btc cbtc mbtc msat nbtc pbtc sat satoshi ubtc`. Rune amounts are `DECIMAL:RUNE` and \
-    respect divisibility"
+    respect divisibility. Sat ranges are `START-END`, and do not include `END`"
   )]
   asset: Outgoing,
 }
@@ -78,6 +78,13 @@ impl Send {
         self.fee_rate,
         true,
       )?,
+      Outgoing::SatRange { start, end } => Self::create_unsigned_send_sat_range_transaction(
+        &wallet,
+        address,
+        start,
+        end,
+        self.fee_rate,
+      )?,
     };
 
     let (txid, psbt, fee) =
@@ -166,6 +173,129 @@ impl Send {
     )
   }
 
+  fn create_unsigned_send_sat_range_transaction(
+    wallet: &Wallet,
+    destination: Address,
+    start: Sat,
+    end: Sat,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let satpoint = wallet.find_sat_range_in_outputs(start, end)?;
+
+    let size = end.n() - start.n();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    ensure!(
+      !runic_outputs.contains(&satpoint.outpoint),
+      "runic outpoints may not be sent by sat range"
+    );
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    for inscription_satpoint in wallet.inscriptions().keys() {
+      if inscription_satpoint.outpoint == satpoint.outpoint
+        && (satpoint.offset..satpoint.offset + size).contains(&inscription_satpoint.offset)
+      {
+        bail!("inscriptions must be sent by inscription ID");
+      }
+    }
+
+    let recipient = destination.script_pubkey();
+
+    ensure!(
+      Amount::from_sat(size) >= recipient.minimal_non_dust(),
+      "sat range `{start}-{end}` contains {size} sats, less than the dust limit of {} for {destination}",
+      recipient.minimal_non_dust().to_sat(),
+    );
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    let dust_limit = change.minimal_non_dust().to_sat();
+
+    let value = wallet.utxos()[&satpoint.outpoint].value.to_sat();
+
+    let mut inputs = vec![satpoint.outpoint];
+    let mut input_value = value;
+    let mut outputs = Vec::new();
+
+    // sats before the range go to change, prefixed by a cardinal output if
+    // they would otherwise be dust
+    if satpoint.offset > 0 {
+      let mut before = satpoint.offset;
+
+      if before < dust_limit {
+        let (outpoint, tx_out) = wallet
+          .utxos()
+          .iter()
+          .filter(|(outpoint, tx_out)| {
+            **outpoint != satpoint.outpoint
+              && !inscribed_outputs.contains(outpoint)
+              && !runic_outputs.contains(outpoint)
+              && tx_out.value.to_sat() + before >= dust_limit
+          })
+          .min_by_key(|(_, tx_out)| tx_out.value)
+          .ok_or_else(|| anyhow!("not enough cardinal utxos"))?;
+
+        inputs.insert(0, *outpoint);
+        input_value += tx_out.value.to_sat();
+        before += tx_out.value.to_sat();
+      }
+
+      outputs.push(TxOut {
+        script_pubkey: change.clone(),
+        value: Amount::from_sat(before),
+      });
+    }
+
+    outputs.push(TxOut {
+      script_pubkey: recipient,
+      value: Amount::from_sat(size),
+    });
+
+    // sats after the range flow into the change output added when funding,
+    // which pays the fee from its end, unless they hold inscriptions
+    let after = value - satpoint.offset - size;
+
+    if after >= dust_limit
+      && wallet.inscriptions().keys().any(|inscription_satpoint| {
+        inscription_satpoint.outpoint == satpoint.outpoint
+          && inscription_satpoint.offset >= satpoint.offset + size
+      })
+    {
+      outputs.push(TxOut {
+        script_pubkey: change,
+        value: Amount::from_sat(after),
+      });
+    }
+
+    let (unsigned_transaction, _fee) = wallet.fund_transaction(
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: inputs
+          .iter()
+          .map(|outpoint| TxIn {
+            previous_output: *outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output: outputs,
+      },
+      Amount::from_sat(input_value),
+      |vsize| fee_rate.fee(vsize),
+      &inputs.iter().copied().collect(),
+    )?;
+
+    Ok(unsigned_transaction)
+  }
+
   fn create_unsigned_send_runes_transaction(
     wallet: &Wallet,
     destination: Address,
@@ -269,7 +399,7 @@ impl Send {
         .map(|previous_output| TxIn {
           previous_output,
           script_sig: ScriptBuf::new(),
-          sequence: Sequence::MAX,
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
           witness: Witness::new(),
         })
         .collect(),
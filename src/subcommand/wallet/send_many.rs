@@ -0,0 +1,488 @@
+use {
+  super::*,
+  split::{
+    splitfile::{RuneInfo, Splitfile},
+    Error, Split,
+  },
+};
+
+/// Transactions are split into chunks no heavier than half the maximum
+/// standard weight, leaving room for the cardinal inputs and change output
+/// added when funding them.
+const MAX_CHUNK_WEIGHT: u64 = MAX_STANDARD_TX_WEIGHT as u64 / 2;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecipientsUnchecked {
+  outputs: Vec<RecipientUnchecked>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecipientUnchecked {
+  address: Address<NetworkUnchecked>,
+  #[serde(default)]
+  inscriptions: Vec<InscriptionId>,
+  #[serde(default)]
+  runes: BTreeMap<SpacedRune, Decimal>,
+  value: Option<DeserializeFromStr<Amount>>,
+}
+
+struct Recipient {
+  address: Address,
+  inscribed: Vec<(OutPoint, Amount)>,
+  runes: BTreeMap<Rune, u128>,
+  value: Option<Amount>,
+}
+
+struct Chunk {
+  input_value: Amount,
+  rune_change: Option<(u32, BTreeMap<Rune, u128>)>,
+  runic_inputs: Vec<OutPoint>,
+  transaction: Transaction,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct SendMany {
+  #[arg(long, help = "Don't sign or broadcast transactions")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Send outputs defined in YAML <RECIPIENTS_FILE>.",
+    value_name = "RECIPIENTS_FILE"
+  )]
+  file: PathBuf,
+  #[arg(
+    long,
+    alias = "nolimit",
+    help = "Allow OP_RETURN greater than 83 bytes. Transactions over this limit are nonstandard \
+    and will not be relayed by bitcoind in its default configuration. Do not use this flag unless \
+    you understand the implications."
+  )]
+  no_limit: bool,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with rune change outputs. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+impl SendMany {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let (recipients, rune_info) = Self::load(&self.file, &wallet)?;
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    let change_script_pubkey = wallet.get_change_address()?.script_pubkey();
+
+    let threshold = change_script_pubkey.minimal_non_dust();
+
+    if postage < threshold {
+      return Err(
+        Error::DustPostage {
+          value: postage,
+          threshold,
+        }
+        .into(),
+      );
+    }
+
+    let mut balances = if rune_info.is_empty() {
+      BTreeMap::new()
+    } else {
+      Split::rune_balances(&wallet)?
+    };
+
+    let mut values = balances
+      .keys()
+      .map(|outpoint| (*outpoint, wallet.utxos()[outpoint].value))
+      .collect::<BTreeMap<OutPoint, Amount>>();
+
+    let mut spent = BTreeSet::new();
+
+    let mut transactions = Vec::new();
+
+    let mut start = 0;
+
+    while start < recipients.len() {
+      let mut end = start + 1;
+
+      let mut chunk = self.build_chunk(
+        start,
+        &recipients[start..end],
+        &balances,
+        &values,
+        &change_script_pubkey,
+        postage,
+        &rune_info,
+      )?;
+
+      while end < recipients.len() {
+        match self.build_chunk(
+          start,
+          &recipients[start..=end],
+          &balances,
+          &values,
+          &change_script_pubkey,
+          postage,
+          &rune_info,
+        ) {
+          Ok(next) if Self::fits(&next.transaction) => {
+            chunk = next;
+            end += 1;
+          }
+          Ok(_) | Err(Error::RunestoneSize { .. }) => break,
+          Err(err) => return Err(err.into()),
+        }
+      }
+
+      let (transaction, fee) = wallet.fund_transaction(
+        chunk.transaction,
+        chunk.input_value,
+        |vsize| self.fee_rate.fee(vsize),
+        &spent,
+      )?;
+
+      for outpoint in &chunk.runic_inputs {
+        balances.remove(outpoint);
+      }
+
+      if let Some((vout, runes)) = chunk.rune_change {
+        let outpoint = OutPoint {
+          txid: transaction.compute_txid(),
+          vout,
+        };
+
+        balances.insert(outpoint, runes);
+        values.insert(outpoint, postage);
+      }
+
+      spent.extend(bump::Bump::spent_outputs(&transaction));
+
+      transactions.push((transaction, fee));
+
+      start = end;
+    }
+
+    let mut outputs = Vec::new();
+
+    for (transaction, fee) in transactions {
+      let (txid, psbt) = wallet.sign_and_broadcast(transaction, self.dry_run, None)?;
+
+      outputs.push(Output {
+        txid,
+        psbt,
+        fee: fee.to_sat(),
+      });
+    }
+
+    Ok(Some(Box::new(outputs)))
+  }
+
+  fn load(path: &Path, wallet: &Wallet) -> Result<(Vec<Recipient>, BTreeMap<Rune, RuneInfo>)> {
+    let unchecked = Self::load_unchecked(path)?;
+
+    ensure!(
+      !unchecked.outputs.is_empty(),
+      "recipients file must contain at least one output"
+    );
+
+    let network = wallet.chain().network();
+
+    let inscription_info = wallet.inscription_info();
+
+    let mut rune_info = BTreeMap::new();
+
+    let mut destinations = BTreeMap::<OutPoint, usize>::new();
+
+    let mut listed = HashSet::new();
+
+    let mut recipients = Vec::new();
+
+    for (i, output) in unchecked.outputs.into_iter().enumerate() {
+      ensure!(
+        output.value.is_some() || !output.inscriptions.is_empty() || !output.runes.is_empty(),
+        "output {i} does not send anything",
+      );
+
+      ensure!(
+        output.value.is_none() || output.inscriptions.is_empty(),
+        "output {i} sends inscriptions and cannot also specify a value",
+      );
+
+      let mut inscribed = Vec::new();
+
+      for id in &output.inscriptions {
+        ensure!(listed.insert(*id), "inscription {id} listed more than once");
+
+        let info = inscription_info
+          .get(id)
+          .ok_or_else(|| anyhow!("inscription {id} not in wallet"))?;
+
+        let outpoint = info.satpoint.outpoint;
+
+        match destinations.get(&outpoint) {
+          Some(&j) if j == i => continue,
+          Some(&j) => bail!("output {outpoint} holds inscriptions sent to both output {j} and {i}"),
+          None => {}
+        }
+
+        for other in wallet.get_inscriptions_in_output(&outpoint) {
+          ensure!(
+            output.inscriptions.contains(&other),
+            "output {outpoint} holding inscription {id} also holds inscription {other}, which \
+            must be sent to the same recipient",
+          );
+        }
+
+        let value = wallet.utxos()[&outpoint].value;
+
+        ensure!(
+          value <= TransactionBuilder::MAX_POSTAGE,
+          "output {outpoint} holding inscription {id} has value {value} over maximum postage {}, \
+          split it before sending",
+          TransactionBuilder::MAX_POSTAGE,
+        );
+
+        ensure!(
+          wallet.get_runes_balances_in_output(&outpoint)?.is_empty(),
+          "output {outpoint} holding inscription {id} also holds runes",
+        );
+
+        destinations.insert(outpoint, i);
+
+        inscribed.push((outpoint, value));
+      }
+
+      if !output.runes.is_empty() {
+        ensure!(
+          wallet.has_rune_index(),
+          "sending runes with `ord wallet send-many` requires index created with `--index-runes`",
+        );
+      }
+
+      let runes = Splitfile::resolve_runes(wallet, output.runes, &mut rune_info)?;
+
+      for (rune, amount) in &runes {
+        if *amount == 0 {
+          return Err(
+            Error::ZeroValue {
+              output: i,
+              rune: rune_info[rune].spaced_rune,
+            }
+            .into(),
+          );
+        }
+      }
+
+      recipients.push(Recipient {
+        address: output.address.require_network(network)?,
+        inscribed,
+        runes,
+        value: output.value.map(|DeserializeFromStr(value)| value),
+      });
+    }
+
+    Ok((recipients, rune_info))
+  }
+
+  fn load_unchecked(path: &Path) -> Result<RecipientsUnchecked> {
+    Ok(serde_yaml::from_reader(File::open(path)?)?)
+  }
+
+  /// Builds an unfunded transaction sending to `recipients`, which start at
+  /// index `offset` in the recipients file. Outputs are laid out as an
+  /// optional runestone, one output per inscribed input carrying its whole
+  /// value so inscriptions keep their offsets, an optional rune change
+  /// output, and finally recipients which receive no inscriptions.
+  #[allow(clippy::too_many_arguments)]
+  fn build_chunk(
+    &self,
+    offset: usize,
+    recipients: &[Recipient],
+    balances: &BTreeMap<OutPoint, BTreeMap<Rune, u128>>,
+    values: &BTreeMap<OutPoint, Amount>,
+    change_script_pubkey: &ScriptBuf,
+    postage: Amount,
+    rune_info: &BTreeMap<Rune, RuneInfo>,
+  ) -> Result<Chunk, Error> {
+    let mut required = BTreeMap::<Rune, u128>::new();
+
+    for recipient in recipients {
+      for (&rune, &amount) in &recipient.runes {
+        let entry = required.entry(rune).or_default();
+        *entry = entry.checked_add(amount).ok_or(Error::Overflow {
+          rune: rune_info[&rune].spaced_rune,
+        })?;
+      }
+    }
+
+    let (runic_inputs, input_balances) = if required.is_empty() {
+      (Vec::new(), BTreeMap::new())
+    } else {
+      Split::select_runic_inputs(balances.clone(), &required, rune_info)?
+    };
+
+    let mut input = Vec::new();
+    let mut output = Vec::new();
+    let mut input_value = Amount::ZERO;
+
+    if !required.is_empty() {
+      output.push(TxOut {
+        script_pubkey: ScriptBuf::new(),
+        value: Amount::ZERO,
+      });
+    }
+
+    let mut destinations = vec![0; recipients.len()];
+
+    for (i, recipient) in recipients.iter().enumerate() {
+      for (j, (outpoint, value)) in recipient.inscribed.iter().enumerate() {
+        if j == 0 {
+          destinations[i] = output.len();
+        }
+
+        input.push(*outpoint);
+        input_value += *value;
+
+        output.push(TxOut {
+          script_pubkey: recipient.address.script_pubkey(),
+          value: *value,
+        });
+      }
+    }
+
+    let rune_change = input_balances
+      .iter()
+      .map(|(rune, balance)| {
+        (
+          *rune,
+          balance - required.get(rune).copied().unwrap_or_default(),
+        )
+      })
+      .filter(|(_, change)| *change > 0)
+      .collect::<BTreeMap<Rune, u128>>();
+
+    let rune_change_output = if rune_change.is_empty() {
+      None
+    } else {
+      output.push(TxOut {
+        script_pubkey: change_script_pubkey.clone(),
+        value: postage,
+      });
+
+      Some(u32::try_from(output.len() - 1).unwrap())
+    };
+
+    for (i, recipient) in recipients.iter().enumerate() {
+      if !recipient.inscribed.is_empty() {
+        continue;
+      }
+
+      let script_pubkey = recipient.address.script_pubkey();
+      let threshold = script_pubkey.minimal_non_dust();
+      let value = recipient.value.unwrap_or(threshold);
+
+      if value < threshold {
+        return Err(Error::DustOutput {
+          value,
+          threshold,
+          output: offset + i,
+        });
+      }
+
+      destinations[i] = output.len();
+
+      output.push(TxOut {
+        script_pubkey,
+        value,
+      });
+    }
+
+    for outpoint in &runic_inputs {
+      input.push(*outpoint);
+      input_value += values[outpoint];
+    }
+
+    if !required.is_empty() {
+      let mut edicts = Vec::new();
+
+      for (recipient, destination) in recipients.iter().zip(destinations) {
+        for (rune, amount) in &recipient.runes {
+          edicts.push(Edict {
+            id: rune_info[rune].id,
+            amount: *amount,
+            output: destination.try_into().unwrap(),
+          });
+        }
+      }
+
+      edicts.sort_by_key(|edict| edict.id);
+
+      let script_pubkey = Runestone {
+        edicts,
+        pointer: rune_change_output,
+        ..default()
+      }
+      .encipher();
+
+      let size = script_pubkey.len();
+
+      if !self.no_limit && size > MAX_STANDARD_OP_RETURN_SIZE {
+        return Err(Error::RunestoneSize { size });
+      }
+
+      output[0].script_pubkey = script_pubkey;
+    }
+
+    Ok(Chunk {
+      input_value,
+      rune_change: rune_change_output.map(|vout| (vout, rune_change)),
+      runic_inputs,
+      transaction: Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: input
+          .into_iter()
+          .map(|previous_output| TxIn {
+            previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+          })
+          .collect(),
+        output,
+      },
+    })
+  }
+
+  fn fits(transaction: &Transaction) -> bool {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    transaction.weight().to_wu() <= MAX_CHUNK_WEIGHT
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_recipients_file_is_valid() {
+    SendMany::load_unchecked("recipients.yaml".as_ref()).unwrap();
+  }
+}
@@ -1,9 +1,9 @@
 use {super::*, splitfile::Splitfile};
 
-mod splitfile;
+pub(super) mod splitfile;
 
 #[derive(Debug, PartialEq)]
-enum Error {
+pub(super) enum Error {
   DustOutput {
     value: Amount,
     threshold: Amount,
@@ -14,6 +14,9 @@ enum Error {
     threshold: Amount,
   },
   NoOutputs,
+  Overflow {
+    rune: SpacedRune,
+  },
   RunestoneSize {
     size: usize,
   },
@@ -43,6 +46,7 @@ impl Display for Error {
         write!(f, "postage value {value} below dust threshold {threshold}")
       }
       Self::NoOutputs => write!(f, "split file must contain at least one output"),
+      Self::Overflow { rune } => write!(f, "total amount of {rune} sent overflows"),
       Self::RunestoneSize { size } => write!(
         f,
         "runestone size {size} over maximum standard OP_RETURN size {MAX_STANDARD_OP_RETURN_SIZE}"
@@ -105,13 +109,40 @@ impl Split {
 
     let splits = Splitfile::load(&self.splits, &wallet)?;
 
+    let balances = Self::rune_balances(&wallet)?;
+
+    let unfunded_transaction = Self::build_transaction(
+      self.no_limit,
+      balances,
+      &wallet.get_change_address()?,
+      self.postage,
+      &splits,
+    )?;
+
+    let unsigned_transaction = fund_raw_transaction(
+      wallet.bitcoin_client(),
+      self.fee_rate,
+      &unfunded_transaction,
+    )?;
+
+    let unsigned_transaction = consensus::encode::deserialize(&unsigned_transaction)?;
+
+    let (txid, psbt, fee) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output { txid, psbt, fee })))
+  }
+
+  /// Returns the rune balances of each runic output in the wallet, excluding
+  /// outputs which also hold inscriptions.
+  pub(super) fn rune_balances(wallet: &Wallet) -> Result<BTreeMap<OutPoint, BTreeMap<Rune, u128>>> {
     let inscribed_outputs = wallet
       .inscriptions()
       .keys()
       .map(|satpoint| satpoint.outpoint)
       .collect::<HashSet<OutPoint>>();
 
-    let balances = wallet
+    wallet
       .get_runic_outputs()?
       .into_iter()
       .filter(|output| !inscribed_outputs.contains(output))
@@ -126,28 +157,62 @@ impl Split {
           )
         })
       })
-      .collect::<Result<BTreeMap<OutPoint, BTreeMap<Rune, u128>>>>()?;
+      .collect()
+  }
 
-    let unfunded_transaction = Self::build_transaction(
-      self.no_limit,
-      balances,
-      &wallet.get_change_address()?,
-      self.postage,
-      &splits,
-    )?;
+  /// Selects outputs from `balances` until the `required` amount of each
+  /// rune is covered, returning the selected outputs and their combined
+  /// balances.
+  pub(super) fn select_runic_inputs(
+    balances: BTreeMap<OutPoint, BTreeMap<Rune, u128>>,
+    required: &BTreeMap<Rune, u128>,
+    rune_info: &BTreeMap<Rune, splitfile::RuneInfo>,
+  ) -> Result<(Vec<OutPoint>, BTreeMap<Rune, u128>), Error> {
+    let mut input_rune_balances: BTreeMap<Rune, u128> = BTreeMap::new();
 
-    let unsigned_transaction = fund_raw_transaction(
-      wallet.bitcoin_client(),
-      self.fee_rate,
-      &unfunded_transaction,
-    )?;
+    let mut inputs = Vec::new();
 
-    let unsigned_transaction = consensus::encode::deserialize(&unsigned_transaction)?;
+    for (output, runes) in balances {
+      for (rune, required) in required {
+        if input_rune_balances.get(rune).copied().unwrap_or_default() >= *required {
+          continue;
+        }
 
-    let (txid, psbt, fee) =
-      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+        if runes.get(rune).copied().unwrap_or_default() == 0 {
+          continue;
+        }
 
-    Ok(Some(Box::new(Output { txid, psbt, fee })))
+        for (rune, balance) in &runes {
+          *input_rune_balances.entry(*rune).or_default() += balance;
+        }
+
+        inputs.push(output);
+
+        break;
+      }
+    }
+
+    for (&rune, &need) in required {
+      let have = input_rune_balances.get(&rune).copied().unwrap_or_default();
+      if have < need {
+        let info = rune_info[&rune];
+        return Err(Error::Shortfall {
+          rune: info.spaced_rune,
+          have: Pile {
+            amount: have,
+            divisibility: info.divisibility,
+            symbol: info.symbol,
+          },
+          need: Pile {
+            amount: need,
+            divisibility: info.divisibility,
+            symbol: info.symbol,
+          },
+        });
+      }
+    }
+
+    Ok((inputs, input_rune_balances))
   }
 
   fn build_transaction(
@@ -189,49 +254,8 @@ impl Split {
       }
     }
 
-    let mut input_rune_balances: BTreeMap<Rune, u128> = BTreeMap::new();
-
-    let mut inputs = Vec::new();
-
-    for (output, runes) in balances {
-      for (rune, required) in &input_runes_required {
-        if input_rune_balances.get(rune).copied().unwrap_or_default() >= *required {
-          continue;
-        }
-
-        if runes.get(rune).copied().unwrap_or_default() == 0 {
-          continue;
-        }
-
-        for (rune, balance) in &runes {
-          *input_rune_balances.entry(*rune).or_default() += balance;
-        }
-
-        inputs.push(output);
-
-        break;
-      }
-    }
-
-    for (&rune, &need) in &input_runes_required {
-      let have = input_rune_balances.get(&rune).copied().unwrap_or_default();
-      if have < need {
-        let info = splits.rune_info[&rune];
-        return Err(Error::Shortfall {
-          rune: info.spaced_rune,
-          have: Pile {
-            amount: have,
-            divisibility: info.divisibility,
-            symbol: info.symbol,
-          },
-          need: Pile {
-            amount: need,
-            divisibility: info.divisibility,
-            symbol: info.symbol,
-          },
-        });
-      }
-    }
+    let (inputs, input_rune_balances) =
+      Self::select_runic_inputs(balances, &input_runes_required, &splits.rune_info)?;
 
     let mut need_rune_change_output = false;
     for (rune, input) in input_rune_balances {
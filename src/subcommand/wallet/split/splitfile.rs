@@ -44,31 +44,7 @@ impl Splitfile {
     let mut outputs = Vec::new();
 
     for output in unchecked.outputs {
-      let mut runes = BTreeMap::new();
-
-      for (spaced_rune, decimal) in output.runes {
-        let info = if let Some(info) = rune_info.get(&spaced_rune.rune) {
-          info
-        } else {
-          let (id, entry, _parent) = wallet
-            .get_rune(spaced_rune.rune)?
-            .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
-          rune_info.insert(
-            spaced_rune.rune,
-            RuneInfo {
-              divisibility: entry.divisibility,
-              id,
-              spaced_rune: entry.spaced_rune,
-              symbol: entry.symbol,
-            },
-          );
-          rune_info.get(&spaced_rune.rune).unwrap()
-        };
-
-        let amount = decimal.to_integer(info.divisibility)?;
-
-        runes.insert(spaced_rune.rune, amount);
-      }
+      let runes = Self::resolve_runes(wallet, output.runes, &mut rune_info)?;
 
       outputs.push(Output {
         address: output.address.require_network(network)?,
@@ -80,6 +56,42 @@ impl Splitfile {
     Ok(Self { outputs, rune_info })
   }
 
+  /// Looks up each rune, caching its info in `rune_info`, and converts
+  /// decimal amounts to integers using the rune's divisibility.
+  pub(crate) fn resolve_runes(
+    wallet: &Wallet,
+    runes: BTreeMap<SpacedRune, Decimal>,
+    rune_info: &mut BTreeMap<Rune, RuneInfo>,
+  ) -> Result<BTreeMap<Rune, u128>> {
+    let mut amounts = BTreeMap::new();
+
+    for (spaced_rune, decimal) in runes {
+      let info = if let Some(info) = rune_info.get(&spaced_rune.rune) {
+        info
+      } else {
+        let (id, entry, _parent) = wallet
+          .get_rune(spaced_rune.rune)?
+          .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+        rune_info.insert(
+          spaced_rune.rune,
+          RuneInfo {
+            divisibility: entry.divisibility,
+            id,
+            spaced_rune: entry.spaced_rune,
+            symbol: entry.symbol,
+          },
+        );
+        rune_info.get(&spaced_rune.rune).unwrap()
+      };
+
+      let amount = decimal.to_integer(info.divisibility)?;
+
+      amounts.insert(spaced_rune.rune, amount);
+    }
+
+    Ok(amounts)
+  }
+
   fn load_unchecked(path: &Path) -> Result<SplitfileUnchecked> {
     Ok(serde_yaml::from_reader(File::open(path)?)?)
   }
@@ -2,33 +2,510 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Transactions {
+  #[arg(long, help = "Also write transactions to CSV file <CSV>.")]
+  csv: Option<PathBuf>,
   #[arg(long, help = "Fetch at most <LIMIT> transactions.")]
   limit: Option<u16>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+  Burn,
+  Etch,
+  Inscribe,
+  Mint,
+  Other,
+  Receive,
+  Send,
+  Split,
+}
+
+impl Display for Kind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Burn => "burn",
+        Self::Etch => "etch",
+        Self::Inscribe => "inscribe",
+        Self::Mint => "mint",
+        Self::Other => "other",
+        Self::Receive => "receive",
+        Self::Send => "send",
+        Self::Split => "split",
+      }
+    )
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Output {
   pub transaction: Txid,
   pub confirmations: i32,
+  pub kind: Kind,
+  pub net: i64,
+  pub fee: Option<u64>,
+  pub inscriptions_received: Vec<InscriptionId>,
+  pub inscriptions_sent: Vec<InscriptionId>,
+  pub runes_received: BTreeMap<SpacedRune, Decimal>,
+  pub runes_sent: BTreeMap<SpacedRune, Decimal>,
 }
 
 impl Transactions {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
-    let client = wallet.bitcoin_client();
+    let mut txids = Vec::new();
 
-    let mut output = Vec::new();
-    for tx in client.list_transactions(
+    for tx in wallet.bitcoin_client().list_transactions(
       None,
       Some(self.limit.unwrap_or(u16::MAX).into()),
       None,
       None,
     )? {
-      output.push(Output {
-        transaction: tx.info.txid,
-        confirmations: tx.info.confirmations,
-      });
+      if !txids
+        .iter()
+        .any(|(txid, _confirmations)| *txid == tx.info.txid)
+      {
+        txids.push((tx.info.txid, tx.info.confirmations));
+      }
+    }
+
+    let mut annotator = Annotator {
+      histories: BTreeMap::new(),
+      history_index: true,
+      mine: BTreeMap::new(),
+      runes: BTreeMap::new(),
+      transactions: BTreeMap::new(),
+      wallet: &wallet,
+    };
+
+    let mut output = Vec::new();
+
+    for (txid, confirmations) in txids {
+      if let Some(annotated) = annotator.annotate(txid, confirmations)? {
+        output.push(annotated);
+      }
+    }
+
+    // commit transactions are funded by the wallet and spent by the reveal
+    // transaction, so classify them with their reveal
+    for i in 0..output.len() {
+      if !matches!(output[i].kind, Kind::Etch | Kind::Inscribe) {
+        continue;
+      }
+
+      for txin in &annotator.transaction(output[i].transaction)?.input {
+        if let Some(commit) = output
+          .iter()
+          .position(|output| output.transaction == txin.previous_output.txid)
+        {
+          if output[commit].kind == Kind::Send {
+            output[commit].kind = output[i].kind;
+          }
+        }
+      }
+    }
+
+    if let Some(path) = &self.csv {
+      fs::write(path, Self::csv(&output))
+        .with_context(|| format!("I/O error writing `{}`", path.display()))?;
     }
 
     Ok(Some(Box::new(output)))
   }
+
+  fn csv(outputs: &[Output]) -> String {
+    let mut csv = String::from(
+      "transaction,confirmations,kind,net,fee,inscriptions_received,inscriptions_sent,\
+      runes_received,runes_sent\n",
+    );
+
+    let runes = |runes: &BTreeMap<SpacedRune, Decimal>| {
+      runes
+        .iter()
+        .map(|(rune, decimal)| format!("{decimal}:{rune}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+    };
+
+    let inscriptions = |inscriptions: &[InscriptionId]| {
+      inscriptions
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+    };
+
+    for output in outputs {
+      csv.push_str(&format!(
+        "{},{},{},{},{},{},{},{},{}\n",
+        output.transaction,
+        output.confirmations,
+        output.kind,
+        output.net,
+        output.fee.map(|fee| fee.to_string()).unwrap_or_default(),
+        inscriptions(&output.inscriptions_received),
+        inscriptions(&output.inscriptions_sent),
+        runes(&output.runes_received),
+        runes(&output.runes_sent),
+      ));
+    }
+
+    csv
+  }
+
+  fn kind(transaction: &Transaction, wallet_inputs: bool, external_outputs: bool) -> Kind {
+    let artifact = Runestone::decipher(transaction);
+
+    if let Some(Artifact::Runestone(runestone)) = &artifact {
+      if runestone.etching.is_some() {
+        return Kind::Etch;
+      }
+    }
+
+    if !ParsedEnvelope::from_transaction(transaction).is_empty() {
+      return Kind::Inscribe;
+    }
+
+    match artifact {
+      Some(Artifact::Cenotaph(_)) => return Kind::Burn,
+      Some(Artifact::Runestone(runestone)) => {
+        if runestone.mint.is_some() {
+          return Kind::Mint;
+        }
+
+        let destinations = runestone
+          .edicts
+          .iter()
+          .map(|edict| edict.output)
+          .collect::<BTreeSet<u32>>();
+
+        if destinations.len() > 1
+          || destinations.contains(&u32::try_from(transaction.output.len()).unwrap())
+        {
+          return Kind::Split;
+        }
+      }
+      None => {
+        if transaction
+          .output
+          .iter()
+          .any(|tx_out| tx_out.script_pubkey.is_op_return())
+        {
+          return Kind::Burn;
+        }
+      }
+    }
+
+    if !wallet_inputs {
+      Kind::Receive
+    } else if external_outputs {
+      Kind::Send
+    } else {
+      Kind::Other
+    }
+  }
+}
+
+/// Caches the transactions, script ownership, address histories, and rune
+/// entries looked up while annotating wallet transactions.
+struct Annotator<'a> {
+  histories: BTreeMap<ScriptBuf, Vec<api::AddressTransaction>>,
+  history_index: bool,
+  mine: BTreeMap<ScriptBuf, bool>,
+  runes: BTreeMap<RuneId, RuneEntry>,
+  transactions: BTreeMap<Txid, Transaction>,
+  wallet: &'a Wallet,
+}
+
+impl Annotator<'_> {
+  fn annotate(&mut self, txid: Txid, confirmations: i32) -> Result<Option<Output>> {
+    let transaction = self.transaction(txid)?;
+
+    let mut scripts = BTreeSet::new();
+    let mut input_value = 0;
+    let mut spent = 0;
+
+    if !transaction.is_coinbase() {
+      for txin in &transaction.input {
+        let previous = self.transaction(txin.previous_output.txid)?.output
+          [usize::try_from(txin.previous_output.vout).unwrap()]
+        .clone();
+
+        input_value += previous.value.to_sat();
+
+        if self.is_mine(&previous.script_pubkey)? {
+          spent += previous.value.to_sat();
+          scripts.insert(previous.script_pubkey);
+        }
+      }
+    }
+
+    let wallet_inputs = !scripts.is_empty();
+    let mut external_outputs = false;
+    let mut received = 0;
+
+    for tx_out in &transaction.output {
+      if self.is_mine(&tx_out.script_pubkey)? {
+        received += tx_out.value.to_sat();
+        scripts.insert(tx_out.script_pubkey.clone());
+      } else if !tx_out.script_pubkey.is_op_return() {
+        external_outputs = true;
+      }
+    }
+
+    if scripts.is_empty() {
+      return Ok(None);
+    }
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .sum::<u64>();
+
+    let mut inscriptions_received = Vec::new();
+    let mut inscriptions_sent = Vec::new();
+    let mut runes = BTreeMap::<RuneId, i128>::new();
+
+    // the index only records the history of confirmed transactions
+    if confirmations > 0 {
+      for script in scripts {
+        for entry in self.history(&script)? {
+          if entry.txid != txid {
+            continue;
+          }
+
+          inscriptions_received.extend(entry.inscriptions_received.iter().copied());
+          inscriptions_sent.extend(entry.inscriptions_sent.iter().copied());
+
+          for (id, amount) in &entry.runes_received {
+            *runes.entry(*id).or_default() += i128::try_from(*amount)?;
+          }
+
+          for (id, amount) in &entry.runes_sent {
+            *runes.entry(*id).or_default() -= i128::try_from(*amount)?;
+          }
+        }
+      }
+    }
+
+    // inscriptions moved between wallet outputs were neither received nor sent
+    let moved = inscriptions_received
+      .iter()
+      .filter(|id| inscriptions_sent.contains(id))
+      .copied()
+      .collect::<BTreeSet<InscriptionId>>();
+
+    inscriptions_received.retain(|id| !moved.contains(id));
+    inscriptions_sent.retain(|id| !moved.contains(id));
+
+    let mut runes_received = BTreeMap::new();
+    let mut runes_sent = BTreeMap::new();
+
+    for (id, net) in runes {
+      if net == 0 {
+        continue;
+      }
+
+      let entry = self.rune(id)?;
+
+      let decimal = Decimal {
+        value: net.unsigned_abs(),
+        scale: entry.divisibility,
+      };
+
+      if net > 0 {
+        runes_received.insert(entry.spaced_rune, decimal);
+      } else {
+        runes_sent.insert(entry.spaced_rune, decimal);
+      }
+    }
+
+    Ok(Some(Output {
+      transaction: txid,
+      confirmations,
+      kind: Transactions::kind(&transaction, wallet_inputs, external_outputs),
+      net: i64::try_from(received)? - i64::try_from(spent)?,
+      fee: (!transaction.is_coinbase()).then(|| input_value.saturating_sub(output_value)),
+      inscriptions_received,
+      inscriptions_sent,
+      runes_received,
+      runes_sent,
+    }))
+  }
+
+  fn history(&mut self, script: &Script) -> Result<&[api::AddressTransaction]> {
+    if !self.histories.contains_key(script) {
+      let history = if self.history_index {
+        let address = self.wallet.chain().address_from_script(script)?;
+        self.wallet.get_address_history(&address)?
+      } else {
+        None
+      };
+
+      if history.is_none() && self.history_index {
+        eprintln!(
+          "warning: ord server index has no address history, inscriptions and runes will not be \
+          reported"
+        );
+        self.history_index = false;
+      }
+
+      self
+        .histories
+        .insert(script.into(), history.unwrap_or_default());
+    }
+
+    Ok(&self.histories[script])
+  }
+
+  fn is_mine(&mut self, script: &Script) -> Result<bool> {
+    if let Some(mine) = self.mine.get(script) {
+      return Ok(*mine);
+    }
+
+    let mine = match self.wallet.chain().address_from_script(script) {
+      Ok(address) => self
+        .wallet
+        .bitcoin_client()
+        .get_address_info(&address)?
+        .is_mine
+        .unwrap_or_default(),
+      Err(_) => false,
+    };
+
+    self.mine.insert(script.into(), mine);
+
+    Ok(mine)
+  }
+
+  fn rune(&mut self, id: RuneId) -> Result<&RuneEntry> {
+    if !self.runes.contains_key(&id) {
+      let entry = self
+        .wallet
+        .get_rune_by_id(id)?
+        .with_context(|| format!("rune {id} not found"))?;
+      self.runes.insert(id, entry);
+    }
+
+    Ok(&self.runes[&id])
+  }
+
+  fn transaction(&mut self, txid: Txid) -> Result<Transaction> {
+    if let Some(transaction) = self.transactions.get(&txid) {
+      return Ok(transaction.clone());
+    }
+
+    let transaction = self
+      .wallet
+      .bitcoin_client()
+      .get_raw_transaction(&txid, None)?;
+
+    self.transactions.insert(txid, transaction.clone());
+
+    Ok(transaction)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn kind() {
+    #[track_caller]
+    fn case(output: Vec<TxOut>, wallet_inputs: bool, external_outputs: bool, expected: Kind) {
+      assert_eq!(
+        Transactions::kind(
+          &Transaction {
+            version: Version(2),
+            lock_time: LockTime::ZERO,
+            input: Vec::new(),
+            output,
+          },
+          wallet_inputs,
+          external_outputs,
+        ),
+        expected,
+      );
+    }
+
+    let tx_out = |script_pubkey: ScriptBuf| TxOut {
+      script_pubkey,
+      value: Amount::from_sat(1_000),
+    };
+
+    let runestone = |runestone: Runestone| TxOut {
+      script_pubkey: runestone.encipher(),
+      value: Amount::ZERO,
+    };
+
+    let edict = |output| Edict {
+      id: RuneId { block: 1, tx: 1 },
+      amount: 0,
+      output,
+    };
+
+    case(vec![tx_out(ScriptBuf::new())], false, false, Kind::Receive);
+    case(vec![tx_out(ScriptBuf::new())], true, true, Kind::Send);
+    case(vec![tx_out(ScriptBuf::new())], true, false, Kind::Other);
+    case(
+      vec![tx_out(ScriptBuf::new_op_return([]))],
+      true,
+      false,
+      Kind::Burn,
+    );
+    case(
+      vec![
+        runestone(Runestone {
+          etching: Some(Etching::default()),
+          ..default()
+        }),
+        tx_out(ScriptBuf::new()),
+      ],
+      true,
+      false,
+      Kind::Etch,
+    );
+    case(
+      vec![
+        runestone(Runestone {
+          mint: Some(RuneId { block: 1, tx: 1 }),
+          ..default()
+        }),
+        tx_out(ScriptBuf::new()),
+      ],
+      true,
+      false,
+      Kind::Mint,
+    );
+    case(
+      vec![
+        runestone(Runestone {
+          edicts: vec![edict(1), edict(2)],
+          ..default()
+        }),
+        tx_out(ScriptBuf::new()),
+        tx_out(ScriptBuf::new()),
+      ],
+      true,
+      true,
+      Kind::Split,
+    );
+    case(
+      vec![
+        runestone(Runestone {
+          edicts: vec![edict(2)],
+          ..default()
+        }),
+        tx_out(ScriptBuf::new()),
+        tx_out(ScriptBuf::new()),
+      ],
+      true,
+      true,
+      Kind::Send,
+    );
+  }
 }
@@ -1,8 +1,9 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
-  crate::subcommand::server::ServerConfig,
+  crate::subcommand::server::{InscriptionsQuery, ServerConfig},
   address::AddressHtml,
+  address_history::AddressHistoryHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
@@ -10,6 +11,7 @@ pub(crate) use {
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
+  inscription_history::InscriptionHistoryHtml,
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
@@ -20,6 +22,7 @@ pub(crate) use {
     PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
   },
   rare::RareTxt,
+  rune_activity::RuneActivityHtml,
   rune_not_found::RuneNotFoundHtml,
   sat::SatHtml,
 };
@@ -30,6 +33,7 @@ pub use {
 };
 
 pub mod address;
+mod address_history;
 pub mod block;
 pub mod blocks;
 mod children;
@@ -39,6 +43,7 @@ mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+mod inscription_history;
 pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
@@ -47,6 +52,7 @@ mod parents;
 mod preview;
 mod rare;
 pub mod rune;
+mod rune_activity;
 pub mod rune_not_found;
 pub mod runes;
 pub mod sat;
@@ -204,4 +210,18 @@ mod tests {
       r".*<nav>\s*<a href=/ title=home>Ordinals<sup>signet</sup></a>.*"
     );
   }
+
+  #[test]
+  fn page_testnet4() {
+    assert_regex_match!(
+      Foo.page(Arc::new(ServerConfig {
+        chain: Chain::Testnet4,
+        csp_origin: None,
+        domain: None,
+        index_sats: true,
+        ..default()
+      })),
+      r".*<nav>\s*<a href=/ title=home>Ordinals<sup>testnet4</sup></a>.*"
+    );
+  }
 }
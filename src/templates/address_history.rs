@@ -0,0 +1,101 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHistoryHtml {
+  pub(crate) address: Address,
+  pub(crate) history: Vec<api::AddressTransaction>,
+  pub(crate) next_page: Option<u32>,
+  pub(crate) prev_page: Option<u32>,
+  pub(crate) runes: BTreeMap<RuneId, RuneEntry>,
+}
+
+impl PageContent for AddressHistoryHtml {
+  fn title(&self) -> String {
+    format!("Address {} History", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn address() -> Address {
+    Address::from_str("bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8")
+      .unwrap()
+      .require_network(Network::Bitcoin)
+      .unwrap()
+  }
+
+  #[test]
+  fn display() {
+    let id = RuneId { block: 1, tx: 0 };
+
+    assert_regex_match!(
+      AddressHistoryHtml {
+        address: address(),
+        history: vec![api::AddressTransaction {
+          height: 3,
+          inscriptions_received: vec![inscription_id(1)],
+          inscriptions_sent: Vec::new(),
+          received: 1000,
+          runes_received: BTreeMap::new(),
+          runes_sent: [(id, 15)].into(),
+          sent: 2000,
+          txid: txid(1),
+        }],
+        next_page: None,
+        prev_page: Some(0),
+        runes: [(
+          id,
+          RuneEntry {
+            divisibility: 1,
+            spaced_rune: SpacedRune {
+              rune: Rune(0),
+              spacers: 0,
+            },
+            symbol: Some('%'),
+            ..default()
+          },
+        )]
+        .into(),
+      },
+      "<h1><a href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8>Address bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8</a> History</h1>
+<table>
+.*
+  <tr>
+    <td><a href=/block/3>3</a></td>
+    <td><a class=collapse href=/tx/1{64}>1{64}</a></td>
+    <td>1000</td>
+    <td>2000</td>
+    <td>
+      <a class=collapse href=/inscription/1{64}i1>\\+1{64}i1</a>
+    </td>
+    <td>
+      <a href=/rune/A>-1.5\u{A0}%</a>
+    </td>
+  </tr>
+</table>
+<div class=center>
+  <a class=prev href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8/history/0>prev</a>
+next
+</div>
+"
+    );
+  }
+
+  #[test]
+  fn no_transactions() {
+    assert_regex_match!(
+      AddressHistoryHtml {
+        address: address(),
+        history: Vec::new(),
+        next_page: None,
+        prev_page: None,
+        runes: BTreeMap::new(),
+      },
+      "<h1><a href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8>Address bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8</a> History</h1>
+<h3>No transactions</h3>
+"
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, bitcoin::hashes::sha256};
 
 #[derive(Boilerplate, Default)]
 pub struct InscriptionHtml {
@@ -6,7 +6,10 @@ pub struct InscriptionHtml {
   pub charms: u16,
   pub child_count: u64,
   pub children: Vec<InscriptionId>,
+  pub content_duplicates: Option<u64>,
+  pub content_hash: Option<sha256::Hash>,
   pub fee: u64,
+  pub first_with_content: Option<InscriptionId>,
   pub height: u32,
   pub inscription: Inscription,
   pub id: InscriptionId,
@@ -0,0 +1,76 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionHistoryHtml {
+  pub(crate) history: Vec<api::InscriptionTransfer>,
+  pub(crate) id: InscriptionId,
+  pub(crate) next_page: Option<u32>,
+  pub(crate) number: i32,
+  pub(crate) prev_page: Option<u32>,
+}
+
+impl PageContent for InscriptionHistoryHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} History", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        history: vec![api::InscriptionTransfer {
+          address: Some(
+            "bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8"
+              .parse()
+              .unwrap()
+          ),
+          height: 5,
+          new_satpoint: satpoint(2, 0),
+          old_satpoint: satpoint(1, 0),
+          txid: txid(2),
+        }],
+        id: inscription_id(1),
+        next_page: Some(1),
+        number: 0,
+        prev_page: None,
+      },
+      "<h1><a href=/inscription/1{64}i1>Inscription 0</a> History</h1>
+<table>
+.*
+  <tr>
+    <td><a href=/block/5>5</a></td>
+    <td><a class=collapse href=/tx/2{64}>2{64}</a></td>
+    <td><a class=collapse href=/satpoint/1{64}:1:0>1{64}:1:0</a></td>
+    <td><a class=collapse href=/satpoint/2{64}:2:0>2{64}:2:0</a></td>
+    <td><a class=collapse href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8>bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8</a></td>
+  </tr>
+</table>
+<div class=center>
+prev
+  <a class=next href=/inscription/1{64}i1/history/1>next</a>
+</div>
+"
+    );
+  }
+
+  #[test]
+  fn no_transfers() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        history: Vec::new(),
+        id: inscription_id(1),
+        next_page: None,
+        number: 0,
+        prev_page: None,
+      },
+      "<h1><a href=/inscription/1{64}i1>Inscription 0</a> History</h1>
+<h3>No transfers</h3>
+"
+    );
+  }
+}
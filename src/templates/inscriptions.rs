@@ -5,6 +5,7 @@ pub(crate) struct InscriptionsHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) prev: Option<u32>,
   pub(crate) next: Option<u32>,
+  pub(crate) query: InscriptionsQuery,
 }
 
 impl PageContent for InscriptionsHtml {
@@ -24,6 +25,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: None,
         next: None,
+        query: InscriptionsQuery::default(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -47,6 +49,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: Some(1),
         next: Some(2),
+        query: InscriptionsQuery::default(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -62,4 +65,27 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn pagination_links_preserve_query() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        inscriptions: vec![inscription_id(1)],
+        prev: Some(1),
+        next: Some(3),
+        query: InscriptionsQuery {
+          media: Some("image".into()),
+          metaprotocol: Some("foo".into()),
+          ..default()
+        },
+      },
+      "
+        .*
+        <a class=prev href=/inscriptions/1\\?media=image&amp;metaprotocol=foo>prev</a>
+        <a class=next href=/inscriptions/3\\?media=image&amp;metaprotocol=foo>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
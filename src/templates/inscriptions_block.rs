@@ -8,6 +8,7 @@ pub(crate) struct InscriptionsBlockHtml {
   pub(crate) next_block: Option<u32>,
   pub(crate) prev_page: Option<u32>,
   pub(crate) next_page: Option<u32>,
+  pub(crate) query: InscriptionsQuery,
 }
 
 impl InscriptionsBlockHtml {
@@ -17,8 +18,9 @@ impl InscriptionsBlockHtml {
     inscriptions: Vec<InscriptionId>,
     more_inscriptions: bool,
     page_index: u32,
+    query: InscriptionsQuery,
   ) -> Result<Self> {
-    if inscriptions.is_empty() {
+    if inscriptions.is_empty() && (page_index > 0 || query.is_empty()) {
       return Err(anyhow!("page index {page_index} exceeds inscription count"));
     }
 
@@ -37,6 +39,7 @@ impl InscriptionsBlockHtml {
       } else {
         None
       },
+      query,
     })
   }
 }
@@ -61,6 +64,7 @@ mod tests {
         next_block: None,
         prev_page: None,
         next_page: None,
+        query: InscriptionsQuery::default(),
       },
       "
         <h1>Inscriptions in <a href=/block/21>Block 21</a></h1>
@@ -87,6 +91,7 @@ mod tests {
         next_block: Some(22),
         next_page: Some(3),
         prev_page: Some(1),
+        query: InscriptionsQuery::default(),
       },
       "
         <h1>Inscriptions in <a href=/block/21>Block 21</a></h1>
@@ -49,6 +49,7 @@ mod tests {
           burned: 123456789123456789,
           divisibility: 9,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 100,
           terms: Some(Terms {
             cap: Some(101),
@@ -115,6 +116,8 @@ mod tests {
   <dd>0.12%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{A0}@</dd>
+  <dt>holders</dt>
+  <dd>0</dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -140,6 +143,7 @@ mod tests {
           terms: None,
           divisibility: 9,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 0,
           number: 25,
           premine: 0,
@@ -174,6 +178,7 @@ mod tests {
           terms: None,
           divisibility: 9,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 0,
           number: 25,
           premine: 0,
@@ -213,6 +218,7 @@ mod tests {
           }),
           divisibility: 9,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 0,
           premine: 0,
           number: 25,
@@ -263,6 +269,7 @@ mod tests {
           burned: 0,
           divisibility: 0,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 5555,
           terms: Some(Terms {
             cap: Some(10000),
@@ -300,6 +307,7 @@ mod tests {
           burned: 0,
           divisibility: 0,
           etching: Txid::all_zeros(),
+          holders: 0,
           mints: 5555,
           terms: Some(Terms {
             cap: Some(10000),
@@ -0,0 +1,114 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct RuneActivityHtml {
+  pub(crate) activity: Vec<api::RuneActivity>,
+  pub(crate) entry: RuneEntry,
+  pub(crate) next_page: Option<u32>,
+  pub(crate) prev_page: Option<u32>,
+}
+
+impl PageContent for RuneActivityHtml {
+  fn title(&self) -> String {
+    format!("Rune {} Activity", self.entry.spaced_rune)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entry() -> RuneEntry {
+    RuneEntry {
+      divisibility: 1,
+      spaced_rune: SpacedRune {
+        rune: Rune(26),
+        spacers: 1,
+      },
+      symbol: Some('%'),
+      ..default()
+    }
+  }
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneActivityHtml {
+        activity: vec![
+          api::RuneActivity {
+            amount: 15,
+            from: vec![outpoint(1)],
+            height: 5,
+            kind: api::RuneActivityKind::Transfer,
+            to: Some(outpoint(2)),
+            txid: txid(2),
+          },
+          api::RuneActivity {
+            amount: 10,
+            from: Vec::new(),
+            height: 4,
+            kind: api::RuneActivityKind::Mint,
+            to: None,
+            txid: txid(1),
+          },
+        ],
+        entry: entry(),
+        next_page: Some(2),
+        prev_page: Some(0),
+      },
+      "<h1><a href=/rune/A•A>A•A</a> Activity</h1>
+<table>
+  <tr>
+    <th>block</th>
+    <th>kind</th>
+    <th>amount</th>
+    <th>transaction</th>
+    <th>from</th>
+    <th>to</th>
+  </tr>
+  <tr>
+    <td><a href=/block/5>5</a></td>
+    <td>transfer</td>
+    <td>1.5\u{A0}%</td>
+    <td><a class=collapse href=/tx/2{64}>2{64}</a></td>
+    <td>
+      <a class=collapse href=/output/1{64}:1>1{64}:1</a>
+    </td>
+    <td>
+      <a class=collapse href=/output/2{64}:2>2{64}:2</a>
+    </td>
+  </tr>
+  <tr>
+    <td><a href=/block/4>4</a></td>
+    <td>mint</td>
+    <td>1\u{A0}%</td>
+    <td><a class=collapse href=/tx/1{64}>1{64}</a></td>
+    <td>
+    </td>
+    <td>
+    </td>
+  </tr>
+</table>
+<div class=center>
+  <a class=prev href=/rune/A•A/activity/0>prev</a>
+  <a class=next href=/rune/A•A/activity/2>next</a>
+</div>
+"
+    );
+  }
+
+  #[test]
+  fn no_activity() {
+    assert_regex_match!(
+      RuneActivityHtml {
+        activity: Vec::new(),
+        entry: entry(),
+        next_page: None,
+        prev_page: None,
+      },
+      "<h1><a href=/rune/A•A>A•A</a> Activity</h1>
+<h3>No activity</h3>
+"
+    );
+  }
+}
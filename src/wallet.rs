@@ -1,5 +1,6 @@
 use {
   super::*,
+  annotations::Annotations,
   base64::{self, Engine},
   batch::ParentInfo,
   bitcoin::{
@@ -20,6 +21,7 @@ use {
   transaction_builder::TransactionBuilder,
 };
 
+pub mod annotations;
 pub mod batch;
 pub mod entry;
 pub mod transaction_builder;
@@ -86,6 +88,7 @@ pub(crate) struct Wallet {
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
   locked_utxos: BTreeMap<OutPoint, TxOut>,
   settings: Settings,
+  watch_only: bool,
 }
 
 impl Wallet {
@@ -152,10 +155,47 @@ impl Wallet {
     )))
   }
 
+  /// Returns the satpoint of `start` if the sats from `start` up to, but not
+  /// including, `end` are held contiguously by a single wallet output.
+  pub(crate) fn find_sat_range_in_outputs(&self, start: Sat, end: Sat) -> Result<SatPoint> {
+    for (outpoint, sat_ranges) in self.get_wallet_sat_ranges()? {
+      let mut offset = 0;
+      let mut ranges = sat_ranges.iter();
+
+      while let Some((range_start, range_end)) = ranges.next() {
+        if *range_start <= start.n() && start.n() < *range_end {
+          let mut cursor = *range_end;
+
+          while cursor < end.n() {
+            match ranges.next() {
+              Some((next_start, next_end)) if *next_start == cursor => cursor = *next_end,
+              _ => bail!("sat range `{start}-{end}` is not contiguous in output {outpoint}"),
+            }
+          }
+
+          return Ok(SatPoint {
+            outpoint,
+            offset: offset + start.n() - range_start,
+          });
+        }
+
+        offset += range_end - range_start;
+      }
+    }
+
+    Err(anyhow!(
+      "could not find sat range `{start}-{end}` in wallet outputs"
+    ))
+  }
+
   pub(crate) fn bitcoin_client(&self) -> &Client {
     &self.bitcoin_client
   }
 
+  pub(crate) fn watch_only(&self) -> bool {
+    self.watch_only
+  }
+
   pub(crate) fn utxos(&self) -> &BTreeMap<OutPoint, TxOut> {
     &self.utxos
   }
@@ -217,6 +257,21 @@ impl Wallet {
     )
   }
 
+  pub(crate) fn get_output_info(&self, outputs: &[OutPoint]) -> Result<Vec<api::Output>> {
+    let response = self
+      .ord_client
+      .post(self.rpc_url.join("/outputs")?)
+      .json(outputs)
+      .header(header::ACCEPT, "application/json")
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("wallet failed get outputs: {}", response.text()?);
+    }
+
+    Ok(serde_json::from_str(&response.text()?)?)
+  }
+
   pub(crate) fn get_inscriptions_in_output(&self, output: &OutPoint) -> Vec<InscriptionId> {
     self.output_info.get(output).unwrap().inscriptions.clone()
   }
@@ -280,14 +335,25 @@ impl Wallet {
     &self,
     rune: Rune,
   ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    Ok(
+      self
+        .get_rune_json(&SpacedRune { rune, spacers: 0 }.to_string())?
+        .map(|rune_json| (rune_json.id, rune_json.entry, rune_json.parent)),
+    )
+  }
+
+  pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<RuneEntry>> {
+    Ok(
+      self
+        .get_rune_json(&id.to_string())?
+        .map(|rune_json| rune_json.entry),
+    )
+  }
+
+  fn get_rune_json(&self, query: &str) -> Result<Option<api::Rune>> {
     let response = self
       .ord_client
-      .get(
-        self
-          .rpc_url
-          .join(&format!("/rune/{}", SpacedRune { rune, spacers: 0 }))
-          .unwrap(),
-      )
+      .get(self.rpc_url.join(&format!("/rune/{query}")).unwrap())
       .send()?;
 
     if response.status() == StatusCode::NOT_FOUND {
@@ -296,9 +362,42 @@ impl Wallet {
 
     let response = response.error_for_status()?;
 
-    let rune_json: api::Rune = serde_json::from_str(&response.text()?)?;
+    Ok(Some(serde_json::from_str(&response.text()?)?))
+  }
+
+  /// Returns the transactions recorded for `address` by the ord server, or
+  /// `None` if its index has no address history.
+  pub(crate) fn get_address_history(
+    &self,
+    address: &Address,
+  ) -> Result<Option<Vec<api::AddressTransaction>>> {
+    let mut history = Vec::new();
 
-    Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
+    for page_index in 0.. {
+      let response = self
+        .ord_client
+        .get(
+          self
+            .rpc_url
+            .join(&format!("/address/{address}/history/{page_index}"))
+            .unwrap(),
+        )
+        .send()?;
+
+      if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+      }
+
+      let page: api::AddressHistory = serde_json::from_str(&response.error_for_status()?.text()?)?;
+
+      history.extend(page.history);
+
+      if !page.more {
+        break;
+      }
+    }
+
+    Ok(Some(history))
   }
 
   pub(crate) fn get_change_address(&self) -> Result<Address> {
@@ -334,10 +433,9 @@ impl Wallet {
   fn is_above_minimum_at_height(&self, rune: Rune) -> Result<bool> {
     Ok(
       rune
-        >= Rune::minimum_at_height(
-          self.chain().network(),
-          Height(u32::try_from(self.bitcoin_client().get_block_count()? + 1).unwrap()),
-        ),
+        >= self.chain().minimum_rune_at_height(Height(
+          u32::try_from(self.bitcoin_client().get_block_count()? + 1).unwrap(),
+        )),
     )
   }
 
@@ -459,6 +557,21 @@ impl Wallet {
     Ok(descriptors)
   }
 
+  fn has_private_keys(descriptor: &str) -> Result<bool> {
+    // miniscript does not support `rawtr` descriptors, which contain a single key
+    if let Some(key) = descriptor
+      .strip_prefix("rawtr(")
+      .and_then(|key| key.split(')').next())
+    {
+      return Ok(DescriptorSecretKey::from_str(key).is_ok());
+    }
+
+    let (_descriptor, key_map) =
+      miniscript::Descriptor::parse_descriptor(&Secp256k1::new(), descriptor)?;
+
+    Ok(!key_map.is_empty())
+  }
+
   pub(crate) fn initialize_from_descriptors(
     name: String,
     settings: &Settings,
@@ -468,7 +581,21 @@ impl Wallet {
 
     let descriptors = Self::check_descriptors(&name, descriptors)?;
 
-    client.create_wallet(&name, None, Some(true), None, None)?;
+    let mut private = 0;
+    for descriptor in &descriptors {
+      if Self::has_private_keys(&descriptor.desc)
+        .with_context(|| format!("invalid descriptor `{}`", descriptor.desc))?
+      {
+        private += 1;
+      }
+    }
+
+    ensure!(
+      private == 0 || private == descriptors.len(),
+      "descriptors must either all contain private keys or all contain only public keys",
+    );
+
+    client.create_wallet(&name, Some(private == 0), Some(true), None, None)?;
 
     let descriptors = descriptors
       .into_iter()
@@ -739,6 +866,94 @@ impl Wallet {
     )
   }
 
+  /// Appends a change output to `transaction`, then adds cardinal inputs
+  /// until `input_value`, the total value of its inputs, covers its outputs
+  /// and `required_fee` for its size, leaving change above the dust limit.
+  /// Inputs without witnesses are assumed to be taproot key path spends when
+  /// estimating size, and all witnesses are cleared from the returned
+  /// transaction. Outputs in `exclude` are never added.
+  pub(crate) fn fund_transaction(
+    &self,
+    mut transaction: Transaction,
+    mut input_value: Amount,
+    required_fee: impl Fn(usize) -> Amount,
+    exclude: &BTreeSet<OutPoint>,
+  ) -> Result<(Transaction, Amount)> {
+    let change = self.get_change_address()?.script_pubkey();
+
+    let dust_limit = change.minimal_non_dust();
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value)
+      .sum::<Amount>();
+
+    transaction.output.push(TxOut {
+      script_pubkey: change,
+      value: Amount::ZERO,
+    });
+
+    for txin in &mut transaction.input {
+      if txin.witness.is_empty() {
+        txin.witness = Witness::from_slice(&[&[0; 64]]);
+      }
+    }
+
+    let inscribed = self
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = self.get_runic_outputs()?;
+
+    let mut cardinals = self
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _)| {
+        !inscribed.contains(outpoint) && !runic.contains(outpoint) && !exclude.contains(outpoint)
+      })
+      .collect::<Vec<(&OutPoint, &TxOut)>>();
+
+    cardinals.sort_by_key(|(_, tx_out)| std::cmp::Reverse(tx_out.value));
+
+    let mut cardinals = cardinals.into_iter();
+
+    let fee = loop {
+      let fee = required_fee(transaction.vsize());
+
+      if let Some(change) = input_value
+        .checked_sub(output_value)
+        .and_then(|available| available.checked_sub(fee))
+      {
+        if change >= dust_limit {
+          transaction.output.last_mut().unwrap().value = change;
+          break fee;
+        }
+      }
+
+      let Some((outpoint, tx_out)) = cardinals.next() else {
+        bail!("not enough cardinal utxos");
+      };
+
+      transaction.input.push(TxIn {
+        previous_output: *outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::from_slice(&[&[0; 64]]),
+      });
+
+      input_value += tx_out.value;
+    };
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::new();
+    }
+
+    Ok((transaction, fee))
+  }
+
   pub(super) fn sign_and_broadcast_transaction(
     &self,
     unsigned_transaction: Transaction,
@@ -747,53 +962,209 @@ impl Wallet {
   ) -> Result<(Txid, String, u64)> {
     let unspent_outputs = self.utxos();
 
-    let (txid, psbt) = if dry_run {
+    let (txid, psbt) =
+      self.sign_and_broadcast(unsigned_transaction.clone(), dry_run, burn_amount)?;
+
+    let mut fee = 0;
+    for txin in unsigned_transaction.input.iter() {
+      let Some(txout) = unspent_outputs.get(&txin.previous_output) else {
+        panic!("input {} not found in utxos", txin.previous_output);
+      };
+      fee += txout.value.to_sat();
+    }
+
+    for txout in unsigned_transaction.output.iter() {
+      fee = fee.checked_sub(txout.value.to_sat()).unwrap();
+    }
+
+    Ok((txid, psbt, fee))
+  }
+
+  /// Like `sign_and_broadcast_transaction`, but without computing the fee,
+  /// for transactions whose inputs are not all in the wallet's unspent
+  /// outputs, like replacements and children of unconfirmed transactions.
+  pub(super) fn sign_and_broadcast(
+    &self,
+    unsigned_transaction: Transaction,
+    dry_run: bool,
+    burn_amount: Option<Amount>,
+  ) -> Result<(Txid, String)> {
+    self.sign_and_broadcast_psbt(
+      Psbt::from_unsigned_tx(unsigned_transaction)?,
+      dry_run,
+      burn_amount,
+    )
+  }
+
+  /// Like `sign_and_broadcast`, but for a PSBT which may already carry
+  /// signatures for inputs that do not belong to the wallet. Watch-only
+  /// wallets cannot sign, so instead return the PSBT unsigned, annotated with
+  /// the inscriptions and runes of its inputs and outputs, to be signed
+  /// externally and broadcast with `ord wallet broadcast`.
+  pub(super) fn sign_and_broadcast_psbt(
+    &self,
+    psbt: Psbt,
+    dry_run: bool,
+    burn_amount: Option<Amount>,
+  ) -> Result<(Txid, String)> {
+    let txid = psbt.unsigned_tx.compute_txid();
+
+    let psbt = base64::engine::general_purpose::STANDARD.encode(psbt.serialize());
+
+    Ok(if self.watch_only {
       let psbt = self
         .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
+        .wallet_process_psbt(&psbt, Some(false), None, None)?
         .psbt;
 
-      (unsigned_transaction.compute_txid(), psbt)
-    } else {
+      let mut psbt = Psbt::deserialize(&base64::engine::general_purpose::STANDARD.decode(psbt)?)?;
+
+      self.annotate(&mut psbt)?;
+
+      (
+        txid,
+        base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      )
+    } else if dry_run {
       let psbt = self
         .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(true),
-          None,
-          None,
-        )?
+        .wallet_process_psbt(&psbt, Some(false), None, None)?
         .psbt;
 
-      let signed_tx = self
+      (txid, psbt)
+    } else {
+      let psbt = self
         .bitcoin_client()
-        .finalize_psbt(&psbt, None)?
-        .hex
-        .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+        .wallet_process_psbt(&psbt, Some(true), None, None)?
+        .psbt;
 
-      (self.send_raw_transaction(&signed_tx, burn_amount)?, psbt)
-    };
+      (self.finalize_and_broadcast(&psbt, burn_amount)?, psbt)
+    })
+  }
 
-    let mut fee = 0;
-    for txin in unsigned_transaction.input.iter() {
-      let Some(txout) = unspent_outputs.get(&txin.previous_output) else {
-        panic!("input {} not found in utxos", txin.previous_output);
-      };
-      fee += txout.value.to_sat();
+  pub(super) fn finalize_and_broadcast(
+    &self,
+    psbt: &str,
+    burn_amount: Option<Amount>,
+  ) -> Result<Txid> {
+    let finalized = self.bitcoin_client().finalize_psbt(psbt, None)?;
+
+    let signed_tx = finalized
+      .hex
+      .filter(|_| finalized.complete)
+      .ok_or_else(|| anyhow!("transaction is not fully signed"))?;
+
+    self.send_raw_transaction(&signed_tx, burn_amount)
+  }
+
+  /// Adds annotations to each input and output of `psbt`, recording the
+  /// inscriptions and runes they hold, which are otherwise invisible to
+  /// external signers.
+  pub(crate) fn annotate(&self, psbt: &mut Psbt) -> Result {
+    let outpoints = psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>();
+
+    let foreign = outpoints
+      .iter()
+      .filter(|outpoint| !self.output_info.contains_key(outpoint))
+      .copied()
+      .collect::<Vec<OutPoint>>();
+
+    let foreign = foreign
+      .iter()
+      .copied()
+      .zip(self.get_output_info(&foreign)?)
+      .collect::<BTreeMap<OutPoint, api::Output>>();
+
+    let mut satpoints = self
+      .inscription_info
+      .iter()
+      .map(|(id, info)| (*id, info.satpoint))
+      .collect::<BTreeMap<InscriptionId, SatPoint>>();
+
+    let unknown = foreign
+      .values()
+      .flat_map(|output| output.inscriptions.iter().copied())
+      .filter(|id| !satpoints.contains_key(id))
+      .collect::<Vec<InscriptionId>>();
+
+    if !unknown.is_empty() {
+      let response = self
+        .ord_client
+        .post(self.rpc_url.join("/inscriptions")?)
+        .json(&unknown)
+        .header(header::ACCEPT, "application/json")
+        .send()?;
+
+      if !response.status().is_success() {
+        bail!("wallet failed get inscriptions: {}", response.text()?);
+      }
+
+      for info in serde_json::from_str::<Vec<api::Inscription>>(&response.text()?)? {
+        satpoints.insert(info.id, info.satpoint);
+      }
     }
 
-    for txout in unsigned_transaction.output.iter() {
-      fee = fee.checked_sub(txout.value.to_sat()).unwrap();
+    let mut rune_ids = BTreeMap::new();
+
+    let mut inputs = Vec::new();
+
+    for (outpoint, input) in outpoints.iter().zip(&psbt.inputs) {
+      let output = self
+        .output_info
+        .get(outpoint)
+        .or_else(|| foreign.get(outpoint))
+        .unwrap();
+
+      let inscriptions = output
+        .inscriptions
+        .iter()
+        .map(|id| (*id, satpoints[id].offset))
+        .collect();
+
+      let mut runes = Vec::new();
+
+      for (spaced_rune, pile) in &output.runes {
+        let id = match rune_ids.get(&spaced_rune.rune) {
+          Some(id) => *id,
+          None => {
+            let (id, _entry, _parent) = self
+              .get_rune(spaced_rune.rune)?
+              .with_context(|| format!("rune `{spaced_rune}` has not been etched"))?;
+
+            rune_ids.insert(spaced_rune.rune, id);
+
+            id
+          }
+        };
+
+        runes.push((id, pile.amount));
+      }
+
+      let value = input
+        .witness_utxo
+        .as_ref()
+        .map(|tx_out| tx_out.value)
+        .unwrap_or(Amount::from_sat(output.value));
+
+      inputs.push((value, inscriptions, runes));
     }
 
-    Ok((txid, psbt, fee))
+    let (inputs, outputs) = Annotations::for_transaction(&psbt.unsigned_tx, &inputs);
+
+    for (input, annotations) in psbt.inputs.iter_mut().zip(inputs) {
+      annotations.encode(&mut input.proprietary);
+    }
+
+    for (output, annotations) in psbt.outputs.iter_mut().zip(outputs) {
+      annotations.encode(&mut output.proprietary);
+    }
+
+    Ok(())
   }
 
   fn send_raw_transaction<R: bitcoincore_rpc::RawTx>(
@@ -0,0 +1,348 @@
+//! Proprietary PSBT fields, under the `ord` prefix, describing the
+//! inscriptions and runes held by each input and received by each output, so
+//! that they can be reviewed on an external signer before signing.
+//!
+//! Inscriptions use subtype 0, with the 32 byte txid and 4 byte little endian
+//! index of the inscription ID as key, and the varint encoded offset of the
+//! inscription within the input or output as value. Runes use subtype 1, with
+//! the varint encoded block and transaction index of the rune ID as key, and
+//! the varint encoded amount as value.
+
+use {super::*, bitcoin::psbt::raw::ProprietaryKey};
+
+const PREFIX: &[u8] = b"ord";
+
+const INSCRIPTION: u8 = 0;
+const RUNE: u8 = 1;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Annotations {
+  pub inscriptions: Vec<(InscriptionId, u64)>,
+  pub runes: Vec<(RuneId, u128)>,
+}
+
+impl Annotations {
+  pub fn decode(proprietary: &BTreeMap<ProprietaryKey, Vec<u8>>) -> Result<Self> {
+    let mut annotations = Self::default();
+
+    for (key, value) in proprietary {
+      if key.prefix != PREFIX {
+        continue;
+      }
+
+      match key.subtype {
+        INSCRIPTION => {
+          ensure!(key.key.len() == 36, "invalid inscription annotation key");
+
+          let id = InscriptionId {
+            txid: Txid::from_slice(&key.key[..32])?,
+            index: u32::from_le_bytes(key.key[32..].try_into().unwrap()),
+          };
+
+          let offset = Self::decode_varint(value)?;
+
+          annotations.inscriptions.push((id, u64::try_from(offset)?));
+        }
+        RUNE => {
+          let (block, length) = varint::decode(&key.key)?;
+          let (tx, _) = varint::decode(&key.key[length..])?;
+
+          let id = RuneId {
+            block: u64::try_from(block)?,
+            tx: u32::try_from(tx)?,
+          };
+
+          annotations.runes.push((id, Self::decode_varint(value)?));
+        }
+        subtype => bail!("unknown annotation subtype {subtype}"),
+      }
+    }
+
+    Ok(annotations)
+  }
+
+  pub(crate) fn encode(&self, proprietary: &mut BTreeMap<ProprietaryKey, Vec<u8>>) {
+    for (id, offset) in &self.inscriptions {
+      let mut key = id.txid.to_byte_array().to_vec();
+      key.extend_from_slice(&id.index.to_le_bytes());
+
+      proprietary.insert(
+        ProprietaryKey {
+          prefix: PREFIX.into(),
+          subtype: INSCRIPTION,
+          key,
+        },
+        varint::encode((*offset).into()),
+      );
+    }
+
+    for (id, amount) in &self.runes {
+      let mut key = Vec::new();
+      varint::encode_to_vec(id.block.into(), &mut key);
+      varint::encode_to_vec(id.tx.into(), &mut key);
+
+      proprietary.insert(
+        ProprietaryKey {
+          prefix: PREFIX.into(),
+          subtype: RUNE,
+          key,
+        },
+        varint::encode(*amount),
+      );
+    }
+  }
+
+  fn decode_varint(buffer: &[u8]) -> Result<u128> {
+    let (n, length) = varint::decode(buffer)?;
+    ensure!(length == buffer.len(), "trailing bytes after varint");
+    Ok(n)
+  }
+
+  /// Computes annotations for each input and output of `transaction`.
+  /// Inscriptions are tracked by sat offset. Runes are allocated following
+  /// the runestone's edicts and pointer, with cenotaphs burning all runes,
+  /// but runes minted or etched by the transaction are not included.
+  pub(crate) fn for_transaction(
+    transaction: &Transaction,
+    inputs: &[(Amount, Vec<(InscriptionId, u64)>, Vec<(RuneId, u128)>)],
+  ) -> (Vec<Self>, Vec<Self>) {
+    let mut input_annotations = Vec::new();
+    let mut located = Vec::new();
+    let mut unallocated = BTreeMap::<RuneId, u128>::new();
+
+    let mut start = 0;
+
+    for (value, inscriptions, runes) in inputs {
+      for (id, offset) in inscriptions {
+        located.push((start + offset, *id));
+      }
+
+      for (id, amount) in runes {
+        *unallocated.entry(*id).or_default() += amount;
+      }
+
+      input_annotations.push(Self {
+        inscriptions: inscriptions.clone(),
+        runes: runes.clone(),
+      });
+
+      start += value.to_sat();
+    }
+
+    let mut output_annotations = Self::allocate_runes(transaction, unallocated)
+      .into_iter()
+      .map(|runes| Self {
+        inscriptions: Vec::new(),
+        runes: runes.into_iter().collect(),
+      })
+      .collect::<Vec<Self>>();
+
+    located.sort();
+
+    let mut start = 0;
+
+    for (annotation, tx_out) in output_annotations.iter_mut().zip(&transaction.output) {
+      let end = start + tx_out.value.to_sat();
+
+      for (offset, id) in &located {
+        if *offset >= start && *offset < end {
+          annotation.inscriptions.push((*id, offset - start));
+        }
+      }
+
+      start = end;
+    }
+
+    (input_annotations, output_annotations)
+  }
+
+  fn allocate_runes(
+    transaction: &Transaction,
+    mut unallocated: BTreeMap<RuneId, u128>,
+  ) -> Vec<BTreeMap<RuneId, u128>> {
+    let mut allocated = vec![BTreeMap::<RuneId, u128>::new(); transaction.output.len()];
+
+    let artifact = Runestone::decipher(transaction);
+
+    let runestone = match artifact {
+      Some(Artifact::Cenotaph(_)) => return allocated,
+      Some(Artifact::Runestone(runestone)) => Some(runestone),
+      None => None,
+    };
+
+    let destinations = transaction
+      .output
+      .iter()
+      .enumerate()
+      .filter_map(|(output, tx_out)| (!tx_out.script_pubkey.is_op_return()).then_some(output))
+      .collect::<Vec<usize>>();
+
+    for Edict { id, amount, output } in runestone
+      .iter()
+      .flat_map(|runestone| runestone.edicts.iter().copied())
+    {
+      let output = usize::try_from(output).unwrap();
+
+      let Some(balance) = unallocated.get_mut(&id) else {
+        continue;
+      };
+
+      let mut allocate = |balance: &mut u128, amount: u128, output: usize| {
+        if amount > 0 {
+          *balance -= amount;
+          *allocated[output].entry(id).or_default() += amount;
+        }
+      };
+
+      if output == transaction.output.len() {
+        if destinations.is_empty() {
+          continue;
+        }
+
+        if amount == 0 {
+          let share = *balance / destinations.len() as u128;
+          let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
+
+          for (i, output) in destinations.iter().enumerate() {
+            allocate(
+              balance,
+              if i < remainder { share + 1 } else { share },
+              *output,
+            );
+          }
+        } else {
+          for output in &destinations {
+            allocate(balance, amount.min(*balance), *output);
+          }
+        }
+      } else {
+        let amount = if amount == 0 {
+          *balance
+        } else {
+          amount.min(*balance)
+        };
+
+        allocate(balance, amount, output);
+      }
+    }
+
+    let pointer = runestone
+      .and_then(|runestone| runestone.pointer)
+      .map(|pointer| usize::try_from(pointer).unwrap())
+      .or_else(|| destinations.first().copied());
+
+    if let Some(vout) = pointer {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          *allocated[vout].entry(id).or_default() += balance;
+        }
+      }
+    }
+
+    allocated
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let annotations = Annotations {
+      inscriptions: vec![(inscription_id(1), 0), (inscription_id(2), 1000)],
+      runes: vec![(
+        RuneId {
+          block: 840000,
+          tx: 1,
+        },
+        21_000_000,
+      )],
+    };
+
+    let mut proprietary = BTreeMap::new();
+
+    annotations.encode(&mut proprietary);
+
+    assert_eq!(Annotations::decode(&proprietary).unwrap(), annotations);
+  }
+
+  #[test]
+  fn other_prefixes_are_ignored() {
+    let mut proprietary = BTreeMap::new();
+
+    proprietary.insert(
+      ProprietaryKey {
+        prefix: b"foo".into(),
+        subtype: 0,
+        key: Vec::new(),
+      },
+      Vec::new(),
+    );
+
+    assert_eq!(
+      Annotations::decode(&proprietary).unwrap(),
+      Annotations::default()
+    );
+  }
+
+  #[test]
+  fn runes_follow_runestone() {
+    let id = RuneId { block: 1, tx: 1 };
+
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          script_pubkey: Runestone {
+            edicts: vec![Edict {
+              id,
+              amount: 100,
+              output: 2,
+            }],
+            pointer: Some(1),
+            ..default()
+          }
+          .encipher(),
+          value: Amount::ZERO,
+        },
+        TxOut {
+          script_pubkey: ScriptBuf::new(),
+          value: Amount::from_sat(1000),
+        },
+        TxOut {
+          script_pubkey: ScriptBuf::new(),
+          value: Amount::from_sat(1000),
+        },
+      ],
+    };
+
+    let (inputs, outputs) = Annotations::for_transaction(
+      &transaction,
+      &[(
+        Amount::from_sat(2000),
+        vec![(inscription_id(1), 1500)],
+        vec![(id, 1000)],
+      )],
+    );
+
+    assert_eq!(inputs[0].runes, [(id, 1000)]);
+    assert_eq!(outputs[0], Annotations::default());
+    assert_eq!(
+      outputs[1],
+      Annotations {
+        inscriptions: Vec::new(),
+        runes: vec![(id, 900)],
+      }
+    );
+    assert_eq!(
+      outputs[2],
+      Annotations {
+        inscriptions: vec![(inscription_id(1), 500)],
+        runes: vec![(id, 100)],
+      }
+    );
+  }
+}
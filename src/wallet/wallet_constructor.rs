@@ -47,7 +47,7 @@ impl WalletConstructor {
   pub(crate) fn build(self) -> Result<Wallet> {
     let database = Wallet::open_database(&self.name, &self.settings)?;
 
-    let bitcoin_client = {
+    let (bitcoin_client, watch_only) = {
       let client =
         Wallet::check_version(self.settings.bitcoin_rpc_client(Some(self.name.clone()))?)?;
 
@@ -55,7 +55,9 @@ impl WalletConstructor {
         client.load_wallet(&self.name)?;
       }
 
-      if client.get_wallet_info()?.private_keys_enabled {
+      let watch_only = !client.get_wallet_info()?.private_keys_enabled;
+
+      if !watch_only {
         Wallet::check_descriptors(
           &self.name,
           client
@@ -64,7 +66,7 @@ impl WalletConstructor {
         )?;
       }
 
-      client
+      (client, watch_only)
     };
 
     let chain_block_count = bitcoin_client.get_block_count().unwrap() + 1;
@@ -116,6 +118,7 @@ impl WalletConstructor {
       rpc_url: self.rpc_url,
       settings: self.settings,
       utxos,
+      watch_only,
     })
   }
 
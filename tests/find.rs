@@ -1,6 +1,6 @@
 use {
   super::*,
-  ord::subcommand::find::{FindRangeOutput, Output},
+  ord::subcommand::find::{ContentOutput, FindRangeOutput, Output},
 };
 
 #[test]
@@ -89,3 +89,80 @@ fn no_satoshi_index() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn find_content() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (first, _reveal) = inscribe(&core, &ord);
+  let (second, _reveal) = inscribe(&core, &ord);
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--index-content-hashes find --content foo.txt")
+      .write("foo.txt", "FOO")
+      .core(&core)
+      .run_and_deserialize_output::<ContentOutput>(),
+    ContentOutput {
+      content_hash: "9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3"
+        .parse()
+        .unwrap(),
+      inscriptions: vec![first, second],
+    }
+  );
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--index-content-hashes find --content bar.txt")
+      .write("bar.txt", "BAR")
+      .core(&core)
+      .run_and_deserialize_output::<ContentOutput>()
+      .inscriptions,
+    Vec::new(),
+  );
+}
+
+#[test]
+fn find_content_matches_compressed_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let Batch { inscriptions, .. } =
+    CommandBuilder::new("wallet inscribe --compress --file foo.txt --fee-rate 1")
+      .write("foo.txt", [0; 350_000])
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output();
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--index-content-hashes find --content foo.txt")
+      .write("foo.txt", [0; 350_000])
+      .core(&core)
+      .run_and_deserialize_output::<ContentOutput>()
+      .inscriptions,
+    vec![inscriptions[0].id],
+  );
+}
+
+#[test]
+fn find_content_requires_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("find --content foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .expected_stderr(
+      "error: find --content requires index created with `--index-content-hashes` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, ord::subcommand::index::snapshot::Manifest};
 
 #[test]
 fn run_is_an_alias_for_update() {
@@ -90,3 +90,232 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn events_requires_event_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("index events")
+    .core(&core)
+    .expected_stderr(
+      "error: `ord index events` requires index created with `--index-events` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn events_are_replayed_from_height() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let events = CommandBuilder::new("--index-events index events")
+    .core(&core)
+    .run_and_deserialize_output::<Vec<api::JournaledEvent>>();
+
+  assert_eq!(events.len(), 1);
+  assert_eq!(events[0].height, 2);
+  assert_eq!(events[0].tx_index, 1);
+  assert_eq!(
+    events[0].event,
+    ord::index::event::Event::InscriptionCreated {
+      block_height: 2,
+      charms: 0,
+      inscription_id: InscriptionId { txid, index: 0 },
+      location: Some(SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      }),
+      parent_inscription_ids: Vec::new(),
+      sequence_number: 0,
+    }
+  );
+
+  let events = CommandBuilder::new("--index-events index events --from-height 3")
+    .core(&core)
+    .run_and_deserialize_output::<Vec<api::JournaledEvent>>();
+
+  assert!(events.is_empty());
+}
+
+#[test]
+fn snapshot_can_be_restored() {
+  let core = mockcore::spawn();
+  let blocks = core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+  let restored_path = tempdir.path().join("restored.redb");
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  let manifest = CommandBuilder::new(format!(
+    "--index {} index snapshot create {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<Manifest>();
+
+  assert_eq!(manifest.height, 2);
+  assert_eq!(manifest.block_hash, blocks[1].block_hash());
+  assert_eq!(manifest.chain, Chain::Mainnet);
+  assert!(manifest.index_flags["index_inscriptions"]);
+  assert!(!manifest.index_flags["index_sats"]);
+  assert_eq!(manifest.table_lengths["HEIGHT_TO_BLOCK_HEADER"], 3);
+
+  assert_eq!(
+    manifest.content_hash,
+    <bitcoin::hashes::sha256::Hash as bitcoin::hashes::Hash>::hash(&fs::read(&index_path).unwrap()),
+  );
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} index snapshot restore {}",
+      restored_path.display(),
+      snapshot_path.display(),
+    ))
+    .core(&core)
+    .run_and_deserialize_output::<Manifest>(),
+    manifest,
+  );
+
+  assert_eq!(
+    fs::read(&restored_path).unwrap(),
+    fs::read(&index_path).unwrap(),
+  );
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot restore {}",
+    restored_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .expected_stderr(format!(
+    "error: index file `{}` already exists\n",
+    restored_path.display()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_restore_requires_matching_settings() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot create {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<Manifest>();
+
+  CommandBuilder::new(format!(
+    "--index-sats --index {} index snapshot restore {}",
+    tempdir.path().join("restored.redb").display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .expected_stderr(
+    "error: snapshot was created without `--index-sats`, which does not match settings\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_restore_requires_matching_chain() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+  let restored_path = tempdir.path().join("restored.redb");
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  let manifest = CommandBuilder::new(format!(
+    "--index {} index snapshot create {}",
+    index_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<Manifest>();
+
+  let other = mockcore::spawn();
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot restore {}",
+    restored_path.display(),
+    snapshot_path.display(),
+  ))
+  .core(&other)
+  .expected_stderr(format!(
+    "error: snapshot block {} at height 1 is not in the active chain\n",
+    manifest.block_hash,
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(!restored_path.exists());
+}
+
+#[test]
+fn rollback_restores_savepoint() {
+  let core = mockcore::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+
+  for _ in 0..2 {
+    core.mine_blocks(1);
+
+    CommandBuilder::new(format!("--index {} index update", index_path.display()))
+      .core(&core)
+      .run_and_extract_stdout();
+  }
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} index rollback --to-height 1",
+      index_path.display()
+    ))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::rollback::Output>(),
+    ord::subcommand::index::rollback::Output { height: 1 },
+  );
+
+  CommandBuilder::new(format!(
+    "--index {} index rollback --to-height 0",
+    index_path.display()
+  ))
+  .core(&core)
+  .expected_stderr("error: no savepoint at or below height 0, available savepoints: 1\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -17,6 +17,9 @@ fn json_with_satoshi_index() {
   "outputs_traversed": 1,
   "page_size": \d+,
   "sat_ranges": 1,
+  "savepoints": \[
+    0
+  \],
   "stored_bytes": \d+,
   "tables": .*,
   "total_bytes": \d+,
@@ -51,6 +54,9 @@ fn json_without_satoshi_index() {
   "outputs_traversed": 0,
   "page_size": \d+,
   "sat_ranges": 0,
+  "savepoints": \[
+    0
+  \],
   "stored_bytes": \d+,
   "tables": .*,
   "total_bytes": \d+,
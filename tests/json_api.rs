@@ -170,16 +170,20 @@ fn get_inscription() {
       charms: vec![Charm::Coin, Charm::Uncommon],
       child_count: 0,
       children: Vec::new(),
+      content_duplicates: None,
+      content_hash: None,
       content_length: Some(3),
       content_type: Some("text/plain;charset=utf-8".to_string()),
       effective_content_type: Some("text/plain;charset=utf-8".to_string()),
       fee: 138,
+      first_with_content: None,
       height: 2,
       id: inscription_id,
       number: 0,
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending_transfer: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -226,16 +230,20 @@ fn get_inscription_with_metaprotocol() {
       charms: vec![Charm::Coin, Charm::Uncommon],
       child_count: 0,
       children: Vec::new(),
+      content_duplicates: None,
+      content_hash: None,
       content_length: Some(3),
       content_type: Some("text/plain;charset=utf-8".to_string()),
       effective_content_type: Some("text/plain;charset=utf-8".to_string()),
       fee: 140,
+      first_with_content: None,
       height: 2,
       id: output.inscriptions[0].id,
       number: 0,
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending_transfer: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -412,6 +420,7 @@ fn get_output() {
         InscriptionId { txid, index: 2 },
       ],
       indexed: true,
+      pending_spend: None,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![
         (5000000000, 10000000000,),
@@ -585,6 +594,36 @@ fn get_status() {
   );
 }
 
+#[test]
+fn get_status_testnet4() {
+  let core = mockcore::builder().testnet4().build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--chain", "testnet4"], &[]);
+
+  create_wallet(&core, &ord);
+  core.mine_blocks(1);
+
+  let (inscription, _reveal) = inscribe(&core, &ord);
+
+  let status =
+    serde_json::from_str::<api::Status>(&ord.json_request("/status").text().unwrap()).unwrap();
+
+  assert_eq!(status.chain, Chain::Testnet4);
+  assert_eq!(status.height, Some(3));
+  assert_eq!(status.inscriptions, 1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let address = serde_json::from_str::<api::Inscription>(&response.text().unwrap())
+    .unwrap()
+    .address
+    .unwrap();
+
+  assert!(address.starts_with("tb1"), "{address}");
+}
+
 #[test]
 fn get_runes() {
   let core = mockcore::builder().network(Network::Regtest).build();
@@ -615,6 +654,7 @@ fn get_runes() {
         terms: None,
         divisibility: 0,
         etching: a.output.reveal,
+        holders: 1,
         mints: 0,
         number: 0,
         premine: 1000,
@@ -653,6 +693,7 @@ fn get_runes() {
             terms: None,
             divisibility: 0,
             etching: c.output.reveal,
+            holders: 1,
             mints: 0,
             number: 2,
             premine: 1000,
@@ -673,6 +714,7 @@ fn get_runes() {
             terms: None,
             divisibility: 0,
             etching: b.output.reveal,
+            holders: 1,
             mints: 0,
             number: 1,
             premine: 1000,
@@ -693,6 +735,7 @@ fn get_runes() {
             terms: None,
             divisibility: 0,
             etching: a.output.reveal,
+            holders: 1,
             mints: 0,
             number: 0,
             premine: 1000,
@@ -815,6 +858,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: BTreeMap::new(),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -859,6 +903,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: expected_runes,
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -893,6 +938,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending_spend: None,
       runes: BTreeMap::new(),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -61,6 +61,7 @@ mod index;
 mod info;
 mod json_api;
 mod list;
+mod mempool;
 mod parse;
 mod runes;
 mod server;
@@ -77,9 +78,18 @@ const RUNE: u128 = 99246114928149462;
 type Balance = ord::subcommand::wallet::balance::Output;
 type Balances = ord::subcommand::balances::Output;
 type Batch = ord::wallet::batch::Output;
+type Broadcast = ord::subcommand::wallet::broadcast::Output;
+type Bump = ord::subcommand::wallet::bump::Output;
+type Cancel = ord::subcommand::wallet::cancel::Output;
+type Consolidate = ord::subcommand::wallet::consolidate::Output;
 type Create = ord::subcommand::wallet::create::Output;
+type Extract = ord::subcommand::wallet::extract::Output;
 type Inscriptions = Vec<ord::subcommand::wallet::inscriptions::Output>;
+type OfferAccept = ord::subcommand::wallet::offer::accept::Output;
+type OfferCreate = ord::subcommand::wallet::offer::create::Output;
+type OfferInspect = ord::subcommand::wallet::offer::inspect::Output;
 type Send = ord::subcommand::wallet::send::Output;
+type SendMany = Vec<ord::subcommand::wallet::send_many::Output>;
 type Split = ord::subcommand::wallet::split::Output;
 type Supply = ord::subcommand::supply::Output;
 
@@ -361,6 +371,8 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   <dd>.*</dd>
   <dt>burned</dt>
   <dd>0 {symbol}</dd>
+  <dt>holders</dt>
+  <dd>\d+</dd>
   <dt>divisibility</dt>
   <dd>{divisibility}</dd>
   <dt>symbol</dt>
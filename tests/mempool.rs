@@ -0,0 +1,195 @@
+use {super::*, ord::subcommand::wallet::send::Output as Send};
+
+fn wait_for_mempool(ord: &TestServer, condition: impl Fn(&api::Mempool) -> bool) -> api::Mempool {
+  for _ in 0..100 {
+    let mempool =
+      serde_json::from_str::<api::Mempool>(&ord.json_request("/mempool").text().unwrap()).unwrap();
+
+    if condition(&mempool) {
+      return mempool;
+    }
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  panic!("mempool did not reach expected state");
+}
+
+#[test]
+fn mempool_requires_flag() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  let response = ord.json_request("/mempool");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert_eq!(response.text().unwrap(), "mempool not found");
+}
+
+#[test]
+fn mempool_tracks_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &["--mempool"]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let inscription = batch.inscriptions[0].id;
+
+  let mempool = wait_for_mempool(&ord, |mempool| !mempool.transactions.is_empty());
+
+  assert_eq!(
+    mempool.transactions,
+    [api::MempoolTransaction {
+      inscriptions: vec![api::MempoolInscription {
+        id: inscription,
+        new_satpoint: Some(SatPoint {
+          outpoint: OutPoint {
+            txid: batch.reveal,
+            vout: 0,
+          },
+          offset: 0,
+        }),
+        old_satpoint: None,
+      }],
+      runes: BTreeMap::new(),
+      runestone: None,
+      txid: batch.reveal,
+    }],
+  );
+
+  core.mine_blocks(1);
+
+  wait_for_mempool(&ord, |mempool| mempool.transactions.is_empty());
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let mempool = wait_for_mempool(&ord, |mempool| !mempool.transactions.is_empty());
+
+  assert_eq!(
+    mempool.transactions[0].inscriptions,
+    [api::MempoolInscription {
+      id: inscription,
+      new_satpoint: Some(SatPoint {
+        outpoint: OutPoint {
+          txid: send.txid,
+          vout: 0,
+        },
+        offset: 0,
+      }),
+      old_satpoint: Some(SatPoint {
+        outpoint: OutPoint {
+          txid: batch.reveal,
+          vout: 0,
+        },
+        offset: 0,
+      }),
+    }],
+  );
+
+  let transaction = serde_json::from_str::<api::MempoolTransaction>(
+    &ord
+      .json_request(format!("/mempool/{}", send.txid))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(transaction, mempool.transactions[0]);
+
+  let info = serde_json::from_str::<api::Inscription>(
+    &ord
+      .json_request(format!("/inscription/{inscription}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(info.pending_transfer, Some(send.txid));
+
+  let output = serde_json::from_str::<api::Output>(
+    &ord
+      .json_request(format!("/output/{}:0", batch.reveal))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(output.pending_spend, Some(send.txid));
+
+  core.mine_blocks(1);
+
+  wait_for_mempool(&ord, |mempool| mempool.transactions.is_empty());
+
+  let info = serde_json::from_str::<api::Inscription>(
+    &ord
+      .json_request(format!("/inscription/{inscription}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(info.pending_transfer, None);
+}
+
+#[test]
+fn mempool_tracks_rune_transfers() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord =
+    TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &["--mempool"]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 750:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let mempool = wait_for_mempool(&ord, |mempool| !mempool.transactions.is_empty());
+
+  assert_eq!(mempool.transactions.len(), 1);
+
+  let transaction = &mempool.transactions[0];
+
+  assert_eq!(transaction.txid, send.txid);
+  assert!(transaction.inscriptions.is_empty());
+  assert_eq!(
+    transaction.runes,
+    [(
+      SpacedRune::new(Rune(RUNE), 0),
+      Pile {
+        amount: 1000,
+        divisibility: 0,
+        symbol: Some('¢'),
+      },
+    )]
+    .into(),
+  );
+
+  let Some(Artifact::Runestone(runestone)) = &transaction.runestone else {
+    panic!("mempool transaction has no runestone");
+  };
+
+  assert_eq!(runestone.edicts.len(), 1);
+  assert_eq!(runestone.edicts[0].amount, 750);
+}
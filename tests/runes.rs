@@ -52,6 +52,7 @@ fn one_rune() {
           etching: etch.output.reveal,
           id: RuneId { block: 7, tx: 1 },
           terms: None,
+          holders: 1,
           mints: 0,
           number: 0,
           premine: 1000,
@@ -98,6 +99,7 @@ fn two_runes() {
             etching: a.output.reveal,
             id: RuneId { block: 7, tx: 1 },
             terms: None,
+            holders: 1,
             mints: 0,
             number: 0,
             premine: 1000,
@@ -121,6 +123,7 @@ fn two_runes() {
             etching: b.output.reveal,
             id: RuneId { block: 14, tx: 1 },
             terms: None,
+            holders: 1,
             mints: 0,
             number: 1,
             premine: 1000,
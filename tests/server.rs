@@ -254,7 +254,10 @@ fn inscription_page() {
       charms: 0,
       child_count: 0,
       children: Vec::new(),
+      content_duplicates: None,
+      content_hash: None,
       fee: 138,
+      first_with_content: None,
       height: 2,
       inscription: Inscription {
         content_type: Some("text/plain;charset=utf-8".as_bytes().into()),
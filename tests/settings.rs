@@ -21,13 +21,23 @@ fn default() {
   "hidden": \[\],
   "http_port": null,
   "index": ".*index\.redb",
+  "index_address_history": false,
   "index_addresses": false,
+  "index_blk_files": false,
   "index_cache_size": \d+,
+  "index_content_hashes": false,
+  "index_events": false,
+  "index_inscription_attributes": false,
+  "index_inscription_history": false,
+  "index_metaprotocols": false,
+  "index_rune_activity": false,
   "index_runes": false,
   "index_sats": false,
   "index_transactions": false,
   "integration_test": false,
   "no_index_inscriptions": false,
+  "savepoint_count": 2,
+  "savepoint_interval": 10,
   "server_password": null,
   "server_url": null,
   "server_username": null
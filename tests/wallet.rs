@@ -4,14 +4,20 @@ mod addresses;
 mod authentication;
 mod balance;
 mod batch_command;
+mod broadcast;
+mod bump;
 mod burn;
+mod cancel;
 mod cardinals;
+mod consolidate;
 mod create;
 mod dump;
+mod extract;
 mod inscribe;
 mod inscriptions;
 mod label;
 mod mint;
+mod offer;
 mod outputs;
 #[cfg(unix)]
 mod pending;
@@ -23,6 +29,7 @@ mod runics;
 mod sats;
 mod selection;
 mod send;
+mod send_many;
 mod sign;
 mod split;
 mod transactions;
@@ -0,0 +1,230 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, secp256k1::Secp256k1},
+  ord::wallet::{annotations::Annotations, Descriptor},
+};
+
+fn restore_watch_only(core: &mockcore::Handle, ord: &TestServer) {
+  let secp = Secp256k1::new();
+
+  let descriptors = [
+    "tr([c0b9536d/86'/1'/0']tprv8fXhtVjj3vb7kgxKuiWXzcUsur44gbLbbtwxL4HKmpzkBNoMrYqbQhMe7MWhrZjLFc9RBpTRYZZkrS8HH1Q3SmD5DkfpjKqtd97q1JWfqzr/0/*)",
+    "tr([c0b9536d/86'/1'/0']tprv8fXhtVjj3vb7kgxKuiWXzcUsur44gbLbbtwxL4HKmpzkBNoMrYqbQhMe7MWhrZjLFc9RBpTRYZZkrS8HH1Q3SmD5DkfpjKqtd97q1JWfqzr/1/*)",
+  ]
+  .iter()
+  .enumerate()
+  .map(|(i, descriptor)| {
+    let (descriptor, _key_map) =
+      miniscript::Descriptor::parse_descriptor(&secp, descriptor).unwrap();
+
+    Descriptor {
+      desc: descriptor.to_string(),
+      timestamp: bitcoincore_rpc::json::Timestamp::Time(0),
+      active: true,
+      internal: Some(i == 1),
+      range: Some((0, 1000)),
+      next: Some(0),
+    }
+  })
+  .collect();
+
+  CommandBuilder::new("wallet restore --from descriptor")
+    .stdin(
+      serde_json::to_string(&ListDescriptorsResult {
+        wallet_name: "ord".into(),
+        descriptors,
+      })
+      .unwrap()
+      .into_bytes(),
+    )
+    .core(core)
+    .ord(ord)
+    .run_and_extract_stdout();
+}
+
+fn decode(psbt: &str) -> Psbt {
+  Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap()
+}
+
+fn sign(mut psbt: Psbt) -> String {
+  for input in &mut psbt.inputs {
+    input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+  }
+
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+#[test]
+fn watch_only_wallet_refuses_commands_requiring_private_keys() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  restore_watch_only(&core, &ord);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: wallet `ord` is watch-only, and this command requires private keys\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_send_returns_annotated_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  restore_watch_only(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap();
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    recipient: Some(address.require_network(Network::Bitcoin).unwrap()),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let inscription = InscriptionId { txid, index: 0 };
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+
+  let psbt = decode(&send.psbt);
+
+  assert_eq!(psbt.unsigned_tx.compute_txid(), send.txid);
+  assert!(psbt
+    .inputs
+    .iter()
+    .all(|input| input.final_script_witness.is_none()));
+
+  assert_eq!(
+    Annotations::decode(&psbt.inputs[0].proprietary).unwrap(),
+    Annotations {
+      inscriptions: vec![(inscription, 0)],
+      runes: Vec::new(),
+    }
+  );
+
+  assert_eq!(
+    Annotations::decode(&psbt.outputs[0].proprietary).unwrap(),
+    Annotations {
+      inscriptions: vec![(inscription, 0)],
+      runes: Vec::new(),
+    }
+  );
+
+  CommandBuilder::new(format!("wallet broadcast --psbt {}", send.psbt))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: transaction is not fully signed\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let broadcast = CommandBuilder::new(format!("wallet broadcast --psbt {}", sign(psbt)))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Broadcast>();
+
+  assert_eq!(broadcast.txid, send.txid);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), send.txid);
+}
+
+#[test]
+fn broadcast_requires_burn_amount_for_op_return_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  restore_watch_only(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap();
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    recipient: Some(address.require_network(Network::Bitcoin).unwrap()),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let inscription = InscriptionId { txid, index: 0 };
+
+  let burn = CommandBuilder::new(format!("wallet burn --fee-rate 1 {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+
+  let psbt = sign(decode(&burn.psbt));
+
+  CommandBuilder::new(format!("wallet broadcast --psbt {psbt}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: PSBT burns 1 sat to OP_RETURN outputs, more than `--burn-amount` of 0 sat\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+
+  let broadcast = CommandBuilder::new(format!("wallet broadcast --burn-amount 1sat --psbt {psbt}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Broadcast>();
+
+  assert_eq!(broadcast.txid, burn.txid);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), burn.txid);
+}
@@ -0,0 +1,255 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, Transaction},
+};
+
+fn send(core: &mockcore::Handle, ord: &TestServer) -> Transaction {
+  let output =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .core(core)
+      .ord(ord)
+      .run_and_deserialize_output::<Send>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), output.txid);
+
+  mempool[0].clone()
+}
+
+#[test]
+fn replacement_keeps_original_inputs_and_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let original = send(&core, &ord);
+
+  let output = CommandBuilder::new(format!(
+    "wallet bump --txid {} --fee-rate 5",
+    original.compute_txid()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Bump>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let replacement = &mempool[0];
+
+  assert_eq!(replacement.compute_txid(), output.txid);
+  assert_ne!(replacement.compute_txid(), original.compute_txid());
+
+  assert_eq!(
+    replacement.input[..original.input.len()]
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>(),
+    original
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>(),
+  );
+
+  assert_eq!(
+    replacement.output[..original.output.len()],
+    original.output[..]
+  );
+
+  assert_eq!(replacement.output.len(), original.output.len() + 1);
+
+  assert!(output.fee >= 5 * u64::try_from(replacement.vsize()).unwrap());
+
+  core.mine_blocks(1);
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn replacement_preserves_inscription_location() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let original = core.mempool()[0].clone();
+
+  let bump = CommandBuilder::new(format!("wallet bump --txid {} --fee-rate 10", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Bump>();
+
+  let replacement = core.mempool()[0].clone();
+
+  assert_eq!(replacement.compute_txid(), bump.txid);
+  assert_eq!(replacement.output[0], original.output[0]);
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(
+      ".*<dt>location</dt>
+  <dd><a class=collapse href=/satpoint/{0}:0:0>{0}:0:0</a></dd>.*",
+      bump.txid,
+    ),
+  );
+}
+
+#[test]
+fn cpfp_spends_wallet_output_of_stuck_transaction() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let parent = send(&core, &ord);
+
+  let output = CommandBuilder::new(format!(
+    "wallet bump --txid {} --fee-rate 10 --cpfp",
+    parent.compute_txid()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Bump>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 2);
+  assert_eq!(mempool[0], parent);
+
+  let child = &mempool[1];
+
+  assert_eq!(child.compute_txid(), output.txid);
+  assert_eq!(
+    child.input[0].previous_output,
+    OutPoint {
+      txid: parent.compute_txid(),
+      vout: 1,
+    }
+  );
+  assert_eq!(child.input.len(), 1);
+  assert_eq!(child.output.len(), 1);
+
+  assert!(output.fee >= 10 * u64::try_from(child.vsize()).unwrap());
+
+  CommandBuilder::new(format!(
+    "wallet bump --txid {} --fee-rate 20",
+    parent.compute_txid()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: transaction {} has unconfirmed children which a replacement would evict, use \
+    `--cpfp` to bump it instead\n",
+    parent.compute_txid()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let original = send(&core, &ord);
+
+  let output = CommandBuilder::new(format!(
+    "wallet bump --txid {} --fee-rate 5 --dry-run",
+    original.compute_txid()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Bump>();
+
+  assert_eq!(core.mempool(), [original]);
+
+  let psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(output.psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(psbt.unsigned_tx.compute_txid(), output.txid);
+}
+
+#[test]
+fn transaction_must_be_in_mempool() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let txid = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  CommandBuilder::new(format!("wallet bump --txid {txid} --fee-rate 5"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!("error: transaction {txid} is not in the mempool\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn fee_rate_must_exceed_current_fee_rate() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let original = send(&core, &ord);
+
+  CommandBuilder::new(format!(
+    "wallet bump --txid {} --fee-rate 1",
+    original.compute_txid()
+  ))
+  .core(&core)
+  .ord(&ord)
+  .stderr_regex(format!(
+    "error: fee rate must be greater than current fee rate of transaction {}: \\d+\\.\\d\\d sat/vB\n",
+    original.compute_txid()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
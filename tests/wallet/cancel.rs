@@ -0,0 +1,188 @@
+use super::*;
+
+#[test]
+fn cancel_returns_inscription_to_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let cancel = CommandBuilder::new(format!("wallet cancel --txid {} --fee-rate 10", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Cancel>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), cancel.txid);
+  assert_eq!(mempool[0].output[0].value.to_sat(), 10_000);
+  assert_eq!(mempool[0].output.len(), 2);
+
+  core.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(
+    inscriptions[0].location,
+    SatPoint {
+      outpoint: OutPoint {
+        txid: cancel.txid,
+        vout: 0,
+      },
+      offset: 0,
+    }
+  );
+}
+
+#[test]
+fn cancel_returns_runes_to_wallet() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let cancel = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet cancel --txid {} --fee-rate 10",
+    send.txid
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Cancel>();
+
+  core.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balances>();
+
+  pretty_assert_eq!(
+    balances,
+    ord::subcommand::balances::Output {
+      runes: [(
+        SpacedRune::new(Rune(RUNE), 0),
+        [(
+          OutPoint {
+            txid: cancel.txid,
+            vout: 0
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 0,
+            symbol: Some('¢')
+          },
+        )]
+        .into()
+      )]
+      .into(),
+    }
+  );
+}
+
+#[test]
+fn dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  let cancel = CommandBuilder::new(format!(
+    "wallet cancel --txid {} --fee-rate 10 --dry-run",
+    send.txid
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Cancel>();
+
+  assert_ne!(cancel.txid, send.txid);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), send.txid);
+}
+
+#[test]
+fn transaction_must_be_in_mempool() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let txid = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  CommandBuilder::new(format!("wallet cancel --txid {txid} --fee-rate 5"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!("error: transaction {txid} is not in the mempool\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn transaction_must_signal_replaceability() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    fee: 1000,
+    ..default()
+  });
+
+  CommandBuilder::new(format!("wallet cancel --txid {txid} --fee-rate 10"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "error: transaction {txid} does not signal replaceability and cannot be canceled, wait for \
+      it to confirm or use `ord wallet bump --cpfp` to speed it up\n",
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,190 @@
+use super::*;
+
+#[test]
+fn requires_threshold_or_runes() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet consolidate --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .stderr_regex("error: the following required arguments were not provided:.*")
+    .expected_exit_code(2)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn nothing_to_consolidate() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet consolidate --fee-rate 1 --threshold 100btc")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: wallet contains fewer than two outputs to consolidate\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn consolidate_cardinals() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  core.mine_blocks(3);
+
+  CommandBuilder::new("wallet consolidate --fee-rate 1 --threshold 100btc --dry-run")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Consolidate>();
+
+  assert!(core.mempool().is_empty());
+
+  let output = CommandBuilder::new("wallet consolidate --fee-rate 1 --threshold 100btc")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Consolidate>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let tx = &mempool[0];
+
+  assert_eq!(tx.compute_txid(), output.txid);
+  assert!(tx.input.len() >= 3);
+  assert!(tx
+    .input
+    .iter()
+    .all(|txin| txin.previous_output.txid != reveal));
+  assert_eq!(tx.output.len(), 1);
+  assert!(output.fee > 0);
+
+  core.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(inscriptions[0].location.outpoint.txid, reveal);
+}
+
+#[test]
+fn max_inputs_limits_inputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(4);
+
+  CommandBuilder::new("wallet consolidate --fee-rate 1 --threshold 100btc --max-inputs 2")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Consolidate>();
+
+  assert_eq!(core.mempool()[0].input.len(), 2);
+}
+
+#[test]
+fn consolidate_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  let mut splitfile = String::from("outputs:\n");
+
+  for _ in 0..3 {
+    let address = CommandBuilder::new("--regtest wallet receive")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+      .addresses
+      .into_iter()
+      .next()
+      .unwrap()
+      .assume_checked();
+
+    splitfile.push_str(&format!(
+      "- address: {address}\n  runes:\n    {rune}: 100\n"
+    ));
+  }
+
+  CommandBuilder::new("--regtest wallet split --fee-rate 1 --splits splits.yaml")
+    .core(&core)
+    .ord(&ord)
+    .write("splits.yaml", &splitfile)
+    .run_and_deserialize_output::<Split>();
+
+  core.mine_blocks(1);
+
+  let runics = CommandBuilder::new("--regtest --index-runes wallet runics")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::runics::RunicUtxo>>();
+
+  assert_eq!(runics.len(), 4);
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes wallet consolidate --fee-rate 1 --runes --postage 1000sat",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Consolidate>();
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.compute_txid(), output.txid);
+  assert_eq!(tx.input.len(), 4);
+  assert_eq!(tx.output[1].value.to_sat(), 1000);
+
+  let Some(Artifact::Runestone(runestone)) = Runestone::decipher(tx) else {
+    panic!("consolidation transaction does not contain runestone");
+  };
+
+  assert_eq!(runestone.edicts.len(), 1);
+  assert_eq!(runestone.edicts[0].amount, 0);
+  assert_eq!(runestone.edicts[0].output, 1);
+
+  core.mine_blocks(1);
+
+  let runics = CommandBuilder::new("--regtest --index-runes wallet runics")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::runics::RunicUtxo>>();
+
+  assert_eq!(runics.len(), 1);
+  assert_eq!(
+    runics[0].output,
+    OutPoint {
+      txid: output.txid,
+      vout: 1,
+    }
+  );
+  assert_eq!(
+    runics[0].runes,
+    [(SpacedRune::new(rune, 0), "1000".parse().unwrap())].into()
+  );
+}
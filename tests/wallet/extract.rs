@@ -0,0 +1,85 @@
+use {super::*, std::collections::BTreeSet};
+
+#[test]
+fn extract_inscriptions_from_shared_output() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("--index-sats wallet batch --batch batch.yaml --fee-rate 1")
+    .write("inscription.txt", "Hello World")
+    .write("tulip.png", [0; 555])
+    .write("meow.wav", [0; 2048])
+    .write(
+      "batch.yaml",
+      "mode: shared-output\npostage: 30000\ninscriptions:\n- file: inscription.txt\n- file: tulip.png\n- file: meow.wav\n",
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let outpoint = batch.inscriptions[0].location.outpoint;
+
+  assert!(batch
+    .inscriptions
+    .iter()
+    .all(|inscription| inscription.location.outpoint == outpoint));
+
+  let output = CommandBuilder::new(format!(
+    "--index-sats wallet extract --fee-rate 1 --output {outpoint} --postage 1000sat"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Extract>();
+
+  core.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("--index-sats wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 3);
+
+  let mut outpoints = BTreeSet::new();
+
+  for inscription in inscriptions {
+    assert_eq!(
+      inscription.location,
+      output.inscriptions[&inscription.inscription]
+    );
+    assert_eq!(inscription.location.outpoint.txid, output.txid);
+    outpoints.insert(inscription.location.outpoint);
+  }
+
+  assert_eq!(outpoints.len(), 3);
+}
+
+#[test]
+fn output_must_hold_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let outpoint = OutPoint {
+    txid: core.tx(1, 0).compute_txid(),
+    vout: 0,
+  };
+
+  CommandBuilder::new(format!("wallet extract --fee-rate 1 --output {outpoint}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!("error: output {outpoint} holds no inscriptions\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
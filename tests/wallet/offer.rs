@@ -0,0 +1,312 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, transaction::Version, Transaction, TxIn},
+};
+
+const SINGLE_ANYONECANPAY: u8 = 0x83;
+
+fn decode(psbt: &str) -> Psbt {
+  Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap()
+}
+
+fn offer(outpoint: OutPoint, price: Amount, signature: Option<u8>) -> String {
+  let mut psbt = Psbt::from_unsigned_tx(Transaction {
+    version: Version(2),
+    lock_time: bitcoin::absolute::LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: outpoint,
+      script_sig: Default::default(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      value: price,
+      script_pubkey: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        .parse::<Address<NetworkUnchecked>>()
+        .unwrap()
+        .assume_checked()
+        .script_pubkey(),
+    }],
+  })
+  .unwrap();
+
+  if let Some(sighash_type) = signature {
+    let mut signature = vec![0; 64];
+    signature.push(sighash_type);
+    psbt.inputs[0].final_script_witness = Some(Witness::from_slice(&[signature]));
+  }
+
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+fn external_inscription(core: &mockcore::Handle) -> (InscriptionId, OutPoint) {
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  (InscriptionId { txid, index: 0 }, OutPoint { txid, vout: 0 })
+}
+
+#[test]
+fn create_inscription_offer() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let create = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<OfferCreate>();
+
+  let outpoint = OutPoint {
+    txid: reveal,
+    vout: 0,
+  };
+
+  assert_eq!(create.outpoint, outpoint);
+  assert_eq!(create.price, COIN_VALUE);
+
+  let psbt = decode(&create.psbt);
+
+  assert_eq!(psbt.unsigned_tx.input.len(), 1);
+  assert_eq!(psbt.unsigned_tx.input[0].previous_output, outpoint);
+  assert_eq!(psbt.unsigned_tx.output.len(), 1);
+  assert_eq!(psbt.unsigned_tx.output[0].value.to_sat(), COIN_VALUE);
+  assert_eq!(
+    psbt.inputs[0]
+      .final_script_witness
+      .as_ref()
+      .unwrap()
+      .nth(0)
+      .unwrap()[64],
+    SINGLE_ANYONECANPAY,
+  );
+
+  let inspect = CommandBuilder::new(format!("wallet offer inspect --psbt {}", create.psbt))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<OfferInspect>();
+
+  assert_eq!(inspect.inscriptions, [inscription]);
+  assert_eq!(inspect.outpoint, outpoint);
+  assert_eq!(inspect.price, COIN_VALUE);
+  assert!(inspect.runes.is_empty());
+  assert_eq!(inspect.value, 10_000);
+}
+
+#[test]
+fn accept_inscription_offer() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, outpoint) = external_inscription(&core);
+
+  let psbt = offer(
+    outpoint,
+    Amount::from_sat(COIN_VALUE),
+    Some(SINGLE_ANYONECANPAY),
+  );
+
+  let accept = CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --psbt {psbt} --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<OfferAccept>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let tx = &mempool[0];
+
+  assert_eq!(tx.compute_txid(), accept.txid);
+  assert_eq!(tx.input[1].previous_output, outpoint);
+  assert_eq!(tx.input[1].witness.nth(0).unwrap()[64], SINGLE_ANYONECANPAY);
+  assert_eq!(tx.output[1], decode(&psbt).unsigned_tx.output[0]);
+
+  core.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(
+    inscriptions[0].location.outpoint,
+    OutPoint {
+      txid: accept.txid,
+      vout: 0,
+    }
+  );
+}
+
+#[test]
+fn accept_checks_offer() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, outpoint) = external_inscription(&core);
+
+  let other = InscriptionId {
+    txid: inscription.txid,
+    index: 1,
+  };
+
+  #[track_caller]
+  fn case(core: &mockcore::Handle, ord: &TestServer, args: String, expected: &str) {
+    CommandBuilder::new(format!("wallet offer accept --fee-rate 1 {args}"))
+      .core(core)
+      .ord(ord)
+      .stderr_regex(format!("error: {expected}\n"))
+      .expected_exit_code(1)
+      .run_and_extract_stdout();
+  }
+
+  let signed = offer(
+    outpoint,
+    Amount::from_sat(COIN_VALUE),
+    Some(SINGLE_ANYONECANPAY),
+  );
+
+  case(
+    &core,
+    &ord,
+    format!(
+      "--psbt {} --inscription {inscription} --amount 1btc",
+      offer(outpoint, Amount::from_sat(COIN_VALUE), None)
+    ),
+    "offer input is not signed",
+  );
+
+  case(
+    &core,
+    &ord,
+    format!(
+      "--psbt {} --inscription {inscription} --amount 1btc",
+      offer(outpoint, Amount::from_sat(COIN_VALUE), Some(0x01))
+    ),
+    "offer input must be a taproot key path spend signed with \
+    SIGHASH_SINGLE\\|SIGHASH_ANYONECANPAY",
+  );
+
+  case(
+    &core,
+    &ord,
+    format!("--psbt {signed} --inscription {inscription} --amount 0.5btc"),
+    "offer price .* does not match expected price .*",
+  );
+
+  case(
+    &core,
+    &ord,
+    format!("--psbt {signed} --inscription {other} --amount 1btc"),
+    &format!("offer output {outpoint} does not hold exactly inscription {other}"),
+  );
+
+  case(
+    &core,
+    &ord,
+    format!("--psbt {signed} --rune 1:AAAAAAAAAAAAA --amount 1btc"),
+    &format!("offer output {outpoint} also holds inscriptions"),
+  );
+}
+
+#[test]
+fn rune_offer_round_trip() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 500:{rune} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: wallet contains no output holding exactly 500:{rune} and nothing else, use \
+    `ord wallet split` to create one\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 1000:{rune} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<OfferCreate>();
+
+  let inspect = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer inspect --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<OfferInspect>();
+
+  assert!(inspect.inscriptions.is_empty());
+  assert_eq!(inspect.outpoint, create.outpoint);
+  assert_eq!(
+    inspect.runes,
+    [(SpacedRune::new(rune, 0), "1000".parse().unwrap())].into()
+  );
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --fee-rate 1 --psbt {} --rune 1000:{rune} \
+    --amount 1btc",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: offer output {} is already in wallet\n",
+    create.outpoint
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -1,6 +1,6 @@
 use {
   super::*,
-  ord::subcommand::wallet::sats::{OutputAll, OutputRare, OutputTsv},
+  ord::subcommand::wallet::sats::{extract, OutputAll, OutputRare, OutputTsv},
 };
 
 #[test]
@@ -120,3 +120,84 @@ fn sats_all() {
     .collect::<Vec<OutputAll>>()
   );
 }
+
+#[test]
+fn extract_rare_sats() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-sats wallet sats extract --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<extract::Output>();
+
+  assert_eq!(output.outputs.len(), 2);
+
+  assert_eq!(
+    output.outputs[0],
+    extract::PlannedOutput {
+      output: OutPoint {
+        txid: output.txid,
+        vout: 0,
+      },
+      value: 10_000,
+      sats: vec![Sat(50 * COIN_VALUE)],
+      ranges: vec![format!("{}-{}", 50 * COIN_VALUE, 50 * COIN_VALUE + 10_000)],
+    }
+  );
+
+  assert!(output.outputs[1].sats.is_empty());
+  assert_eq!(
+    output.outputs[1].value + output.fee,
+    50 * COIN_VALUE - 10_000
+  );
+
+  core.mine_blocks(1);
+
+  let rare = CommandBuilder::new("--index-sats wallet sats")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputRare>>();
+
+  assert!(rare.iter().any(|rare| rare.sat == 50 * COIN_VALUE
+    && rare.output
+      == OutPoint {
+        txid: output.txid,
+        vout: 0,
+      }
+    && rare.offset == 0));
+}
+
+#[test]
+fn extract_sats_from_tsv() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let sat = 50 * COIN_VALUE + 500;
+
+  let output = CommandBuilder::new("--index-sats wallet sats extract --fee-rate 1 --tsv foo.tsv")
+    .write("foo.tsv", format!("{sat}\n"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<extract::Output>();
+
+  assert_eq!(output.outputs.len(), 3);
+  assert_eq!(output.outputs[0].value, 500);
+  assert!(output.outputs[0].sats.is_empty());
+  assert_eq!(output.outputs[1].value, 10_000);
+  assert_eq!(output.outputs[1].sats, [Sat(sat)]);
+  assert_eq!(
+    output.outputs[1].ranges,
+    [format!("{sat}-{}", sat + 10_000)]
+  );
+}
@@ -364,6 +364,7 @@ inscriptions:
         },
       ],
       indexed: true,
+      pending_spend: None,
       runes: BTreeMap::new(),
       sat_ranges: Some(vec![(5_000_000_000, 5_000_030_000)]),
       script_pubkey: destination.assume_checked_ref().script_pubkey(),
@@ -787,6 +788,31 @@ fn sending_rune_works() {
   );
 }
 
+#[test]
+fn sending_rune_signals_replaceability() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), output.txid);
+  assert!(mempool[0].is_explicitly_rbf());
+}
+
 #[test]
 fn sending_rune_with_change_works() {
   let core = mockcore::builder().network(Network::Regtest).build();
@@ -1319,3 +1345,146 @@ fn error_messages_use_spaced_runes() {
     .expected_stderr("error: rune `FOO` has not been etched\n")
     .run_and_extract_stdout();
 }
+
+#[test]
+fn send_sat_range() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let start = 50 * COIN_VALUE + 1_000;
+  let end = start + 10_000;
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {start}-{end}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  assert_eq!(
+    output.asset,
+    Outgoing::SatRange {
+      start: Sat(start),
+      end: Sat(end),
+    }
+  );
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.input.len(), 1);
+  assert_eq!(tx.output[0].value.to_sat(), 1_000);
+  assert_eq!(tx.output[1].value.to_sat(), 10_000);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/output/{}:1", output.txid));
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let output_json: api::Output = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(output_json.sat_ranges, Some(vec![(start, end)]));
+  assert_eq!(
+    output_json.address,
+    Some(
+      "bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv"
+        .parse()
+        .unwrap()
+    )
+  );
+}
+
+#[test]
+fn send_sat_range_after_dust() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let start = 50 * COIN_VALUE + 100;
+  let end = start + 10_000;
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {start}-{end}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.input.len(), 2);
+  assert_eq!(tx.output[0].value.to_sat(), 50 * COIN_VALUE + 100);
+  assert_eq!(tx.output[1].value.to_sat(), 10_000);
+
+  core.mine_blocks(1);
+
+  let output_json: api::Output = serde_json::from_str(
+    &ord
+      .json_request(format!("/output/{}:1", output.txid))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(output_json.sat_ranges, Some(vec![(start, end)]));
+}
+
+#[test]
+fn send_sat_range_not_in_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 0-10000",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: could not find sat range `0-10000` in wallet outputs\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn send_sat_range_with_inscription() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (_inscription, txid) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let sat = sats(&core, &ord)
+    .into_iter()
+    .find(|s| s.output.txid == txid)
+    .unwrap()
+    .sat;
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {}-{}",
+    sat.n(),
+    sat.n() + 1_000,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: inscriptions must be sent by inscription ID\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
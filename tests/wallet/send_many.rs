@@ -0,0 +1,357 @@
+use super::*;
+
+#[test]
+fn send_bitcoin_inscription_and_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  let etched = etch(&core, &ord, rune);
+
+  let inscription = etched.output.inscriptions[0].id;
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes wallet send-many --fee-rate 1 --file recipients.yaml",
+  )
+  .core(&core)
+  .ord(&ord)
+  .write(
+    "recipients.yaml",
+    format!(
+      "
+outputs:
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  value: 1000 sat
+- address: bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k
+  inscriptions:
+  - {inscription}
+  runes:
+    {rune}: 100
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  runes:
+    {rune}: 200
+"
+    ),
+  )
+  .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(output.len(), 1);
+
+  let txid = output[0].txid;
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let tx = &mempool[0];
+
+  assert_eq!(tx.compute_txid(), txid);
+
+  pretty_assert_eq!(
+    Runestone::decipher(tx),
+    Some(Artifact::Runestone(Runestone {
+      edicts: vec![
+        Edict {
+          id: etched.id,
+          amount: 100,
+          output: 1,
+        },
+        Edict {
+          id: etched.id,
+          amount: 200,
+          output: 4,
+        },
+      ],
+      pointer: Some(2),
+      ..default()
+    })),
+  );
+
+  assert_eq!(tx.output[1].value.to_sat(), 10_000);
+  assert_eq!(tx.output[2].value.to_sat(), 10_000);
+  assert_eq!(tx.output[3].value.to_sat(), 1000);
+  assert_eq!(tx.output[4].value.to_sat(), 294);
+  assert_eq!(tx.output.len(), 6);
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(
+      ".*<dt>location</dt>
+  <dd><a class=collapse href=/satpoint/{txid}:1:0>{txid}:1:0</a></dd>.*",
+    ),
+  );
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balances>();
+
+  pretty_assert_eq!(
+    balances.runes[&SpacedRune::new(rune, 0)]
+      .iter()
+      .map(|(outpoint, pile)| (*outpoint, pile.amount))
+      .collect::<BTreeMap<OutPoint, u128>>(),
+    [
+      (OutPoint { txid, vout: 1 }, 100),
+      (OutPoint { txid, vout: 2 }, 700),
+      (OutPoint { txid, vout: 4 }, 200),
+    ]
+    .into(),
+  );
+}
+
+fn rune_recipients(rune: Rune, count: usize) -> String {
+  let mut recipients = "outputs:\n".to_string();
+
+  for _ in 0..count {
+    recipients.push_str(&format!(
+      "- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw\n  runes:\n    {rune}: 1\n"
+    ));
+  }
+
+  recipients
+}
+
+#[test]
+fn runestones_over_size_limit_are_split_into_chained_transactions() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes wallet send-many --fee-rate 1 --file recipients.yaml",
+  )
+  .core(&core)
+  .ord(&ord)
+  .write("recipients.yaml", rune_recipients(rune, 40))
+  .run_and_deserialize_output::<SendMany>();
+
+  assert!(output.len() > 1);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), output.len());
+
+  for (parent, child) in output.iter().zip(&mempool[1..]) {
+    assert!(child
+      .input
+      .iter()
+      .any(|txin| txin.previous_output.txid == parent.txid));
+  }
+
+  for tx in &mempool {
+    assert!(tx.output[0].script_pubkey.len() <= 83);
+  }
+
+  core.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balances>();
+
+  let piles = &balances.runes[&SpacedRune::new(rune, 0)];
+
+  assert_eq!(piles.len(), 41);
+  assert_eq!(piles.values().map(|pile| pile.amount).sum::<u128>(), 1000);
+  assert_eq!(piles.values().filter(|pile| pile.amount == 1).count(), 40);
+}
+
+#[test]
+fn dry_run_does_not_broadcast() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes wallet send-many --fee-rate 1 --dry-run --file recipients.yaml",
+  )
+  .core(&core)
+  .ord(&ord)
+  .write("recipients.yaml", rune_recipients(rune, 40))
+  .run_and_deserialize_output::<SendMany>();
+
+  assert!(output.len() > 1);
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn sending_runes_requires_rune_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("--regtest wallet send-many --fee-rate 1 --file recipients.yaml")
+    .core(&core)
+    .ord(&ord)
+    .write("recipients.yaml", rune_recipients(Rune(RUNE), 1))
+    .expected_stderr(
+      "error: sending runes with `ord wallet send-many` requires index created with \
+      `--index-runes`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn rune_amounts_which_overflow_are_rejected() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  CommandBuilder::new(
+    "--regtest --index-runes wallet send-many --fee-rate 1 --file recipients.yaml",
+  )
+  .core(&core)
+  .ord(&ord)
+  .write(
+    "recipients.yaml",
+    format!(
+      "
+outputs:
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  runes:
+    {rune}: 1
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  runes:
+    {rune}: {}
+",
+      u128::MAX
+    ),
+  )
+  .expected_stderr(format!("error: total amount of {rune} sent overflows\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn invalid_recipients_are_rejected() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  #[track_caller]
+  fn case(core: &mockcore::Handle, ord: &TestServer, recipients: &str, expected: &str) {
+    CommandBuilder::new("wallet send-many --fee-rate 1 --file recipients.yaml")
+      .core(core)
+      .ord(ord)
+      .write("recipients.yaml", recipients)
+      .expected_stderr(format!("error: {expected}\n"))
+      .expected_exit_code(1)
+      .run_and_extract_stdout();
+  }
+
+  case(
+    &core,
+    &ord,
+    "outputs: []",
+    "recipients file must contain at least one output",
+  );
+
+  case(
+    &core,
+    &ord,
+    "
+outputs:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+",
+    "output 0 does not send anything",
+  );
+
+  case(
+    &core,
+    &ord,
+    &format!(
+      "
+outputs:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  value: 1000 sat
+  inscriptions:
+  - {inscription}
+"
+    ),
+    "output 0 sends inscriptions and cannot also specify a value",
+  );
+
+  case(
+    &core,
+    &ord,
+    &format!(
+      "
+outputs:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  inscriptions:
+  - {inscription}
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  inscriptions:
+  - {inscription}
+"
+    ),
+    &format!("inscription {inscription} listed more than once"),
+  );
+
+  let missing = InscriptionId {
+    txid: inscription.txid,
+    index: 1,
+  };
+
+  case(
+    &core,
+    &ord,
+    &format!(
+      "
+outputs:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  inscriptions:
+  - {missing}
+"
+    ),
+    &format!("inscription {missing} not in wallet"),
+  );
+
+  case(
+    &core,
+    &ord,
+    "
+outputs:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  value: 1 sat
+",
+    "output 0 value 0.00000001 BTC below dust threshold 0.00000294 BTC",
+  );
+}
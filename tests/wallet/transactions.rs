@@ -1,9 +1,16 @@
-use {super::*, ord::subcommand::wallet::transactions::Output};
+use {
+  super::*,
+  ord::subcommand::wallet::transactions::{Kind, Output},
+};
 
 #[test]
 fn transactions() {
   let core = mockcore::spawn();
-  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-addresses", "--index-address-history"],
+    &[],
+  );
 
   create_wallet(&core, &ord);
 
@@ -30,7 +37,11 @@ fn transactions() {
 #[test]
 fn transactions_with_limit() {
   let core = mockcore::spawn();
-  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-addresses", "--index-address-history"],
+    &[],
+  );
 
   create_wallet(&core, &ord);
 
@@ -65,3 +76,131 @@ fn transactions_with_limit() {
 
   assert_eq!(output.len(), 1);
 }
+
+#[test]
+fn transactions_are_annotated() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-addresses", "--index-address-history"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let reveal = output
+    .iter()
+    .find(|output| output.transaction == reveal)
+    .unwrap();
+
+  assert_eq!(reveal.kind, Kind::Inscribe);
+  assert_eq!(reveal.inscriptions_received, [inscription]);
+  assert!(reveal.inscriptions_sent.is_empty());
+  assert_eq!(reveal.net, 10_000);
+
+  let commit = core.tx_by_id(reveal.transaction).input[0]
+    .previous_output
+    .txid;
+
+  assert_eq!(
+    output
+      .iter()
+      .find(|output| output.transaction == commit)
+      .unwrap()
+      .kind,
+    Kind::Inscribe,
+  );
+
+  let send = output
+    .iter()
+    .find(|output| output.transaction == send.txid)
+    .unwrap();
+
+  assert_eq!(send.kind, Kind::Send);
+  assert_eq!(send.inscriptions_sent, [inscription]);
+  assert!(send.inscriptions_received.is_empty());
+  assert!(send.fee.unwrap() > 0);
+  assert_eq!(send.net, -10_000);
+}
+
+#[test]
+fn transactions_csv() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-addresses", "--index-address-history"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let csv = CommandBuilder::new("wallet transactions --csv transactions.csv")
+    .core(&core)
+    .ord(&ord)
+    .stdout_regex(".*")
+    .run_and_extract_file("transactions.csv");
+
+  let mut lines = csv.lines();
+
+  assert_eq!(
+    lines.next().unwrap(),
+    "transaction,confirmations,kind,net,fee,inscriptions_received,inscriptions_sent,\
+    runes_received,runes_sent",
+  );
+
+  assert!(lines.any(
+    |line| line.starts_with(&format!("{reveal},1,inscribe,10000,"))
+      && line.ends_with(&format!(",{inscription},,,"))
+  ));
+}
+
+#[test]
+fn transactions_without_address_history_index() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (_inscription, reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "warning: ord server index has no address history, inscriptions and runes will not be \
+      reported\n",
+    )
+    .run_and_deserialize_output::<Vec<Output>>();
+
+  let reveal = output
+    .iter()
+    .find(|output| output.transaction == reveal)
+    .unwrap();
+
+  assert_eq!(reveal.kind, Kind::Inscribe);
+  assert!(reveal.inscriptions_received.is_empty());
+}